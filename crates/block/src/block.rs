@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(mainnet)]
 use crate::genesis;
-use crate::{header::BlockHeader, ConvergenceBlock, GenesisBlock, ProposalBlock};
+use crate::{header::BlockHeader, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock};
 
 pub trait InnerBlock: std::fmt::Debug + Send {
     type Header;
@@ -48,6 +48,16 @@ impl Block {
         matches!(self, Block::Genesis { .. })
     }
 
+    /// The harvester certificate of the block. Proposal blocks are never
+    /// certified.
+    pub fn certificate(&self) -> Option<&Certificate> {
+        match self {
+            Block::Convergence { block } => block.certificate.as_ref(),
+            Block::Genesis { block } => block.certificate.as_ref(),
+            Block::Proposal { .. } => None,
+        }
+    }
+
     pub fn size(&self) -> usize {
         match self {
             Block::Convergence { block } => block
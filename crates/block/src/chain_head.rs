@@ -0,0 +1,46 @@
+use std::sync::{Arc, RwLock};
+
+use primitives::Epoch;
+use serde::{Deserialize, Serialize};
+
+use crate::BlockHash;
+
+/// Summary of the last confirmed block along with the roots of the stores
+/// after it was applied.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChainHeadSummary {
+    pub tip_hash: BlockHash,
+    pub height: u128,
+    pub state_root_hash: String,
+    pub transactions_root_hash: String,
+    pub claims_root_hash: String,
+    pub timestamp: i64,
+    pub epoch: Epoch,
+}
+
+/// Shared view of the latest `ChainHeadSummary` published by the node, for
+/// components that have no access to the DAG, like the JSON-RPC server.
+#[derive(Clone, Debug, Default)]
+pub struct ChainHeadReadHandle {
+    summary: Arc<RwLock<ChainHeadSummary>>,
+}
+
+impl ChainHeadReadHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the most recently published summary.
+    pub fn summary(&self) -> ChainHeadSummary {
+        self.summary
+            .read()
+            .map(|summary| summary.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn publish(&self, summary: ChainHeadSummary) {
+        if let Ok(mut current) = self.summary.write() {
+            *current = summary;
+        }
+    }
+}
@@ -7,6 +7,10 @@ use crate::Block;
 pub enum BlockError {
     #[error("certificate already exists for block {0:?}")]
     CertificateExists(Block),
+    #[error("genesis mints {minted} tokens but the total supply is {total_supply}")]
+    GenesisSupplyMismatch { minted: u128, total_supply: u128 },
+    #[error("invalid inclusion proof: {0}")]
+    InvalidInclusionProof(String),
     #[error("{0}")]
     Other(String),
 }
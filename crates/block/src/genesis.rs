@@ -1,5 +1,4 @@
 use primitives::Address;
-#[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
 use ritelinked::LinkedHashMap;
 use serde::{Deserialize, Serialize};
@@ -11,6 +10,34 @@ use crate::{error::BlockError, header::BlockHeader, BlockHash, Certificate, Clai
 #[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
 pub struct GenesisRewards(pub LinkedHashMap<GenesisReceiver, u128>);
 
+impl GenesisRewards {
+    /// Sum of the amounts allocated to genesis receivers, or `None` if it
+    /// overflows.
+    pub fn total_allocated(&self) -> Option<u128> {
+        self.0
+            .values()
+            .try_fold(0u128, |total, amount| total.checked_add(*amount))
+    }
+
+    /// Checks that the genesis allocations plus `GENESIS_REWARD` mint
+    /// exactly `total_supply`.
+    pub fn validate_total_supply(&self, total_supply: u128) -> Result<(), BlockError> {
+        let minted = self
+            .total_allocated()
+            .and_then(|allocated| allocated.checked_add(GENESIS_REWARD))
+            .ok_or_else(|| BlockError::Other("genesis allocations overflow".to_string()))?;
+
+        if minted != total_supply {
+            return Err(BlockError::GenesisSupplyMismatch {
+                minted,
+                total_supply,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
 #[repr(C)]
 pub struct GenesisBlock {
@@ -26,6 +26,11 @@ pub struct BlockHeader {
     pub block_height: u128,
     pub timestamp: i64,
     pub txn_hash: String,
+    /// Merkle root of the block's consolidated transactions, which
+    /// transaction inclusion proofs are checked against. Headers built
+    /// before the root was introduced don't carry one.
+    #[serde(default)]
+    pub txn_root: Option<String>,
     pub miner_claim: Claim,
     pub claim_list_hash: String,
     pub block_reward: Reward,
@@ -130,6 +135,7 @@ impl BlockHeader {
             block_height: 0,
             timestamp,
             txn_hash,
+            txn_root: None,
             miner_claim,
             claim_list_hash,
             block_reward,
@@ -221,6 +227,7 @@ impl BlockHeader {
             block_height: last_block.get_header().block_height + 1,
             timestamp,
             txn_hash,
+            txn_root: None,
             miner_claim,
             claim_list_hash,
             block_reward,
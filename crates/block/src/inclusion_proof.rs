@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use primitives::NodeId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use signer::engine::SignerEngine;
+use utils::hash_data;
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{
+    error::BlockError, header::BlockHeader, BlockHash, Certificate, ConsolidatedTxns, RefHash,
+};
+
+/// Proof that a transaction was included in a confirmed convergence block.
+///
+/// The header's `txn_root` is the Merkle root of the block's consolidated
+/// transactions, the block hash commits to the header and the certificate
+/// proves the harvester quorum finalized the block. A client holding the
+/// harvester quorum membership can check every link with `verify` without
+/// trusting the node that produced the proof. The proof only carries the
+/// Merkle path of the transaction, so its size grows logarithmically with the
+/// number of transactions in the block.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InclusionProof {
+    pub txn_digest: TransactionDigest,
+    pub block_hash: BlockHash,
+    pub header: BlockHeader,
+    pub certificate: Certificate,
+    /// Hash of the proposal block that included the transaction
+    pub ref_hash: RefHash,
+    /// Siblings of the transaction's leaf, from the leaf up to the root
+    pub txn_path: Vec<MerkleSibling>,
+}
+
+/// A sibling along a Merkle path, and the side it is hashed on.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MerkleSibling {
+    Left([u8; 32]),
+    Right([u8; 32]),
+}
+
+impl InclusionProof {
+    /// Verifies the proof against the harvester quorum known to `sig_engine`.
+    pub fn verify(&self, sig_engine: &SignerEngine) -> Result<(), BlockError> {
+        let root = self.txn_path.iter().fold(
+            txn_leaf(&self.ref_hash, &self.txn_digest),
+            |node, sibling| match sibling {
+                MerkleSibling::Left(left) => merkle_node(left, &node),
+                MerkleSibling::Right(right) => merkle_node(&node, right),
+            },
+        );
+
+        let txn_root = self
+            .header
+            .txn_root
+            .as_ref()
+            .ok_or_else(|| invalid_proof("block doesn't commit to a transaction root"))?;
+
+        if hex::encode(root) != *txn_root {
+            return Err(invalid_proof(format!(
+                "transaction {} is not part of the block's transactions",
+                self.txn_digest
+            )));
+        }
+
+        if hash_block_header(&self.header) != self.block_hash {
+            return Err(invalid_proof("header doesn't match the block hash"));
+        }
+
+        if self.certificate.block_hash != self.block_hash {
+            return Err(invalid_proof("certificate was issued for another block"));
+        }
+
+        self.verify_certificate(sig_engine)
+    }
+
+    fn verify_certificate(&self, sig_engine: &SignerEngine) -> Result<(), BlockError> {
+        let quorum_members = sig_engine.quorum_members();
+        let harvesters = quorum_members
+            .get_harvester_data()
+            .ok_or(invalid_proof("harvester quorum members are unknown"))?;
+
+        let mut signers: HashSet<&NodeId> = HashSet::new();
+        for (node_id, _) in self.certificate.signatures.iter() {
+            if !harvesters.members.contains_key(node_id) || !signers.insert(node_id) {
+                return Err(invalid_proof(format!(
+                    "certificate signer {node_id} is not a distinct harvester quorum member"
+                )));
+            }
+        }
+
+        if signers.len() < quorum_members.get_harvester_threshold() {
+            return Err(invalid_proof(
+                "certificate does not reach the signature threshold",
+            ));
+        }
+
+        sig_engine
+            .verify_batch(&self.certificate.signatures, &self.block_hash)
+            .map_err(|err| invalid_proof(format!("invalid certificate signature: {err}")))
+    }
+}
+
+/// Hashes a block's consolidated transactions into `BlockHeader::txn_hash`.
+pub fn hash_consolidated_txns(txns: &ConsolidatedTxns) -> String {
+    let mut txn_hasher = Sha256::new();
+    if let Ok(serialized_txns) = serde_json::to_string(txns) {
+        txn_hasher.update(serialized_txns.as_bytes());
+    }
+
+    format!("{:x}", txn_hasher.finalize())
+}
+
+/// Merkle root of a block's consolidated transactions, the value of
+/// `BlockHeader::txn_root`.
+///
+/// Leaves are the `(ref_hash, digest)` pairs of the transactions, in the order
+/// they appear in `txns`. A node left without a sibling is carried up to the
+/// next level unchanged.
+pub fn txn_merkle_root(txns: &ConsolidatedTxns) -> String {
+    let mut level = txn_leaves(txns);
+    while level.len() > 1 {
+        level = merkle_level(&level);
+    }
+
+    let root = level.pop().unwrap_or_else(|| Sha256::digest(b"").into());
+
+    hex::encode(root)
+}
+
+/// Merkle path from the leaf of `txn_digest` to the root of `txns`, along
+/// with the hash of the proposal block that included it, or `None` if `txns`
+/// doesn't contain the transaction.
+pub fn txn_inclusion_path(
+    txns: &ConsolidatedTxns,
+    txn_digest: &TransactionDigest,
+) -> Option<(RefHash, Vec<MerkleSibling>)> {
+    let ref_hash = txns
+        .iter()
+        .find(|(_, digests)| digests.contains(txn_digest))
+        .map(|(ref_hash, _)| ref_hash.clone())?;
+
+    let leaf = txn_leaf(&ref_hash, txn_digest);
+    let mut level = txn_leaves(txns);
+    let mut index = level.iter().position(|node| *node == leaf)?;
+    let mut path = Vec::new();
+
+    while level.len() > 1 {
+        if index % 2 == 1 {
+            path.push(MerkleSibling::Left(level[index - 1]));
+        } else if let Some(right) = level.get(index + 1) {
+            path.push(MerkleSibling::Right(*right));
+        }
+
+        level = merkle_level(&level);
+        index /= 2;
+    }
+
+    Some((ref_hash, path))
+}
+
+fn merkle_level(nodes: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    nodes
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => merkle_node(left, right),
+            [node] => *node,
+            _ => unreachable!("chunks of two are never empty"),
+        })
+        .collect()
+}
+
+fn txn_leaves(txns: &ConsolidatedTxns) -> Vec<[u8; 32]> {
+    txns.iter()
+        .flat_map(|(ref_hash, digests)| {
+            digests.iter().map(move |digest| txn_leaf(ref_hash, digest))
+        })
+        .collect()
+}
+
+// Leaves and inner nodes are domain separated so an inner node can't be
+// passed off as a leaf.
+fn txn_leaf(ref_hash: &RefHash, txn_digest: &TransactionDigest) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update((ref_hash.len() as u64).to_be_bytes());
+    hasher.update(ref_hash.as_bytes());
+    hasher.update(txn_digest.digest_string().as_bytes());
+    hasher.finalize().into()
+}
+
+fn merkle_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Hash of a block, computed from the fields of its header.
+///
+/// Headers without a `txn_root` hash the same way they did before the root
+/// was introduced, so the hashes of blocks built back then don't change.
+pub fn hash_block_header(header: &BlockHeader) -> BlockHash {
+    let block_hash = match &header.txn_root {
+        None => hash_data!(
+            header.ref_hashes,
+            header.round,
+            header.block_seed,
+            header.next_block_seed,
+            header.block_height,
+            header.timestamp,
+            header.txn_hash,
+            header.miner_claim,
+            header.claim_list_hash,
+            header.block_reward,
+            header.next_block_reward,
+            header.miner_signature
+        ),
+        Some(txn_root) => hash_data!(
+            header.ref_hashes,
+            header.round,
+            header.block_seed,
+            header.next_block_seed,
+            header.block_height,
+            header.timestamp,
+            header.txn_hash,
+            header.miner_claim,
+            header.claim_list_hash,
+            header.block_reward,
+            header.next_block_reward,
+            header.miner_signature,
+            txn_root
+        ),
+    };
+
+    format!("{block_hash:x}")
+}
+
+fn invalid_proof(reason: impl Into<String>) -> BlockError {
+    BlockError::InvalidInclusionProof(reason.into())
+}
@@ -1,12 +1,17 @@
 pub mod block;
+pub mod chain_head;
 pub mod convergence_block;
 pub mod error;
 pub mod genesis;
 pub mod header;
+pub mod inclusion_proof;
 pub mod proposal_block;
 mod types;
 
-pub use crate::{block::*, convergence_block::*, genesis::*, proposal_block::*, types::*};
+pub use crate::{
+    block::*, chain_head::*, convergence_block::*, genesis::*, inclusion_proof::*,
+    proposal_block::*, types::*,
+};
 
 pub mod valid {
     use primitives::{ByteVec, NodeId, Signature, SignatureType};
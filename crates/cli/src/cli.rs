@@ -1,9 +1,11 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
+use telemetry::custom_subscriber::LogFormat;
 
 use crate::commands::dev::DevOpts;
 use crate::commands::faucet::FaucetOpts;
+use crate::commands::state::StateOpts;
 use crate::commands::{config::ConfigOpts, keygen::KeygenCmd, node::NodeOpts, wallet::WalletOpts};
 
 #[derive(Parser, Debug)]
@@ -21,6 +23,11 @@ pub struct Args {
     #[clap(short, long, default_value = "local")]
     pub network: String,
 
+    /// Format of the logs written to stdout, either pretty or json. Defaults to json
+    /// unless pretty printed logs are enabled in the environment
+    #[clap(long, value_parser, value_name = "FORMAT")]
+    pub log_format: Option<LogFormat>,
+
     #[clap(subcommand)]
     pub command: Option<Commands>,
 }
@@ -44,4 +51,7 @@ pub enum Commands {
 
     /// Start a faucet server to transfer tokens to accounts
     Faucet(FaucetOpts),
+
+    /// Inspect the database of a stopped node
+    State(StateOpts),
 }
@@ -150,6 +150,7 @@ impl From<RunOpts> for NodeConfig {
             http_api_version: opts.http_api_version,
             http_api_shutdown_timeout: default_node_config.http_api_shutdown_timeout,
             jsonrpc_server_address: opts.jsonrpc_api_address,
+            admin_jsonrpc_server_address: default_node_config.admin_jsonrpc_server_address,
             preload_mock_state: default_node_config.preload_mock_state,
             bootstrap_config,
             bootstrap_peer_data: None,
@@ -167,7 +168,17 @@ impl From<RunOpts> for NodeConfig {
             public_ip_address: opts.raptorq_gossip_address,
             quorum_config: default_node_config.quorum_config,
             enable_block_indexing: default_node_config.enable_block_indexing,
+            maintenance_mode: default_node_config.maintenance_mode,
             threshold_config: default_node_config.threshold_config,
+            proposal_fairness: default_node_config.proposal_fairness,
+            mempool: default_node_config.mempool,
+            mempool_pressure: default_node_config.mempool_pressure,
+            genesis_total_supply: default_node_config.genesis_total_supply,
+            genesis_allocations: default_node_config.genesis_allocations,
+            signature_collection_deadline: default_node_config.signature_collection_deadline,
+            event_send_timeout: default_node_config.event_send_timeout,
+            enable_event_wal: default_node_config.enable_event_wal,
+            event_audit_log_retention: default_node_config.event_audit_log_retention,
             whitelisted_nodes: default_node_config.whitelisted_nodes,
             prometheus_bind_addr: default_node_config.prometheus_bind_addr,
             prometheus_bind_port: default_node_config.prometheus_bind_port,
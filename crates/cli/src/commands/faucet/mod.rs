@@ -14,7 +14,7 @@ pub enum FaucetCmd {
     Run,
 }
 
-#[derive(Parser, Debug)]
+#[derive(Parser)]
 pub struct FaucetOpts {
     #[clap(subcommand)]
     pub subcommand: FaucetCmd,
@@ -31,6 +31,17 @@ pub struct FaucetOpts {
     pub host_port: String,
 }
 
+impl std::fmt::Debug for FaucetOpts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaucetOpts")
+            .field("subcommand", &self.subcommand)
+            .field("rpc_server_address", &self.rpc_server_address)
+            .field("secret_key", &"<redacted>")
+            .field("host_port", &self.host_port)
+            .finish()
+    }
+}
+
 pub async fn exec(args: FaucetOpts) -> Result<()> {
     match args.subcommand {
         FaucetCmd::Run => {
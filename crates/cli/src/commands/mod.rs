@@ -3,6 +3,7 @@ pub mod dev;
 pub mod faucet;
 pub mod keygen;
 pub mod node;
+pub mod state;
 pub mod utils;
 pub mod wallet;
 
@@ -12,8 +13,6 @@ use crate::{
 };
 
 pub async fn exec(args: Args) -> Result<()> {
-    telemetry::debug!("args: {:?}", args);
-
     let cmd = args.command;
 
     match cmd {
@@ -22,6 +21,7 @@ pub async fn exec(args: Args) -> Result<()> {
         Some(Commands::Wallet(wallet_args)) => wallet::exec(wallet_args).await,
         Some(Commands::Keygen(keygen_args)) => keygen::exec(keygen_args),
         Some(Commands::Faucet(faucet_args)) => faucet::exec(faucet_args).await,
+        Some(Commands::State(state_args)) => state::exec(state_args),
         None => Err(CliError::NoSubcommand),
         _ => Err(CliError::InvalidCommand(format!("{cmd:?}"))),
     }
@@ -0,0 +1,130 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use primitives::Address;
+use storage::{
+    storage_utils::StorageError,
+    vrrbdb::{VrrbDb, VrrbDbConfig},
+};
+
+use crate::result::{CliError, Result};
+
+#[derive(Debug, Subcommand)]
+pub enum StateCmd {
+    /// Prints the balance of an account
+    Balance {
+        /// Address of the account, as 0x prefixed hex
+        address: String,
+    },
+
+    /// Prints the root hashes of the state, transaction and claim stores
+    RootHash,
+
+    /// Writes a snapshot of the database to a file, which a node can import
+    Export {
+        /// File the snapshot is written to
+        path: PathBuf,
+    },
+}
+
+#[derive(Parser, Debug)]
+pub struct StateOpts {
+    /// Database directory of the node to inspect. The node must be stopped.
+    /// Defaults to the database within VRRB's data directory
+    #[clap(long, value_parser, value_name = "DIR")]
+    pub db_path: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    pub subcommand: StateCmd,
+}
+
+/// Root hashes of the stores of a node's database, hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootHashes {
+    pub state: String,
+    pub transactions: String,
+    pub claims: String,
+}
+
+pub fn exec(args: StateOpts) -> Result<()> {
+    let db_path = args.db_path.unwrap_or_else(|| VrrbDbConfig::default().path);
+
+    match args.subcommand {
+        StateCmd::Balance { address } => {
+            let address = address
+                .parse::<Address>()
+                .map_err(|err| CliError::OptsError(format!("invalid address {address}: {err}")))?;
+
+            println!("{}", balance(&db_path, &address)?);
+        }
+        StateCmd::RootHash => {
+            let root_hashes = root_hashes(&db_path)?;
+
+            println!("state: {}", root_hashes.state);
+            println!("transactions: {}", root_hashes.transactions);
+            println!("claims: {}", root_hashes.claims);
+        }
+        StateCmd::Export { path } => {
+            export(&db_path, &path)?;
+
+            println!("Exported state to {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens the database at `db_path`, failing rather than creating an empty
+/// database when there's none.
+fn open_db(db_path: &Path) -> Result<VrrbDb> {
+    if !db_path.is_dir() {
+        return Err(CliError::OptsError(format!(
+            "no database found at {}",
+            db_path.display()
+        )));
+    }
+
+    Ok(VrrbDb::new(
+        VrrbDbConfig::default().with_path(db_path.to_path_buf()),
+    ))
+}
+
+fn storage_error(err: StorageError) -> CliError {
+    CliError::Other(err.to_string())
+}
+
+/// Returns the balance, credits minus debits, of `address` in the database
+/// at `db_path`.
+pub fn balance(db_path: &Path, address: &Address) -> Result<u128> {
+    let account = open_db(db_path)?
+        .state_store_factory()
+        .handle()
+        .get(address)
+        .map_err(storage_error)?;
+
+    Ok(account.credits().saturating_sub(account.debits()))
+}
+
+/// Returns the root hashes of the stores of the database at `db_path`.
+pub fn root_hashes(db_path: &Path) -> Result<RootHashes> {
+    let db = open_db(db_path)?;
+
+    Ok(RootHashes {
+        state: hex::encode(db.state_root_hash().map_err(storage_error)?.0),
+        transactions: hex::encode(db.transactions_root_hash().map_err(storage_error)?.0),
+        claims: hex::encode(db.claims_root_hash().map_err(storage_error)?.0),
+    })
+}
+
+/// Writes a snapshot of the database at `db_path` to `path`, in the format
+/// read by `StateManager::import_snapshot`.
+pub fn export(db_path: &Path, path: &Path) -> Result<()> {
+    let snapshot = open_db(db_path)?.snapshot().map_err(storage_error)?;
+
+    let bytes = bincode::serialize(&snapshot)
+        .map_err(|err| CliError::Other(format!("failed to serialize snapshot: {err}")))?;
+
+    std::fs::write(path, bytes)?;
+
+    Ok(())
+}
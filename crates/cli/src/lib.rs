@@ -4,14 +4,31 @@ use telemetry::tracing;
 mod cli;
 pub mod result;
 
+pub use crate::cli::Args;
 pub(crate) use crate::cli::*;
 pub mod commands;
 
-#[telemetry::instrument]
-pub async fn run() -> anyhow::Result<()> {
-    let args = Args::parse();
+/// Parses the command line arguments.
+pub fn parse_args() -> Args {
+    Args::parse()
+}
 
+#[telemetry::instrument(skip_all)]
+pub async fn run(args: Args) -> anyhow::Result<()> {
+    log_startup(&args);
     commands::exec(args).await?;
 
     Ok(())
 }
+
+/// Logs the arguments the CLI was started with. Secrets are redacted by the
+/// `Debug` implementations of the options holding them.
+pub fn log_startup(args: &Args) {
+    telemetry::info!(
+        config = ?args.config,
+        debug = args.debug,
+        network = %args.network,
+        command = ?args.command,
+        "starting versa"
+    );
+}
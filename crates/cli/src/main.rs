@@ -1,10 +1,13 @@
-use telemetry::custom_subscriber::TelemetrySubscriber;
+use telemetry::custom_subscriber::{LogFormat, TelemetrySubscriber};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    TelemetrySubscriber::init(std::io::stdout)?;
+    let args = cli::parse_args();
 
-    cli::run().await?;
+    let log_format = args.log_format.unwrap_or_else(LogFormat::from_env);
+    TelemetrySubscriber::init_with_format(std::io::stdout, log_format)?;
+
+    cli::run(args).await?;
 
     Ok(())
 }
@@ -0,0 +1,53 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use clap::Parser;
+use cli::{log_startup, Args};
+use telemetry::custom_subscriber::{LogFormat, TelemetrySubscriber};
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn startup_logs_parsed_args_without_secrets() {
+    let args = Args::parse_from([
+        "versa",
+        "--network",
+        "testnet",
+        "faucet",
+        "--secret-key",
+        "0badc0ffee",
+        "run",
+    ]);
+
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let sub = TelemetrySubscriber::builder()
+        .format(LogFormat::Json)
+        .build(move || writer.clone());
+
+    telemetry::subscriber::with_default(sub, || log_startup(&args));
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let event: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+
+    assert_eq!(event["message"], "starting versa");
+    assert_eq!(event["level"], "INFO");
+    assert_eq!(event["network"], "testnet");
+    assert_eq!(event["debug"], false);
+    assert!(event["command"].as_str().unwrap().contains("Faucet"));
+    assert!(!output.contains("0badc0ffee"));
+    assert!(!output.contains("[crates/cli/src"));
+}
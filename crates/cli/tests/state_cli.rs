@@ -0,0 +1,41 @@
+use cli::commands::state::{balance, root_hashes};
+use primitives::Address;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+use vrrb_core::{
+    account::{Account, AccountField},
+    keypair::Keypair,
+};
+
+fn random_address() -> Address {
+    Address::new(Keypair::random().miner_public_key_owned())
+}
+
+#[test]
+fn state_balance_reads_seeded_account() {
+    let db_path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let address = random_address();
+
+    let mut account = Account::new(address.clone());
+    account.set_credits(1_000);
+    account.update_field(AccountField::Debits(250)).unwrap();
+
+    let state_root_hash = {
+        let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path.clone()));
+        db.insert_account(address.clone(), account).unwrap();
+        db.commit_state();
+
+        hex::encode(db.state_root_hash().unwrap().0)
+    };
+
+    assert_eq!(balance(&db_path, &address).unwrap(), 750);
+    assert!(balance(&db_path, &random_address()).is_err());
+    assert_eq!(root_hashes(&db_path).unwrap().state, state_root_hash);
+}
+
+#[test]
+fn state_commands_require_an_existing_db() {
+    let db_path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+
+    assert!(balance(&db_path, &random_address()).is_err());
+    assert!(!db_path.exists());
+}
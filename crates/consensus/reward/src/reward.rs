@@ -1,5 +1,8 @@
 //FEATURE TAG(S): Rewards, Block Structure
 
+use std::collections::BTreeMap;
+
+use primitives::Address;
 use serde::{Deserialize, Serialize};
 use vrrb_core::accountable::Accountable;
 
@@ -21,6 +24,92 @@ pub const MAX_BASELINE_REWARD: u128 = 25;
 pub const NUMBER_OF_BLOCKS_PER_EPOCH: u128 = 30000000;
 pub const GENESIS_REWARD: u128 = 400_000_000;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RewardError {
+    #[error("reward amount {amount} is outside of the valid range {min}..={max}")]
+    OutOfRange { amount: u128, min: u128, max: u128 },
+
+    #[error("reward amount {amount} does not match the expected amount {expected}")]
+    AmountMismatch { amount: u128, expected: u128 },
+
+    #[error("reward belongs to epoch {epoch} but epoch {expected} was expected")]
+    WrongEpoch { epoch: u128, expected: u128 },
+
+    #[error("genesis distribution has no receivers")]
+    NoGenesisReceivers,
+
+    #[error("genesis receiver {0:?} has a weight of zero")]
+    ZeroGenesisWeight(Address),
+
+    #[error("genesis receiver weights overflow")]
+    GenesisWeightOverflow,
+
+    #[error("genesis receivers are allocated {allocated} but the total is {total}")]
+    GenesisTotalMismatch { allocated: u128, total: u128 },
+}
+
+/// A validated set of genesis receivers and the amounts credited to them.
+///
+/// Allocations to the same address are added up and receivers are ordered by
+/// address, so the distribution doesn't depend on how the list was
+/// configured. The amounts always add up to the total the distribution was
+/// built for. There is no `Deserialize` impl on purpose: a distribution can
+/// only be obtained through `new`, which enforces that invariant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Hash)]
+pub struct GenesisDistribution {
+    receivers: Vec<(Address, u128)>,
+}
+
+impl GenesisDistribution {
+    pub fn new(receivers: Vec<(Address, u128)>, total: u128) -> Result<Self, RewardError> {
+        if receivers.is_empty() {
+            return Err(RewardError::NoGenesisReceivers);
+        }
+
+        if let Some((address, _)) = receivers.iter().find(|(_, weight)| *weight == 0) {
+            return Err(RewardError::ZeroGenesisWeight(address.clone()));
+        }
+
+        let mut merged: BTreeMap<Address, u128> = BTreeMap::new();
+        for (address, weight) in receivers {
+            let amount = merged.entry(address).or_insert(0);
+            *amount = amount
+                .checked_add(weight)
+                .ok_or(RewardError::GenesisWeightOverflow)?;
+        }
+
+        let allocated = merged
+            .values()
+            .try_fold(0u128, |sum, weight| sum.checked_add(*weight))
+            .ok_or(RewardError::GenesisWeightOverflow)?;
+
+        if allocated != total {
+            return Err(RewardError::GenesisTotalMismatch { allocated, total });
+        }
+
+        Ok(Self {
+            receivers: merged.into_iter().collect(),
+        })
+    }
+
+    /// The whole genesis reward goes to `miner`.
+    pub fn single(miner: Address) -> Self {
+        Self {
+            receivers: vec![(miner, GENESIS_REWARD)],
+        }
+    }
+
+    /// The amount credited to each receiver, ordered by address.
+    pub fn receivers(&self) -> &[(Address, u128)] {
+        &self.receivers
+    }
+
+    /// Sum of the amounts credited to all receivers.
+    pub fn total(&self) -> u128 {
+        self.receivers.iter().map(|(_, amount)| amount).sum()
+    }
+}
+
 /// `Reward` is a struct that contains the epoch, next epoch block, current
 /// block, miner, and amount.
 ///
@@ -122,7 +211,39 @@ impl Reward {
     ///
     /// A boolean value.
     pub fn valid_reward(&self) -> bool {
-        self.amount >= MIN_BASELINE_REWARD || self.amount <= MAX_BASELINE_REWARD
+        (MIN_BASELINE_REWARD..=MAX_BASELINE_REWARD).contains(&self.amount)
+    }
+
+    /// Validates the reward claimed by a block against `self`, the reward
+    /// the block is expected to carry (i.e. the previous block's
+    /// `next_block_reward`).
+    ///
+    /// Returns the precise reason for rejection so harvesters certifying a
+    /// convergence block can report why its reward is invalid.
+    pub fn validate_block_reward(&self, reward: &Reward) -> Result<(), RewardError> {
+        if !reward.valid_reward() {
+            return Err(RewardError::OutOfRange {
+                amount: reward.amount,
+                min: MIN_BASELINE_REWARD,
+                max: MAX_BASELINE_REWARD,
+            });
+        }
+
+        if reward.epoch != self.epoch {
+            return Err(RewardError::WrongEpoch {
+                epoch: reward.epoch,
+                expected: self.epoch,
+            });
+        }
+
+        if reward.amount != self.amount {
+            return Err(RewardError::AmountMismatch {
+                amount: reward.amount,
+                expected: self.amount,
+            });
+        }
+
+        Ok(())
     }
 }
 
@@ -160,9 +281,16 @@ impl Accountable for Reward {
 
 #[cfg(test)]
 mod tests {
-    use super::{Reward, BASELINE_REWARD};
+    use primitives::generate_account_keypair;
+    use primitives::Address;
+
+    use super::{GenesisDistribution, Reward, RewardError, BASELINE_REWARD, GENESIS_REWARD};
     use crate::reward::{MAX_BASELINE_REWARD, MIN_BASELINE_REWARD, NUMBER_OF_BLOCKS_PER_EPOCH};
 
+    fn address() -> Address {
+        Address::new(generate_account_keypair().1)
+    }
+
     #[test]
     fn test_reward_state_starting_point() {
         let reward = Reward::genesis(Some("MINER_1".to_string()));
@@ -187,4 +315,117 @@ mod tests {
         reward.reset();
         assert!(reward.amount == BASELINE_REWARD);
     }
+
+    #[test]
+    fn test_validate_block_reward_accepts_expected_reward() {
+        let expected = Reward::genesis(None);
+        let mut reward = expected.clone();
+        reward.miner = Some("MINER".to_string());
+        reward.current_block = 1;
+
+        assert_eq!(expected.validate_block_reward(&reward), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_block_reward_rejects_out_of_range_amount() {
+        let expected = Reward::genesis(None);
+        let mut reward = expected.clone();
+        reward.amount = MAX_BASELINE_REWARD + 1;
+
+        assert_eq!(
+            expected.validate_block_reward(&reward),
+            Err(RewardError::OutOfRange {
+                amount: MAX_BASELINE_REWARD + 1,
+                min: MIN_BASELINE_REWARD,
+                max: MAX_BASELINE_REWARD,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_block_reward_rejects_wrong_epoch() {
+        let expected = Reward::genesis(None);
+        let mut reward = expected.clone();
+        reward.epoch += 1;
+
+        assert_eq!(
+            expected.validate_block_reward(&reward),
+            Err(RewardError::WrongEpoch {
+                epoch: expected.epoch + 1,
+                expected: expected.epoch,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_block_reward_rejects_mismatched_amount() {
+        let expected = Reward::genesis(None);
+        let mut reward = expected.clone();
+        reward.amount = MIN_BASELINE_REWARD;
+
+        assert_eq!(
+            expected.validate_block_reward(&reward),
+            Err(RewardError::AmountMismatch {
+                amount: MIN_BASELINE_REWARD,
+                expected: BASELINE_REWARD,
+            })
+        );
+    }
+
+    #[test]
+    fn test_genesis_distribution_merges_and_orders_receivers() {
+        let (first, second) = {
+            let (a, b) = (address(), address());
+            if a < b {
+                (a, b)
+            } else {
+                (b, a)
+            }
+        };
+
+        let distribution = GenesisDistribution::new(
+            vec![
+                (second.clone(), GENESIS_REWARD / 4),
+                (first.clone(), GENESIS_REWARD / 2),
+                (second.clone(), GENESIS_REWARD / 4),
+            ],
+            GENESIS_REWARD,
+        )
+        .unwrap();
+
+        assert_eq!(distribution.total(), GENESIS_REWARD);
+        assert_eq!(
+            distribution.receivers(),
+            &[(first, GENESIS_REWARD / 2), (second, GENESIS_REWARD / 2)]
+        );
+    }
+
+    #[test]
+    fn test_genesis_distribution_rejects_invalid_weights() {
+        let miner = address();
+
+        assert_eq!(
+            GenesisDistribution::new(vec![], GENESIS_REWARD),
+            Err(RewardError::NoGenesisReceivers)
+        );
+        assert_eq!(
+            GenesisDistribution::new(vec![(address(), 1), (miner.clone(), 0)], 1),
+            Err(RewardError::ZeroGenesisWeight(miner.clone()))
+        );
+        assert_eq!(
+            GenesisDistribution::new(vec![(address(), u128::MAX), (address(), 1)], 0),
+            Err(RewardError::GenesisWeightOverflow)
+        );
+        assert_eq!(
+            GenesisDistribution::new(vec![(address(), 1), (address(), 1)], 3),
+            Err(RewardError::GenesisTotalMismatch {
+                allocated: 2,
+                total: 3
+            })
+        );
+        assert_eq!(
+            GenesisDistribution::single(miner.clone()).receivers(),
+            &[(miner, GENESIS_REWARD)]
+        );
+    }
 }
@@ -1,14 +1,38 @@
-use primitives::{NodeId, PublicKey, QuorumId, QuorumKind, SecretKey, Signature};
+use primitives::{
+    FarmerQuorumThreshold, NodeId, PublicKey, QuorumId, QuorumKind, SecretKey, Signature,
+};
 use secp256k1::Message;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::cmp::Ord;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::hash::Hasher;
+use vrrb_core::transactions::{QuorumCertifiedTxn, TransactionKind};
 
 pub const VALIDATION_THRESHOLD: f64 = 0.6;
 
+/// Returns how many distinct members of a quorum of `members` nodes have to
+/// sign for the quorum to reach `VALIDATION_THRESHOLD`. Rounds up, so a
+/// quorum never certifies anything with less than that share of its members.
+pub fn quorum_threshold(members: usize) -> usize {
+    (members as f64 * VALIDATION_THRESHOLD).ceil() as usize
+}
+
+/// Builds the payload a farmer signs when voting on `txn` as a member of the
+/// quorum `quorum_id`: a digest of the serialized transaction along with the
+/// quorum context of the vote, so a signature can't be replayed in a vote
+/// claiming another quorum or threshold.
+pub fn vote_signing_payload(
+    txn: &TransactionKind,
+    quorum_id: &QuorumId,
+    quorum_threshold: FarmerQuorumThreshold,
+) -> Result<Vec<u8>, bincode::Error> {
+    let txn_digest = Sha256::digest(bincode::serialize(txn)?);
+
+    bincode::serialize(&(txn_digest.as_slice(), quorum_id, quorum_threshold))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 #[repr(C)]
 pub struct QuorumData {
@@ -56,6 +80,14 @@ impl QuorumMembers {
         None
     }
 
+    /// Returns the public key of `node_id` if it belongs to a Farmer quorum.
+    pub fn get_farmer_public_key(&self, node_id: &NodeId) -> Option<PublicKey> {
+        self.0
+            .values()
+            .filter(|quorum_data| quorum_data.quorum_kind == QuorumKind::Farmer)
+            .find_map(|quorum_data| quorum_data.members.get(node_id).copied())
+    }
+
     pub fn get_harvester_data(&self) -> Option<QuorumData> {
         for (_, quorum_data) in self.0.iter() {
             if quorum_data.quorum_kind == QuorumKind::Harvester {
@@ -67,13 +99,50 @@ impl QuorumMembers {
 
     pub fn get_harvester_threshold(&self) -> usize {
         if let Some(data) = self.get_harvester_data() {
-            let threshold = (data.members.len() as f64 * VALIDATION_THRESHOLD).ceil() as usize;
-            return threshold;
+            return quorum_threshold(data.members.len());
         }
 
         0usize
     }
 
+    /// Checks that these members can take over as the active quorums: there
+    /// must be exactly one harvester quorum, no quorum may be empty and the
+    /// harvester threshold must be reachable by its members.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(data) = self.0.values().find(|data| data.members.is_empty()) {
+            return Err(Error::InvalidQuorumMembers(format!(
+                "{} quorum has no members",
+                data.quorum_kind
+            )));
+        }
+
+        let harvester_quorums = self
+            .0
+            .values()
+            .filter(|data| data.quorum_kind == QuorumKind::Harvester)
+            .count();
+
+        if harvester_quorums != 1 {
+            return Err(Error::InvalidQuorumMembers(format!(
+                "expected exactly one harvester quorum, found {harvester_quorums}"
+            )));
+        }
+
+        let harvesters = self
+            .get_harvester_data()
+            .map(|data| data.members.len())
+            .unwrap_or_default();
+        let threshold = self.get_harvester_threshold();
+
+        if threshold == 0 || threshold > harvesters {
+            return Err(Error::InvalidQuorumMembers(format!(
+                "harvester threshold {threshold} is unreachable with {harvesters} harvesters"
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn set_quorum_members(&mut self, quorums: Vec<(QuorumKind, Vec<(NodeId, PublicKey)>)>) {
         self.0.clear();
         quorums.iter().for_each(|quorum| {
@@ -139,6 +208,9 @@ pub enum Error {
 
     #[error("is not farmer")]
     IsNotFarmer,
+
+    #[error("invalid quorum members: {0}")]
+    InvalidQuorumMembers(String),
 }
 
 impl SignerEngine {
@@ -168,22 +240,30 @@ impl SignerEngine {
         sig: &Signature,
         data: &T,
     ) -> Result<(), Error> {
-        let mut hasher = Sha256::new();
-        hasher.update(data.as_ref());
-
-        let result = hasher.finalize().to_vec();
-        let message = Message::from_slice(&result);
         let pk = self.quorum_members.get_public_key_from_members(node_id);
 
         if let Some(pk) = pk {
-            return sig
-                .verify(&message.map_err(|e| Error::SecpError(e.to_string()))?, &pk)
-                .map_err(|e| Error::SecpError(e.to_string()));
+            return Self::verify_with_public_key(&pk, sig, data);
         }
 
         Err(Error::FailedVerification("missing public key".to_string()))
     }
 
+    fn verify_with_public_key<T: AsRef<[u8]>>(
+        pk: &PublicKey,
+        sig: &Signature,
+        data: &T,
+    ) -> Result<(), Error> {
+        let mut hasher = Sha256::new();
+        hasher.update(data.as_ref());
+
+        let result = hasher.finalize().to_vec();
+        let message = Message::from_slice(&result);
+
+        sig.verify(&message.map_err(|e| Error::SecpError(e.to_string()))?, pk)
+            .map_err(|e| Error::SecpError(e.to_string()))
+    }
+
     /// Signature verification with a given message
     pub fn verify_with_message(
         &self,
@@ -224,6 +304,120 @@ impl SignerEngine {
         Ok(())
     }
 
+    /// Verifies that `sigs` are signatures of `data` by enough distinct
+    /// members of a single Farmer quorum to reach that quorum's threshold.
+    pub fn verify_farmer_quorum_signatures<T: AsRef<[u8]>>(
+        &self,
+        sigs: &[(NodeId, Signature)],
+        data: &T,
+    ) -> Result<(), Error> {
+        let quorum = sigs
+            .first()
+            .and_then(|(node_id, _)| {
+                self.quorum_members.0.values().find(|quorum_data| {
+                    quorum_data.quorum_kind == QuorumKind::Farmer
+                        && quorum_data.members.contains_key(node_id)
+                })
+            })
+            .ok_or(Error::IsNotFarmer)?;
+
+        let mut signers = HashSet::new();
+        for (node_id, sig) in sigs {
+            let pk = quorum.members.get(node_id).ok_or(Error::IsNotFarmer)?;
+            Self::verify_with_public_key(pk, sig, data)?;
+            signers.insert(node_id);
+        }
+
+        let threshold = quorum_threshold(quorum.members.len());
+        if signers.len() < threshold {
+            return Err(Error::FailedVerification(format!(
+                "not enough farmer signatures to reach threshold: {} < {}",
+                signers.len(),
+                threshold
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that `sigs` are signatures of `data` by enough distinct
+    /// members of the Harvester quorum to reach its threshold. A member may
+    /// only sign once, so that nobody counts twice towards the threshold.
+    pub fn verify_harvester_quorum_signatures<T: AsRef<[u8]>>(
+        &self,
+        sigs: &[(NodeId, Signature)],
+        data: &T,
+    ) -> Result<(), Error> {
+        let quorum = self
+            .quorum_members
+            .get_harvester_data()
+            .ok_or(Error::IsNotHarvester)?;
+
+        let mut signers = HashSet::new();
+        for (node_id, sig) in sigs {
+            let pk = quorum.members.get(node_id).ok_or(Error::IsNotHarvester)?;
+            if !signers.insert(node_id) {
+                return Err(Error::FailedVerification(format!(
+                    "duplicate harvester signature from {node_id}"
+                )));
+            }
+            Self::verify_with_public_key(pk, sig, data)?;
+        }
+
+        let threshold = self.quorum_members.get_harvester_threshold();
+        if signers.len() < threshold {
+            return Err(Error::FailedVerification(format!(
+                "not enough harvester signatures to reach threshold: {} < {}",
+                signers.len(),
+                threshold
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the votes backing a `QuorumCertifiedTxn`: each vote must be a
+    /// signature by the Farmer quorum member named in the receipt of the
+    /// `vote_signing_payload` of the certified transaction, cast in the quorum
+    /// `quorum_id` with `threshold`, and the votes must come from at least
+    /// `threshold` distinct members. Voters are told apart by the
+    /// authenticated `farmer_id`, as `farmer_node_id` isn't covered by the
+    /// signature. Vote signatures are expected in their compact form.
+    pub fn verify_certified_txn(
+        &self,
+        certified_txn: &QuorumCertifiedTxn,
+        quorum_id: &QuorumId,
+        threshold: FarmerQuorumThreshold,
+    ) -> Result<(), Error> {
+        let payload = vote_signing_payload(&certified_txn.txn(), quorum_id, threshold)
+            .map_err(|err| Error::FailedVerification(err.to_string()))?;
+
+        let mut voters = HashSet::new();
+        for vote in certified_txn.votes() {
+            let node_id = NodeId::from_utf8(vote.farmer_id.clone())
+                .map_err(|err| Error::FailedVerification(format!("invalid voter id: {err}")))?;
+            let pk = self
+                .quorum_members
+                .get_farmer_public_key(&node_id)
+                .ok_or(Error::IsNotFarmer)?;
+            let sig = Signature::from_compact(&vote.signature)
+                .map_err(|err| Error::SecpError(err.to_string()))?;
+
+            Self::verify_with_public_key(&pk, &sig, &payload)?;
+            voters.insert(node_id);
+        }
+
+        if voters.len() < threshold {
+            return Err(Error::FailedVerification(format!(
+                "not enough votes to reach threshold: {} < {}",
+                voters.len(),
+                threshold
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn quorum_members(&self) -> QuorumMembers {
         self.quorum_members.clone()
     }
@@ -236,6 +430,20 @@ impl SignerEngine {
         self.quorum_members.set_quorum_members(quorums);
     }
 
+    /// Swaps the active quorums for `quorum_members`, typically the quorums
+    /// carried by a certificate's inauguration. Members that fail
+    /// `QuorumMembers::validate` are rejected and the current quorums are
+    /// left in place.
+    pub fn inaugurate_quorum_members(
+        &mut self,
+        quorum_members: QuorumMembers,
+    ) -> Result<(), Error> {
+        quorum_members.validate()?;
+        self.quorum_members = quorum_members;
+
+        Ok(())
+    }
+
     pub fn is_farmer_quorum_member(
         &mut self,
         quorum_id: &QuorumId,
@@ -254,3 +462,257 @@ impl SignerEngine {
             .is_harvester_quorum_member(quorum_id, node_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vrrb_core::{
+        keypair::KeyPair,
+        transactions::{QuorumCertifiedTxn, TransactionKind, VoteReceipt},
+    };
+
+    use super::*;
+
+    fn farmer_engines(count: usize) -> Vec<(NodeId, SignerEngine)> {
+        (0..count)
+            .map(|idx| {
+                let keypair = KeyPair::random();
+                let engine = SignerEngine::new(
+                    *keypair.get_miner_public_key(),
+                    *keypair.get_miner_secret_key(),
+                );
+
+                (format!("farmer-{idx}"), engine)
+            })
+            .collect()
+    }
+
+    fn verifier(farmers: &[(NodeId, SignerEngine)]) -> SignerEngine {
+        let mut engine = farmers[0].1.clone();
+        let members = farmers
+            .iter()
+            .map(|(node_id, engine)| (node_id.clone(), engine.public_key()))
+            .collect();
+        engine.set_quorum_members(vec![(QuorumKind::Farmer, members)]);
+
+        engine
+    }
+
+    fn farmer_quorum_id(farmers: &[(NodeId, SignerEngine)]) -> QuorumId {
+        QuorumId::new(
+            QuorumKind::Farmer,
+            farmers
+                .iter()
+                .map(|(node_id, engine)| (node_id.clone(), engine.public_key()))
+                .collect(),
+        )
+    }
+
+    /// Signs the same payload farmers sign when casting a `Vote`.
+    fn vote_receipt(
+        farmer_node_id: usize,
+        node_id: &NodeId,
+        engine: &mut SignerEngine,
+        txn: &TransactionKind,
+        quorum_id: &QuorumId,
+        threshold: FarmerQuorumThreshold,
+    ) -> VoteReceipt {
+        let payload = vote_signing_payload(txn, quorum_id, threshold).unwrap();
+        let signature = engine.sign(payload).unwrap();
+
+        VoteReceipt {
+            farmer_id: node_id.as_bytes().to_vec(),
+            farmer_node_id: farmer_node_id as u16,
+            signature: signature.serialize_compact().to_vec(),
+        }
+    }
+
+    fn certify(votes: Vec<VoteReceipt>, txn: TransactionKind) -> QuorumCertifiedTxn {
+        QuorumCertifiedTxn::new(b"farmer-0".to_vec(), votes, txn, vec![0], true)
+    }
+
+    #[test]
+    fn verifies_correctly_certified_txn() {
+        let mut farmers = farmer_engines(3);
+        let engine = verifier(&farmers);
+        let quorum_id = farmer_quorum_id(&farmers);
+        let txn = TransactionKind::default();
+
+        let votes = farmers
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, (node_id, engine))| vote_receipt(idx, node_id, engine, &txn, &quorum_id, 3))
+            .collect();
+        let certified_txn = certify(votes, txn);
+
+        assert!(engine
+            .verify_certified_txn(&certified_txn, &quorum_id, 3)
+            .is_ok());
+        assert!(engine
+            .verify_certified_txn(&certified_txn, &quorum_id, 4)
+            .is_err());
+        assert!(engine
+            .verify_certified_txn(&certified_txn, &QuorumId::default(), 3)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_certified_txn_with_duplicated_receipts() {
+        let mut farmers = farmer_engines(3);
+        let engine = verifier(&farmers);
+        let quorum_id = farmer_quorum_id(&farmers);
+        let txn = TransactionKind::default();
+
+        // One genuine vote, replayed under different farmer_node_ids.
+        let (node_id, farmer) = &mut farmers[0];
+        let replayed = |vote: VoteReceipt| -> Vec<VoteReceipt> {
+            (0..3)
+                .map(|idx| VoteReceipt {
+                    farmer_node_id: idx,
+                    ..vote.clone()
+                })
+                .collect()
+        };
+
+        let vote = vote_receipt(0, node_id, farmer, &txn, &quorum_id, 1);
+        let certified_txn = certify(replayed(vote), txn.clone());
+        assert!(engine
+            .verify_certified_txn(&certified_txn, &quorum_id, 1)
+            .is_ok());
+
+        let vote = vote_receipt(0, node_id, farmer, &txn, &quorum_id, 2);
+        let certified_txn = certify(replayed(vote), txn);
+        assert!(engine
+            .verify_certified_txn(&certified_txn, &quorum_id, 2)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_certified_txn_with_votes_from_outside_the_farmer_quorum() {
+        let mut farmers = farmer_engines(3);
+        let mut engine = verifier(&farmers);
+        let quorum_id = farmer_quorum_id(&farmers);
+        let txn = TransactionKind::default();
+
+        let votes = farmers
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, (node_id, engine))| vote_receipt(idx, node_id, engine, &txn, &quorum_id, 3))
+            .collect();
+        let certified_txn = certify(votes, txn);
+
+        let members = farmers
+            .iter()
+            .map(|(node_id, engine)| (node_id.clone(), engine.public_key()))
+            .collect();
+        engine.set_quorum_members(vec![(QuorumKind::Harvester, members)]);
+
+        assert!(matches!(
+            engine.verify_certified_txn(&certified_txn, &quorum_id, 3),
+            Err(Error::IsNotFarmer)
+        ));
+    }
+
+    #[test]
+    fn rejects_harvester_signatures_from_duplicate_or_unknown_signers() {
+        let mut harvesters = farmer_engines(3);
+        let mut outsiders = farmer_engines(1);
+        let mut engine = harvesters[0].1.clone();
+        let members = harvesters
+            .iter()
+            .map(|(node_id, engine)| (node_id.clone(), engine.public_key()))
+            .collect();
+        engine.set_quorum_members(vec![(QuorumKind::Harvester, members)]);
+
+        let mut sigs: Vec<(NodeId, Signature)> = harvesters
+            .iter_mut()
+            .map(|(node_id, engine)| (node_id.clone(), engine.sign("block").unwrap()))
+            .collect();
+        assert!(engine
+            .verify_harvester_quorum_signatures(&sigs, &"block")
+            .is_ok());
+
+        let duplicated = vec![sigs[0].clone(), sigs[0].clone()];
+        assert!(engine
+            .verify_harvester_quorum_signatures(&duplicated, &"block")
+            .is_err());
+
+        let (_, outsider) = &mut outsiders[0];
+        sigs[2] = (String::from("outsider"), outsider.sign("block").unwrap());
+        assert!(matches!(
+            engine.verify_harvester_quorum_signatures(&sigs, &"block"),
+            Err(Error::IsNotHarvester)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_inauguration_and_keeps_current_quorum() {
+        let harvesters = farmer_engines(3)
+            .into_iter()
+            .map(|(node_id, engine)| (node_id, engine.public_key()))
+            .collect::<Vec<_>>();
+        let farmers = farmer_engines(3);
+        let mut engine = verifier(&farmers);
+        let current = engine.quorum_members();
+
+        let mut malformed = QuorumMembers::default();
+        malformed.set_quorum_members(vec![
+            (QuorumKind::Harvester, vec![]),
+            (QuorumKind::Farmer, harvesters.clone()),
+        ]);
+        assert!(matches!(
+            engine.inaugurate_quorum_members(malformed),
+            Err(Error::InvalidQuorumMembers(_))
+        ));
+        assert_eq!(engine.quorum_members(), current);
+
+        let mut without_harvesters = QuorumMembers::default();
+        without_harvesters.set_quorum_members(vec![(QuorumKind::Farmer, harvesters.clone())]);
+        assert!(engine
+            .inaugurate_quorum_members(without_harvesters)
+            .is_err());
+        assert_eq!(engine.quorum_members(), current);
+
+        let mut inauguration = QuorumMembers::default();
+        inauguration.set_quorum_members(vec![(QuorumKind::Harvester, harvesters)]);
+        engine
+            .inaugurate_quorum_members(inauguration.clone())
+            .unwrap();
+        assert_eq!(engine.quorum_members(), inauguration);
+    }
+
+    #[test]
+    fn rejects_certified_txn_with_swapped_out_vote() {
+        let mut farmers = farmer_engines(3);
+        let mut outsiders = farmer_engines(1);
+        let engine = verifier(&farmers);
+        let quorum_id = farmer_quorum_id(&farmers);
+        let txn = TransactionKind::default();
+
+        let mut votes: Vec<VoteReceipt> = farmers
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, (node_id, engine))| vote_receipt(idx, node_id, engine, &txn, &quorum_id, 3))
+            .collect();
+
+        // Signed by a node outside the quorum, under a member's identity.
+        let (_, outsider) = &mut outsiders[0];
+        let (member_id, _) = &farmers[2];
+        votes[2] = vote_receipt(2, member_id, outsider, &txn, &quorum_id, 3);
+
+        let certified_txn = certify(votes, txn);
+
+        assert!(engine
+            .verify_certified_txn(&certified_txn, &quorum_id, 3)
+            .is_err());
+    }
+
+    #[test]
+    fn quorum_threshold_rounds_up() {
+        assert_eq!(quorum_threshold(0), 0);
+        assert_eq!(quorum_threshold(1), 1);
+        assert_eq!(quorum_threshold(3), 2);
+        assert_eq!(quorum_threshold(4), 3);
+        assert_eq!(quorum_threshold(5), 3);
+        assert_eq!(quorum_threshold(10), 6);
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    collections::HashSet,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{Event, EventSubscriber};
+
+/// Variants recorded by `EventAuditLog::with_default_variants`.
+pub const DEFAULT_AUDITED_EVENTS: &[&str] = &[
+    "BlockAppended",
+    "BlockConfirmed",
+    "UpdateState",
+    "ConvergenceBlockCertified",
+    "QuorumFormed",
+    "QuorumMembershipAssigmentsCreated",
+];
+
+/// An audited event along with the time, in seconds since the unix epoch, at
+/// which it was recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: i64,
+    pub variant: String,
+    pub event: Event,
+}
+
+/// A durable log of significant events, kept for a limited retention window.
+///
+/// Only events whose variant is part of the configured set are recorded.
+/// Entries are appended to disk as they are recorded and can be queried by
+/// time range and variant. `prune` drops the entries that fell out of the
+/// retention window.
+#[derive(Debug)]
+pub struct EventAuditLog {
+    path: PathBuf,
+    file: File,
+    variants: HashSet<String>,
+    retention: Duration,
+    entries: Vec<AuditEntry>,
+}
+
+impl EventAuditLog {
+    /// Opens the audit log at `path`, creating it if it does not exist yet,
+    /// recording the given `Event` variants.
+    pub fn open<S: Into<String>>(
+        path: impl AsRef<Path>,
+        variants: impl IntoIterator<Item = S>,
+        retention: Duration,
+    ) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = read_entries(&path)?;
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            file,
+            variants: variants.into_iter().map(Into::into).collect(),
+            retention,
+            entries,
+        })
+    }
+
+    /// Opens the audit log at `path`, recording `DEFAULT_AUDITED_EVENTS`.
+    pub fn with_default_variants(path: impl AsRef<Path>, retention: Duration) -> io::Result<Self> {
+        Self::open(path, DEFAULT_AUDITED_EVENTS.iter().copied(), retention)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records `event` with the current time if its variant is audited.
+    /// Returns whether the event was recorded.
+    pub fn record(&mut self, event: &Event) -> io::Result<bool> {
+        self.record_at(event, chrono::Utc::now().timestamp())
+    }
+
+    /// Records `event` with the given timestamp if its variant is audited.
+    pub fn record_at(&mut self, event: &Event, timestamp: i64) -> io::Result<bool> {
+        let variant = event_variant(event);
+        if !self.variants.contains(&variant) {
+            return Ok(false);
+        }
+
+        let entry = AuditEntry {
+            timestamp,
+            variant,
+            event: event.clone(),
+        };
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.file.sync_data()?;
+
+        self.entries.push(entry);
+
+        Ok(true)
+    }
+
+    /// Returns the entries recorded within `from..=to`, optionally restricted
+    /// to a single variant, in the order they were recorded.
+    pub fn query(&self, from: i64, to: i64, variant: Option<&str>) -> Vec<&AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| (from..=to).contains(&entry.timestamp))
+            .filter(|entry| variant.map_or(true, |variant| entry.variant == variant))
+            .collect()
+    }
+
+    /// Removes the entries recorded more than the retention window before
+    /// `now`, returning how many were removed.
+    pub fn prune(&mut self, now: i64) -> io::Result<usize> {
+        let cutoff = now.saturating_sub(self.retention.as_secs() as i64);
+
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.timestamp >= cutoff);
+        let pruned = before - self.entries.len();
+
+        if pruned > 0 {
+            self.rewrite()?;
+        }
+
+        Ok(pruned)
+    }
+
+    /// Records every audited event published to `events_rx`, pruning expired
+    /// entries as it goes, until the channel closes or a `Stop` is received.
+    pub async fn run(mut self, mut events_rx: EventSubscriber) -> io::Result<()> {
+        loop {
+            let event: Event = match events_rx.recv().await {
+                Ok(message) => message.into(),
+                Err(RecvError::Lagged(skipped)) => {
+                    telemetry::warn!("event audit log skipped {skipped} events");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            if event == Event::Stop {
+                break;
+            }
+
+            if self.record(&event)? {
+                self.prune(chrono::Utc::now().timestamp())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the file on disk with the entries currently retained.
+    fn rewrite(&mut self) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+
+        {
+            let mut tmp = File::create(&tmp_path)?;
+            for entry in self.entries.iter() {
+                let mut line = serde_json::to_vec(entry)?;
+                line.push(b'\n');
+                tmp.write_all(&line)?;
+            }
+            tmp.sync_all()?;
+        }
+
+        fs::rename(&tmp_path, &self.path)?;
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+
+        Ok(())
+    }
+}
+
+/// Name of the `Event` variant, as used to configure `EventAuditLog`.
+pub fn event_variant(event: &Event) -> String {
+    match serde_json::to_value(event) {
+        Ok(serde_json::Value::String(variant)) => variant,
+        Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Reads every entry in the log. Lines that can't be parsed, like a trailing
+/// entry that was only partially written before a crash, are skipped and
+/// reported, so that one bad line doesn't hide the entries after it.
+fn read_entries(path: &Path) -> io::Result<Vec<AuditEntry>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        match serde_json::from_str(&line?) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => telemetry::warn!(
+                "skipping malformed entry on line {} of event audit log {}: {err}",
+                index + 1,
+                path.display()
+            ),
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit_log_path() -> PathBuf {
+        std::env::temp_dir().join(format!("events-{}.audit", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn audited_events_can_be_queried_by_time_range_and_variant() {
+        let path = audit_log_path();
+        let mut audit_log =
+            EventAuditLog::with_default_variants(&path, Duration::from_secs(3600)).unwrap();
+
+        assert!(audit_log.record_at(&Event::QuorumFormed, 100).unwrap());
+        assert!(audit_log
+            .record_at(&Event::BlockAppended(String::from("block-1")), 110)
+            .unwrap());
+        assert!(!audit_log.record_at(&Event::NoOp, 115).unwrap());
+        assert!(audit_log
+            .record_at(&Event::BlockAppended(String::from("block-2")), 120)
+            .unwrap());
+        assert!(audit_log.record_at(&Event::QuorumFormed, 130).unwrap());
+
+        let appended = audit_log.query(105, 125, Some("BlockAppended"));
+        assert_eq!(
+            appended
+                .iter()
+                .map(|entry| entry.event.clone())
+                .collect::<Vec<Event>>(),
+            vec![
+                Event::BlockAppended(String::from("block-1")),
+                Event::BlockAppended(String::from("block-2")),
+            ]
+        );
+
+        assert_eq!(audit_log.query(100, 130, Some("QuorumFormed")).len(), 2);
+        assert_eq!(audit_log.query(111, 130, None).len(), 2);
+
+        // entries survive reopening the log
+        drop(audit_log);
+        let audit_log =
+            EventAuditLog::with_default_variants(&path, Duration::from_secs(3600)).unwrap();
+        assert_eq!(audit_log.len(), 4);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn pruning_removes_entries_older_than_retention_window() {
+        let path = audit_log_path();
+        let mut audit_log =
+            EventAuditLog::open(&path, ["QuorumFormed"], Duration::from_secs(60)).unwrap();
+
+        audit_log.record_at(&Event::QuorumFormed, 1_000).unwrap();
+        audit_log.record_at(&Event::QuorumFormed, 1_030).unwrap();
+        audit_log.record_at(&Event::QuorumFormed, 1_100).unwrap();
+
+        assert_eq!(audit_log.prune(1_100).unwrap(), 2);
+        assert_eq!(audit_log.prune(1_100).unwrap(), 0);
+        assert_eq!(audit_log.query(0, i64::MAX, None).len(), 1);
+
+        audit_log.record_at(&Event::QuorumFormed, 1_110).unwrap();
+
+        drop(audit_log);
+        let audit_log =
+            EventAuditLog::open(&path, ["QuorumFormed"], Duration::from_secs(60)).unwrap();
+        assert_eq!(
+            audit_log
+                .query(0, i64::MAX, None)
+                .iter()
+                .map(|entry| entry.timestamp)
+                .collect::<Vec<i64>>(),
+            vec![1_100, 1_110]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped_when_reading_the_log() {
+        let path = audit_log_path();
+
+        {
+            let mut audit_log =
+                EventAuditLog::open(&path, ["QuorumFormed"], Duration::from_secs(60)).unwrap();
+            audit_log.record_at(&Event::QuorumFormed, 1_000).unwrap();
+        }
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"timestamp\":1010,\n").unwrap();
+        drop(file);
+
+        {
+            let mut audit_log =
+                EventAuditLog::open(&path, ["QuorumFormed"], Duration::from_secs(60)).unwrap();
+            audit_log.record_at(&Event::QuorumFormed, 1_020).unwrap();
+        }
+
+        let audit_log =
+            EventAuditLog::open(&path, ["QuorumFormed"], Duration::from_secs(60)).unwrap();
+        assert_eq!(
+            audit_log
+                .query(0, i64::MAX, None)
+                .iter()
+                .map(|entry| entry.timestamp)
+                .collect::<Vec<i64>>(),
+            vec![1_000, 1_020]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,53 @@
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+
+use crate::{event::log_discarded_event, Error, Event};
+
+/// Default number of dead letters an `EventDecoder` holds on to until its
+/// receiver reads them.
+pub const DEFAULT_DEAD_LETTER_BUFFER: usize = 100;
+
+/// Raw bytes of an event that failed to decode, along with the reason.
+pub type DeadLetter = (Vec<u8>, Error);
+pub type DeadLetterSender = Sender<DeadLetter>;
+pub type DeadLetterReceiver = Receiver<DeadLetter>;
+
+/// Decodes raw event payloads, forwarding the ones that fail to decode to a
+/// bounded dead-letter channel instead of only turning them into
+/// `Event::NoOp` like the `From` impls do.
+///
+/// The channel belongs to whoever created the decoder: dead letters are
+/// dropped, with a warning, while its buffer is full, and no longer
+/// collected once its receiver is dropped.
+#[derive(Debug, Clone)]
+pub struct EventDecoder {
+    dead_letters: DeadLetterSender,
+}
+
+impl EventDecoder {
+    /// Creates a decoder along with the receiving end of its dead-letter
+    /// channel, which buffers up to `capacity` dead letters.
+    pub fn new(capacity: usize) -> (Self, DeadLetterReceiver) {
+        let (dead_letters, dead_letters_rx) = channel(capacity);
+
+        (Self { dead_letters }, dead_letters_rx)
+    }
+
+    /// Decodes `data` into an `Event`, falling back to `Event::NoOp` after
+    /// sending `data` and the reason it failed to decode to the dead-letter
+    /// channel.
+    pub fn decode(&self, data: &[u8]) -> Event {
+        match Event::decode(data) {
+            Ok(event) => event,
+            Err(err) => {
+                log_discarded_event(&err);
+
+                if let Err(TrySendError::Full(_)) = self.dead_letters.try_send((data.to_vec(), err))
+                {
+                    telemetry::warn!("dead-letter channel is full, dropping undecodable event");
+                }
+
+                Event::default()
+            }
+        }
+    }
+}
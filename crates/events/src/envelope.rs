@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Event, Result};
+
+/// Version of the `Event` wire format. Bump it whenever a change to `Event`
+/// makes payloads encoded by older nodes undecodable.
+pub const EVENT_VERSION: u16 = 1;
+
+/// An `Event` tagged with the version of the wire format it was encoded with,
+/// so that events sent by incompatible peers are reported as such rather than
+/// decoded into something else.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    pub version: u16,
+    pub event: Event,
+}
+
+/// The envelope with its event left undecoded, read first to check the
+/// version before trying to make sense of the event.
+#[derive(Deserialize)]
+struct RawEnvelope {
+    version: u16,
+    event: serde_json::Value,
+}
+
+impl EventEnvelope {
+    /// Wraps `event` in an envelope of the current version.
+    pub fn new(event: Event) -> Self {
+        Self {
+            version: EVENT_VERSION,
+            event,
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Decodes an envelope, failing with `Error::UnsupportedVersion` if it was
+    /// encoded with another version of the wire format.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let raw: RawEnvelope = serde_json::from_slice(data)?;
+
+        if raw.version != EVENT_VERSION {
+            return Err(Error::UnsupportedVersion {
+                expected: EVENT_VERSION,
+                found: raw.version,
+            });
+        }
+
+        Ok(Self {
+            version: raw.version,
+            event: serde_json::from_value(raw.event)?,
+        })
+    }
+
+    pub fn into_event(self) -> Event {
+        self.event
+    }
+}
+
+impl From<Event> for EventEnvelope {
+    fn from(event: Event) -> Self {
+        Self::new(event)
+    }
+}
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to encode or decode event: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+
+    #[error("failed to encode or decode event data: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    #[error("unsupported event version {found}, expected {expected}")]
+    UnsupportedVersion { expected: u16, found: u16 },
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
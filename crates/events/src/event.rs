@@ -1,4 +1,3 @@
-use block::GenesisReceiver;
 use block::{header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, ProposalBlock};
 use ethereum_types::U256;
 use hbbft::sync_key_gen::Ack;
@@ -11,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use vrrb_core::claim::Claim;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 
-use crate::event_data::*;
+use crate::{event_data::*, Error, EventEnvelope, Result};
 
 pub type AccountBytes = Vec<u8>;
 pub type BlockBytes = Vec<u8>;
@@ -40,6 +39,34 @@ pub enum Event {
     /// that needs to be validated.
     NewTxnCreated(TransactionKind),
 
+    /// `NewTxnForwarded(NodeId, Txn, Signature)` is triggered when a peer,
+    /// identified by the `NodeId`, gossips a transaction to this node. The
+    /// `Signature` is the peer's signature over its id and the transaction
+    /// digest, authenticating where the transaction came from. The node adds
+    /// valid transactions to its mempool, if they're new, and gossips them
+    /// onward once.
+    NewTxnForwarded(NodeId, TransactionKind, Signature),
+
+    /// `StateSyncRequested` is triggered when a lagging peer, identified by
+    /// `from`, asks this node for the height and state root hash of its last
+    /// confirmed block. `at_height` is the height of the last block the peer
+    /// has state for, if any, and `signature` is `from`'s signature over
+    /// both.
+    StateSyncRequested {
+        from: NodeId,
+        at_height: Option<u128>,
+        signature: Signature,
+    },
+
+    /// `StateSyncResponse` answers a `StateSyncRequested` from the peer `to`
+    /// with the height and state root hash of this node's last confirmed
+    /// block.
+    StateSyncResponse {
+        to: NodeId,
+        height: u128,
+        state_root_hash: String,
+    },
+
     /// `TxnValidated(Txn)` is an event that is triggered when a transaction has
     /// been validated by the validator module. The `Txn` parameter contains
     /// the details of the validated transaction. This event can be used to
@@ -53,6 +80,22 @@ pub enum Event {
     /// that has been added to the mempool.
     TxnAddedToMempool(TransactionDigest),
 
+    /// `MempoolPressureReached` is emitted when the mempool grows past its
+    /// high watermark. It carries the number of pending transactions and the
+    /// transaction whose insertion crossed the watermark, so that consumers
+    /// can throttle the intake of new transactions.
+    MempoolPressureReached {
+        occupancy: usize,
+        cutoff: TransactionDigest,
+    },
+
+    /// `MempoolPressureRelieved` is emitted once a mempool under pressure
+    /// shrinks below its low watermark, carrying the number of pending
+    /// transactions.
+    MempoolPressureRelieved {
+        occupancy: usize,
+    },
+
     /// `BlockReceived(NodeId, Block)` represents a block that has been received from
     /// peers in the network. The block can be a genesis block, a proposal
     /// block, or a convergence block.
@@ -83,6 +126,16 @@ pub enum Event {
     /// request for Account updation on the chain has been requested.
     AccountUpdateRequested((Address, AccountBytes)),
 
+    /// `UpdatedAccount((Address, AccountBytes))` is emitted once an
+    /// `AccountUpdateRequested` has been applied to the state store, carrying
+    /// the resulting account.
+    UpdatedAccount((Address, AccountBytes)),
+
+    /// `MaintenanceModeRequested(bool)` is triggered when an operator turns
+    /// maintenance mode on or off, pausing or resuming block production and
+    /// quorum signing on the node.
+    MaintenanceModeRequested(bool),
+
     /// `BlockCreated(Block)` is an event that occurs whenever a block of any
     /// kind is created
     BlockCreated(Block),
@@ -112,13 +165,13 @@ pub enum Event {
     /// the network.
     HarvesterPublicKeyReceived(PublicKeySet),
 
-    MinerElectionStarted(BlockHeader),
+    /// Starts the election of the miner of the block following the given
+    /// certified block.
+    MinerElectionStarted(Block),
 
     MinerElected((U256, Claim)),
 
-    GenesisMinerElected {
-        genesis_receivers: Vec<GenesisReceiver>,
-    },
+    GenesisMinerElected,
 
     ConvergenceBlockCertified(ConvergenceBlock),
 
@@ -173,34 +226,80 @@ pub enum Event {
     BlockCertificateCreated(Certificate),
     QuorumFormed,
     HarvesterSignatureReceived(BlockHash, NodeId, Signature),
+    /// `SignatureCollectionSweepRequested` is triggered periodically so the
+    /// node escalates the certificates whose signature collection timed out.
+    SignatureCollectionSweepRequested,
     BroadcastCertificate(Certificate),
     BroadcastTransactionVote(Vote),
+    /// Gossips a transaction to peers along with this node's signature over
+    /// its id and the transaction digest, see `NewTxnForwarded`
+    BroadcastTxn(TransactionKind, Signature),
     BlockAppended(String),
     BuildProposalBlock(ConvergenceBlock),
     BroadcastProposalBlock(ProposalBlock),
 }
 
+impl Event {
+    /// Decodes an `Event` from an `EventEnvelope`, failing with
+    /// `Error::UnsupportedVersion` if it was encoded with another version of
+    /// the wire format.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        EventEnvelope::decode(data).map(EventEnvelope::into_event)
+    }
+
+    /// Encodes the event wrapped in an `EventEnvelope` of the current
+    /// version.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        EventEnvelope::new(self.clone()).encode()
+    }
+
+    /// Decodes an `Event` from the enveloped payload of a `theater::Message`.
+    pub fn try_from_message(msg: &theater::Message) -> Result<Self> {
+        Self::decode(&msg.data)
+    }
+
+    /// Decodes an `Event` from an `EventEnvelope`, logging malformed or
+    /// incompatible payloads and falling back to `Event::NoOp`, which is what
+    /// the infallible `From` impls rely on. See `EventDecoder` to also keep
+    /// the payloads that failed to decode.
+    fn decode_or_default(data: &[u8]) -> Self {
+        Self::decode(data).unwrap_or_else(|err| {
+            log_discarded_event(&err);
+            Event::default()
+        })
+    }
+}
+
+/// Logs why an event payload is being discarded.
+pub(crate) fn log_discarded_event(err: &Error) {
+    if let Error::UnsupportedVersion { .. } = err {
+        telemetry::error!("discarding event from an incompatible peer: {err}");
+    } else {
+        telemetry::warn!("discarding malformed event: {err}");
+    }
+}
+
 impl From<&theater::Message> for Event {
     fn from(msg: &theater::Message) -> Self {
-        serde_json::from_slice(&msg.data).unwrap_or_default()
+        Event::decode_or_default(&msg.data)
     }
 }
 
 impl From<theater::Message> for Event {
     fn from(msg: theater::Message) -> Self {
-        serde_json::from_slice(&msg.data).unwrap_or_default()
+        Event::decode_or_default(&msg.data)
     }
 }
 
 impl From<Vec<u8>> for Event {
     fn from(data: Vec<u8>) -> Self {
-        serde_json::from_slice(&data).unwrap_or_default()
+        Event::decode_or_default(&data)
     }
 }
 
 impl From<Event> for Vec<u8> {
     fn from(evt: Event) -> Self {
-        serde_json::to_vec(&evt).unwrap_or_default()
+        EventEnvelope::new(evt).encode().unwrap_or_default()
     }
 }
 
@@ -210,6 +309,9 @@ impl From<Event> for messr::Message<Event> {
             Event::Stop => messr::Message::stop_signal(None),
             Event::CreateAccountRequested(_)
             | Event::NewTxnCreated(_)
+            | Event::NewTxnForwarded(..)
+            | Event::StateSyncRequested { .. }
+            | Event::SignatureCollectionSweepRequested
             | Event::TxnAddedToMempool(_) => {
                 messr::Message::new(Some(RUNTIME_TOPIC_STR.into()), evt)
             }
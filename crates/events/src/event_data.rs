@@ -3,12 +3,17 @@ use std::net::SocketAddr;
 use block::BlockHash;
 use primitives::{
     ByteVec, FarmerId, FarmerQuorumThreshold, IsTxnValid, KademliaPeerId, NodeId, NodeType,
-    PublicKey, QuorumKind, RawSignature, Signature, ValidatorPublicKeyShare,
+    ProgramExecutionOutput, PublicKey, QuorumId, QuorumKind, RawSignature, Signature,
+    ValidatorPublicKeyShare,
 };
+use secp256k1::Message;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use vrrb_config::QuorumMember;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 
+use crate::Result;
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PeerData {
     pub node_id: NodeId,
@@ -55,9 +60,72 @@ pub struct Vote {
     /// Partial Signature
     pub signature: Signature,
     pub txn: TransactionKind,
+    /// The Farmer quorum the vote was cast in, identified by the hash of its
+    /// members' public keys.
+    pub quorum_id: QuorumId,
+    /// Number of votes the quorum needs to certify the transaction.
+    pub quorum_threshold: FarmerQuorumThreshold,
     pub is_txn_valid: bool,
-    // May want to serialize this as a vector of bytes
-    pub execution_result: Option<String>,
+    pub execution_result: Option<ExecutionOutcome>,
+}
+
+/// The outcome of executing the transaction a [`Vote`] was cast on.
+#[derive(Debug, Deserialize, Serialize, Hash, Clone, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    /// The transaction executed successfully, producing the given events.
+    Success { events: Vec<ProgramExecutionOutput> },
+    /// The transaction failed to execute for the given reason.
+    Failure { reason: String },
+}
+
+impl ExecutionOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, ExecutionOutcome::Success { .. })
+    }
+}
+
+impl Vote {
+    /// Returns the canonical bytes a farmer signs when casting this vote.
+    ///
+    /// The payload covers a digest of the serialized transaction being voted
+    /// on along with the quorum context of the vote, so a signature can't be
+    /// replayed in a vote claiming another quorum or threshold. Every farmer
+    /// in a quorum signs the exact same bytes, so their signatures can be
+    /// batch verified by harvesters.
+    pub fn signing_payload(&self) -> Result<Vec<u8>> {
+        Self::build_signing_payload(&self.txn, &self.quorum_id, self.quorum_threshold)
+    }
+
+    /// Builds the payload signed by a farmer voting on `txn` as a member of
+    /// the quorum `quorum_id`.
+    pub fn build_signing_payload(
+        txn: &TransactionKind,
+        quorum_id: &QuorumId,
+        quorum_threshold: FarmerQuorumThreshold,
+    ) -> Result<Vec<u8>> {
+        Ok(signer::engine::vote_signing_payload(
+            txn,
+            quorum_id,
+            quorum_threshold,
+        )?)
+    }
+
+    /// Verifies the vote's signature against the given farmer public key.
+    /// Votes whose payload can't be serialized never verify.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        let Ok(payload) = self.signing_payload() else {
+            return false;
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(payload);
+        let digest = hasher.finalize();
+
+        match Message::from_slice(&digest) {
+            Ok(message) => self.signature.verify(&message, public_key).is_ok(),
+            Err(_) => false,
+        }
+    }
 }
 
 pub type SerializedConvergenceBlock = ByteVec;
@@ -1,10 +1,17 @@
 use messr::Router;
 use tokio::sync::{broadcast::Receiver, mpsc::Sender};
 
-pub use crate::{event::*, event_data::*};
+pub use crate::{
+    audit_log::*, dead_letter::*, envelope::*, error::*, event::*, event_data::*, wal::*,
+};
 
+mod audit_log;
+mod dead_letter;
+mod envelope;
+mod error;
 mod event;
 mod event_data;
+mod wal;
 
 pub const DEFAULT_BUFFER: usize = 1000;
 
@@ -16,6 +23,9 @@ pub type Topic = messr::Topic;
 
 #[cfg(test)]
 mod tests {
+    use sha2::Digest;
+    use vrrb_core::keypair::Keypair;
+
     use super::*;
 
     #[test]
@@ -28,4 +38,239 @@ mod tests {
             messr::Message::new_with_id(message.id, Event::NoOp, None)
         );
     }
+
+    fn signed_vote(keypair: &Keypair) -> Vote {
+        let txn = vrrb_core::transactions::TransactionKind::default();
+        let quorum_id = primitives::QuorumId::new(
+            primitives::QuorumKind::Farmer,
+            vec![(
+                String::from("farmer-1"),
+                *keypair.get_validator_public_key(),
+            )],
+        );
+        let payload = Vote::build_signing_payload(&txn, &quorum_id, 1).unwrap();
+        let digest = sha2::Sha256::digest(payload);
+        let message = secp256k1::Message::from_slice(&digest).unwrap();
+        let signature = keypair.get_validator_secret_key().sign_ecdsa(message);
+
+        Vote {
+            farmer_id: String::from("farmer-1"),
+            farmer_node_id: String::from("farmer-1"),
+            signature,
+            txn,
+            quorum_id,
+            quorum_threshold: 1,
+            is_txn_valid: true,
+            execution_result: None,
+        }
+    }
+
+    #[test]
+    fn vote_can_be_verified_against_its_signing_payload() {
+        let keypair = Keypair::random();
+        let vote = signed_vote(&keypair);
+
+        assert_eq!(
+            vote.signing_payload().unwrap(),
+            bincode::serialize(&(
+                sha2::Sha256::digest(bincode::serialize(&vote.txn).unwrap()).as_slice(),
+                &vote.quorum_id,
+                vote.quorum_threshold
+            ))
+            .unwrap()
+        );
+        assert!(vote.verify(keypair.get_validator_public_key()));
+    }
+
+    #[test]
+    fn vote_with_tampered_quorum_context_fails_verification() {
+        let keypair = Keypair::random();
+        let vote = signed_vote(&keypair);
+
+        let mut tampered = vote.clone();
+        tampered.quorum_threshold += 1;
+        assert!(!tampered.verify(keypair.get_validator_public_key()));
+
+        let mut tampered = vote;
+        tampered.quorum_id = primitives::QuorumId::new(primitives::QuorumKind::Farmer, vec![]);
+        assert!(!tampered.verify(keypair.get_validator_public_key()));
+    }
+
+    #[test]
+    fn vote_over_tampered_txn_fails_verification() {
+        let keypair = Keypair::random();
+        let mut vote = signed_vote(&keypair);
+
+        let vrrb_core::transactions::TransactionKind::Transfer(transfer) = &mut vote.txn;
+        transfer.amount += 1;
+
+        assert!(!vote.verify(keypair.get_validator_public_key()));
+    }
+
+    #[test]
+    fn vote_with_typed_execution_outcome_can_be_serialized() {
+        let mut vote = signed_vote(&Keypair::random());
+        vote.execution_result = Some(ExecutionOutcome::Success {
+            events: vec![String::from("{\"transfer\":{\"amount\":10}}")],
+        });
+
+        let serialized = serde_json::to_string(&vote).unwrap();
+        let deserialized: Vote = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(serialized.matches("\"execution_result\"").count(), 1);
+        assert_eq!(deserialized, vote);
+        assert!(deserialized.execution_result.unwrap().is_success());
+
+        vote.execution_result = Some(ExecutionOutcome::Failure {
+            reason: String::from("out of gas"),
+        });
+
+        let serialized = bincode::serialize(&vote).unwrap();
+        let deserialized: Vote = bincode::deserialize(&serialized).unwrap();
+
+        assert_eq!(deserialized, vote);
+    }
+
+    #[test]
+    fn certified_txn_signed_like_farmer_votes_verifies() {
+        use signer::engine::SignerEngine;
+        use vrrb_core::transactions::{QuorumCertifiedTxn, TransactionKind, VoteReceipt};
+
+        let mut farmers: Vec<(String, SignerEngine)> = (0..3)
+            .map(|idx| {
+                let keypair = Keypair::random();
+                let engine = SignerEngine::new(
+                    *keypair.get_validator_public_key(),
+                    *keypair.get_validator_secret_key(),
+                );
+                (format!("farmer-{idx}"), engine)
+            })
+            .collect();
+        let members: Vec<_> = farmers
+            .iter()
+            .map(|(node_id, engine)| (node_id.clone(), engine.public_key()))
+            .collect();
+        let quorum_id = primitives::QuorumId::new(primitives::QuorumKind::Farmer, members.clone());
+
+        let txn = TransactionKind::default();
+        let payload = Vote::build_signing_payload(&txn, &quorum_id, 2).unwrap();
+        let votes = farmers
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, (node_id, engine))| VoteReceipt {
+                farmer_id: node_id.as_bytes().to_vec(),
+                farmer_node_id: idx as u16,
+                signature: engine
+                    .sign(payload.clone())
+                    .unwrap()
+                    .serialize_compact()
+                    .to_vec(),
+            })
+            .collect();
+        let certified_txn =
+            QuorumCertifiedTxn::new(b"farmer-0".to_vec(), votes, txn, vec![0], true);
+
+        let mut verifier = farmers[0].1.clone();
+        verifier.set_quorum_members(vec![(primitives::QuorumKind::Farmer, members)]);
+
+        assert!(verifier
+            .verify_certified_txn(&certified_txn, &quorum_id, 2)
+            .is_ok());
+        assert!(verifier
+            .verify_certified_txn(&certified_txn, &quorum_id, 3)
+            .is_err());
+    }
+
+    #[test]
+    fn vote_fails_verification_against_another_public_key() {
+        let vote = signed_vote(&Keypair::random());
+
+        assert!(!vote.verify(Keypair::random().get_validator_public_key()));
+    }
+
+    #[test]
+    fn events_round_trip_through_decode() {
+        let event = Event::BlockAppended(String::from("block-1"));
+        let encoded = event.encode().unwrap();
+
+        assert_eq!(Event::decode(&encoded).unwrap(), event);
+
+        let enveloped: Vec<u8> = event.clone().into();
+        assert_eq!(enveloped, encoded);
+        assert_eq!(
+            EventEnvelope::decode(&enveloped).unwrap().version,
+            EVENT_VERSION
+        );
+        assert_eq!(Event::from(enveloped), event);
+    }
+
+    #[test]
+    fn malformed_event_bytes_fail_to_decode() {
+        let malformed = b"{\"BlockAppended\":".to_vec();
+
+        assert!(matches!(
+            Event::decode(&malformed),
+            Err(Error::SerdeJson(_))
+        ));
+        assert!(matches!(
+            EventEnvelope::decode(&malformed),
+            Err(Error::SerdeJson(_))
+        ));
+        assert_eq!(Event::from(malformed), Event::NoOp);
+    }
+
+    #[test]
+    fn envelope_with_another_version_fails_to_decode() {
+        let envelope = EventEnvelope {
+            version: EVENT_VERSION + 1,
+            event: Event::BlockAppended(String::from("block-1")),
+        };
+        let encoded = serde_json::to_vec(&envelope).unwrap();
+
+        assert!(matches!(
+            EventEnvelope::decode(&encoded),
+            Err(Error::UnsupportedVersion { expected, found })
+                if expected == EVENT_VERSION && found == EVENT_VERSION + 1
+        ));
+        assert!(matches!(
+            Event::decode(&encoded),
+            Err(Error::UnsupportedVersion { .. })
+        ));
+        assert_eq!(Event::from(encoded), Event::NoOp);
+    }
+
+    #[test]
+    fn undecodable_events_are_sent_to_the_dead_letter_channel() {
+        let (decoder, mut dead_letters) = EventDecoder::new(1);
+        let invalid = b"not an event: dead letter test".to_vec();
+
+        assert_eq!(
+            decoder.decode(&Event::QuorumFormed.encode().unwrap()),
+            Event::QuorumFormed
+        );
+        assert!(dead_letters.try_recv().is_err());
+
+        assert_eq!(decoder.decode(&invalid), Event::NoOp);
+        // the channel is bounded, dead letters past its capacity are dropped
+        assert_eq!(decoder.decode(b"also not an event"), Event::NoOp);
+
+        let (data, err) = dead_letters.try_recv().unwrap();
+        assert_eq!(data, invalid);
+        assert!(matches!(err, Error::SerdeJson(_)));
+        assert!(dead_letters.try_recv().is_err());
+
+        // decoding keeps working once nobody collects dead letters anymore
+        drop(dead_letters);
+        assert_eq!(decoder.decode(&invalid), Event::NoOp);
+    }
+
+    #[test]
+    fn envelope_with_corrupt_event_fails_to_decode() {
+        let corrupt = format!("{{\"version\":{EVENT_VERSION},\"event\":{{\"NoSuchEvent\":1}}}}");
+
+        assert!(matches!(
+            EventEnvelope::decode(corrupt.as_bytes()),
+            Err(Error::SerdeJson(_))
+        ));
+    }
 }
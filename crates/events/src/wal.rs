@@ -0,0 +1,462 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Event;
+
+/// Position of an event within the write-ahead log.
+pub type WalSeq = u64;
+
+/// Decides whether an `Event` has to be persisted to the write-ahead log
+/// before it is processed.
+pub type WalFilter = fn(&Event) -> bool;
+
+/// Number of times handling an event may fail before it is moved to the
+/// dead-letter log and no longer replayed.
+pub const MAX_EVENT_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum WalRecord {
+    Appended {
+        seq: WalSeq,
+        event: Event,
+    },
+    Processed {
+        seq: WalSeq,
+    },
+    Failed {
+        seq: WalSeq,
+    },
+    /// Written in place of the records of a truncated log, so that sequence
+    /// numbers keep increasing across truncations.
+    Checkpoint {
+        next_seq: WalSeq,
+    },
+}
+
+/// An append-only log of consensus-critical events.
+///
+/// Events accepted by the configured filter are flushed to disk before they
+/// are handed to the event bus and marked as processed once handled. After a
+/// crash, `replay_wal` yields every event that was appended but never marked
+/// as processed, in the order it was appended. The log is truncated whenever
+/// every appended event has been processed, so it only grows with the events
+/// in flight. A truncated log keeps a checkpoint of the next sequence number,
+/// so a sequence number is never handed out twice. Records that can't be
+/// parsed are skipped with a warning.
+///
+/// Failed attempts at handling an event are recorded with `mark_failed`. An
+/// event that failed `MAX_EVENT_ATTEMPTS` times is appended to the
+/// dead-letter log next to the write-ahead log and marked as processed, so
+/// that an event which can never be handled isn't replayed on every start.
+#[derive(Debug)]
+pub struct EventWal {
+    path: PathBuf,
+    file: File,
+    filter: WalFilter,
+    next_seq: WalSeq,
+    pending: BTreeSet<WalSeq>,
+    failures: BTreeMap<WalSeq, u32>,
+}
+
+impl EventWal {
+    /// Opens the log at `path`, creating it if it does not exist yet. A
+    /// trailing record that was only partially written before a crash is
+    /// truncated away so new records start on a clean line.
+    pub fn open(path: impl AsRef<Path>, filter: WalFilter) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        let (records, valid_len) = read_records(&path)?;
+        if file.metadata()?.len() > valid_len {
+            file.set_len(valid_len)?;
+        }
+
+        let next_seq = records
+            .iter()
+            .filter_map(|record| match record {
+                WalRecord::Appended { seq, .. } => Some(seq + 1),
+                WalRecord::Checkpoint { next_seq } => Some(*next_seq),
+                WalRecord::Processed { .. } | WalRecord::Failed { .. } => None,
+            })
+            .max()
+            .unwrap_or_default();
+
+        let failures = failed_attempts(&records);
+        let pending = pending_events(records).into_keys().collect();
+
+        Ok(Self {
+            path,
+            file,
+            filter,
+            next_seq,
+            pending,
+            failures,
+        })
+    }
+
+    /// Opens the log at `path`, persisting only consensus-critical events.
+    /// See `is_consensus_event`.
+    pub fn open_for_consensus(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open(path, is_consensus_event)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the log events that failed `MAX_EVENT_ATTEMPTS` times are
+    /// moved to.
+    pub fn dead_letter_path(&self) -> PathBuf {
+        self.path.with_extension("dead")
+    }
+
+    /// Durably appends `event` to the log if it passes the configured filter,
+    /// returning the sequence number to later acknowledge it with.
+    /// Returns `None` for events that are not persisted.
+    pub fn append(&mut self, event: &Event) -> io::Result<Option<WalSeq>> {
+        if !(self.filter)(event) {
+            return Ok(None);
+        }
+
+        let seq = self.next_seq;
+        self.write_record(&WalRecord::Appended {
+            seq,
+            event: event.clone(),
+        })?;
+        self.next_seq += 1;
+        self.pending.insert(seq);
+
+        Ok(Some(seq))
+    }
+
+    /// Marks the event appended under `seq` as handled so it won't be
+    /// replayed.
+    pub fn mark_processed(&mut self, seq: WalSeq) -> io::Result<()> {
+        self.pending.remove(&seq);
+        self.failures.remove(&seq);
+        if self.pending.is_empty() {
+            self.file.set_len(0)?;
+            return self.write_record(&WalRecord::Checkpoint {
+                next_seq: self.next_seq,
+            });
+        }
+
+        self.write_record(&WalRecord::Processed { seq })
+    }
+
+    /// Records a failed attempt at handling `event`, appended under `seq`.
+    /// Once the event failed `MAX_EVENT_ATTEMPTS` times it is moved to the
+    /// dead-letter log and marked as processed, in which case `true` is
+    /// returned.
+    pub fn mark_failed(&mut self, seq: WalSeq, event: &Event) -> io::Result<bool> {
+        if !self.pending.contains(&seq) {
+            return Ok(false);
+        }
+
+        let attempts = self.failures.entry(seq).or_default();
+        *attempts += 1;
+        if *attempts < MAX_EVENT_ATTEMPTS {
+            self.write_record(&WalRecord::Failed { seq })?;
+            return Ok(false);
+        }
+
+        let mut line = serde_json::to_vec(&WalRecord::Appended {
+            seq,
+            event: event.clone(),
+        })?;
+        line.push(b'\n');
+
+        let mut dead_letters = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dead_letter_path())?;
+        dead_letters.write_all(&line)?;
+        dead_letters.sync_data()?;
+
+        self.mark_processed(seq)?;
+
+        Ok(true)
+    }
+
+    /// Returns all events that were appended but never marked as processed,
+    /// ordered by the time they were appended.
+    pub fn replay_wal(&self) -> io::Result<Vec<(WalSeq, Event)>> {
+        let (records, _) = read_records(&self.path)?;
+
+        Ok(pending_events(records).into_iter().collect())
+    }
+
+    fn write_record(&mut self, record: &WalRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        self.file.write_all(&line)?;
+        self.file.sync_data()
+    }
+}
+
+/// Default `WalFilter`, selecting the events that quorum formation, key
+/// generation and block certification cannot be recovered without.
+pub fn is_consensus_event(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::QuorumMembershipAssigmentCreated(_)
+            | Event::QuorumMembershipAssigmentsCreated(_)
+            | Event::QuorumFormed
+            | Event::PartCommitmentCreated(..)
+            | Event::PartCommitmentAcknowledged { .. }
+            | Event::HarvesterPublicKeyReceived(_)
+            | Event::HarvesterSignatureReceived(..)
+            | Event::BlockCertificateCreated(_)
+            | Event::ConvergenceBlockCertified(_)
+    )
+}
+
+/// Events appended by `records` and not marked as processed, by sequence.
+fn pending_events(records: Vec<WalRecord>) -> BTreeMap<WalSeq, Event> {
+    let mut pending = BTreeMap::new();
+
+    for record in records {
+        match record {
+            WalRecord::Appended { seq, event } => {
+                pending.insert(seq, event);
+            }
+            WalRecord::Processed { seq } => {
+                pending.remove(&seq);
+            }
+            WalRecord::Failed { .. } | WalRecord::Checkpoint { .. } => {}
+        }
+    }
+
+    pending
+}
+
+/// Number of failed attempts `records` hold for each event.
+fn failed_attempts(records: &[WalRecord]) -> BTreeMap<WalSeq, u32> {
+    let mut failures = BTreeMap::new();
+
+    for record in records {
+        match record {
+            WalRecord::Failed { seq } => *failures.entry(*seq).or_default() += 1,
+            WalRecord::Processed { seq } => {
+                failures.remove(seq);
+            }
+            WalRecord::Appended { .. } | WalRecord::Checkpoint { .. } => {}
+        }
+    }
+
+    failures
+}
+
+/// Reads every complete record in the log, along with the length in bytes of
+/// the prefix they occupy. A trailing record that was only partially written
+/// before a crash is ignored. Complete lines that can't be parsed are skipped
+/// and reported, so that one corrupt record doesn't keep the node from
+/// starting.
+fn read_records(path: &Path) -> io::Result<(Vec<WalRecord>, u64)> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(err) => return Err(err),
+    };
+
+    let mut reader = BufReader::new(file);
+    let mut records = Vec::new();
+    let mut valid_len = 0;
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 || !line.ends_with('\n') {
+            break;
+        }
+        line_number += 1;
+
+        match serde_json::from_str(line.trim_end()) {
+            Ok(record) => records.push(record),
+            Err(err) => telemetry::warn!(
+                "skipping malformed record on line {line_number} of event write-ahead log {}: {err}",
+                path.display()
+            ),
+        }
+        valid_len += read as u64;
+    }
+
+    Ok((records, valid_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wal_path() -> PathBuf {
+        std::env::temp_dir().join(format!("events-{}.wal", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn unprocessed_consensus_events_are_replayed_in_order_after_restart() {
+        let path = wal_path();
+        let consensus_events = vec![
+            Event::QuorumFormed,
+            Event::QuorumMembershipAssigmentsCreated(vec![]),
+        ];
+
+        {
+            let mut wal = EventWal::open_for_consensus(&path).unwrap();
+
+            let first = wal.append(&consensus_events[0]).unwrap().unwrap();
+            assert_eq!(wal.append(&Event::NoOp).unwrap(), None);
+            wal.append(&consensus_events[1]).unwrap().unwrap();
+            assert_eq!(
+                wal.append(&Event::TxnAddedToMempool(Default::default()))
+                    .unwrap(),
+                None
+            );
+
+            wal.mark_processed(first).unwrap();
+        }
+
+        let mut wal = EventWal::open_for_consensus(&path).unwrap();
+        wal.append(&Event::QuorumFormed).unwrap().unwrap();
+
+        let replayed: Vec<Event> = wal
+            .replay_wal()
+            .unwrap()
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect();
+
+        assert_eq!(
+            replayed,
+            vec![consensus_events[1].clone(), Event::QuorumFormed]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn replay_ignores_torn_trailing_record() {
+        let path = wal_path();
+
+        {
+            let mut wal = EventWal::open_for_consensus(&path).unwrap();
+            wal.append(&Event::QuorumFormed).unwrap();
+        }
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"Appended\":{\"seq\":1,").unwrap();
+
+        let mut wal = EventWal::open_for_consensus(&path).unwrap();
+        assert_eq!(wal.replay_wal().unwrap(), vec![(0, Event::QuorumFormed)]);
+
+        wal.append(&Event::QuorumFormed).unwrap();
+        assert_eq!(
+            wal.replay_wal().unwrap(),
+            vec![(0, Event::QuorumFormed), (1, Event::QuorumFormed)]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn log_is_truncated_once_every_event_is_processed() {
+        let path = wal_path();
+        let mut wal = EventWal::open_for_consensus(&path).unwrap();
+
+        let first = wal.append(&Event::QuorumFormed).unwrap().unwrap();
+        let second = wal.append(&Event::QuorumFormed).unwrap().unwrap();
+
+        wal.mark_processed(first).unwrap();
+        assert_ne!(std::fs::metadata(&path).unwrap().len(), 0);
+
+        wal.mark_processed(second).unwrap();
+        let (records, _) = read_records(&path).unwrap();
+        assert_eq!(records, vec![WalRecord::Checkpoint { next_seq: 2 }]);
+        assert!(wal.replay_wal().unwrap().is_empty());
+
+        let third = wal.append(&Event::QuorumFormed).unwrap().unwrap();
+        assert_eq!(third, 2);
+        assert_eq!(
+            EventWal::open_for_consensus(&path)
+                .unwrap()
+                .replay_wal()
+                .unwrap(),
+            vec![(third, Event::QuorumFormed)]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn sequence_numbers_keep_increasing_after_the_log_is_truncated() {
+        let path = wal_path();
+
+        {
+            let mut wal = EventWal::open_for_consensus(&path).unwrap();
+            let first = wal.append(&Event::QuorumFormed).unwrap().unwrap();
+            wal.mark_processed(first).unwrap();
+        }
+
+        let mut wal = EventWal::open_for_consensus(&path).unwrap();
+        assert_eq!(wal.append(&Event::QuorumFormed).unwrap(), Some(1));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn malformed_records_are_skipped_when_opening_the_log() {
+        let path = wal_path();
+
+        {
+            let mut wal = EventWal::open_for_consensus(&path).unwrap();
+            wal.append(&Event::QuorumFormed).unwrap();
+        }
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"not a record\n").unwrap();
+
+        let mut wal = EventWal::open_for_consensus(&path).unwrap();
+        wal.append(&Event::QuorumFormed).unwrap();
+        assert_eq!(
+            wal.replay_wal().unwrap(),
+            vec![(0, Event::QuorumFormed), (1, Event::QuorumFormed)]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn events_failing_too_often_are_moved_to_the_dead_letter_log() {
+        let path = wal_path();
+        let event = Event::QuorumFormed;
+
+        let seq = {
+            let mut wal = EventWal::open_for_consensus(&path).unwrap();
+            let seq = wal.append(&event).unwrap().unwrap();
+            for _ in 1..MAX_EVENT_ATTEMPTS {
+                assert!(!wal.mark_failed(seq, &event).unwrap());
+            }
+            seq
+        };
+
+        // failed attempts survive a restart
+        let mut wal = EventWal::open_for_consensus(&path).unwrap();
+        assert_eq!(wal.replay_wal().unwrap(), vec![(seq, event.clone())]);
+        assert!(wal.mark_failed(seq, &event).unwrap());
+
+        assert!(wal.replay_wal().unwrap().is_empty());
+        let (dead_letters, _) = read_records(&wal.dead_letter_path()).unwrap();
+        assert_eq!(dead_letters, vec![WalRecord::Appended { seq, event }]);
+
+        std::fs::remove_file(wal.dead_letter_path()).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
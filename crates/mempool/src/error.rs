@@ -14,4 +14,7 @@ pub enum MempoolError {
 
     #[error("transaction {0} already exists")]
     TransactionExists(TransactionDigest),
+
+    #[error("mempool is full")]
+    Full,
 }
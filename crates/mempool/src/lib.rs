@@ -1,10 +1,12 @@
 pub mod error;
 pub mod mempool;
+pub mod validator;
 
 use anyhow::Context;
 use reqwest::StatusCode;
 
 pub use crate::mempool::*;
+pub use crate::validator::*;
 
 pub async fn create_tx_indexer(txn_record: &TxnRecord) -> anyhow::Result<StatusCode> {
     let url = "http://localhost:3444/transactions"; // TODO: Move to config
@@ -42,7 +44,9 @@ mod tests {
     use vrrb_core::keypair::KeyPair;
     use vrrb_core::transactions::{Transaction, TransactionKind};
 
-    use crate::mempool::{LeftRightMempool, TxnRecord};
+    use crate::error::MempoolError;
+    use crate::mempool::{LeftRightMempool, MempoolConfig, TxnRecord, TxnStatus};
+    use crate::validator::MempoolValidator;
 
     fn mock_txn_signature() -> Signature {
         ecdsa::Signature::from_compact(&[
@@ -181,6 +185,82 @@ mod tests {
         };
     }
 
+    #[test]
+    fn get_txn_returns_the_inserted_txn() {
+        let keypair = KeyPair::random();
+        let recv_keypair = KeyPair::random();
+
+        let txn = TransactionKind::transfer_builder()
+            .timestamp(chrono::offset::Utc::now().timestamp())
+            .sender_address(Address::new(*keypair.get_miner_public_key()))
+            .sender_public_key(*keypair.get_miner_public_key())
+            .receiver_address(Address::new(*recv_keypair.get_miner_public_key()))
+            .amount(10)
+            .validators(HashMap::<String, bool>::new())
+            .nonce(0)
+            .signature(mock_txn_signature())
+            .build_kind()
+            .expect("Failed to build transaction");
+
+        let mut mpooldb = LeftRightMempool::new();
+        mpooldb.insert(txn.clone()).unwrap();
+
+        assert_eq!(mpooldb.get_txn(&txn.digest()), Some(txn.clone()));
+        assert_eq!(mpooldb.get(&txn.digest()).unwrap().txn, txn);
+    }
+
+    #[test]
+    fn validator_rejected_txns_land_in_the_rejected_pool() {
+        #[derive(Debug)]
+        struct RejectSender(Address);
+
+        impl MempoolValidator for RejectSender {
+            fn validate(&self, txn: &TransactionKind) -> Result<(), MempoolError> {
+                if txn.sender_address() == self.0 {
+                    return Err(MempoolError::TransactionInvalid(txn.id()));
+                }
+
+                Ok(())
+            }
+        }
+
+        let txn_from = |keypair: &KeyPair| {
+            TransactionKind::transfer_builder()
+                .timestamp(chrono::offset::Utc::now().timestamp())
+                .sender_address(Address::new(*keypair.get_miner_public_key()))
+                .sender_public_key(*keypair.get_miner_public_key())
+                .receiver_address(Address::new(*KeyPair::random().get_miner_public_key()))
+                .amount(10)
+                .validators(HashMap::<String, bool>::new())
+                .nonce(0)
+                .signature(mock_txn_signature())
+                .build_kind()
+                .expect("Failed to build transaction")
+        };
+
+        let banned = KeyPair::random();
+        let rejected = txn_from(&banned);
+        let accepted = txn_from(&KeyPair::random());
+
+        let mut mpooldb = LeftRightMempool::new();
+        mpooldb.set_validator(RejectSender(Address::new(*banned.get_miner_public_key())));
+
+        assert_eq!(
+            mpooldb.insert(rejected.clone()),
+            Err(MempoolError::TransactionInvalid(rejected.id()))
+        );
+        mpooldb.insert(accepted.clone()).unwrap();
+
+        assert_eq!(mpooldb.size(), 1);
+        assert!(mpooldb.get(&rejected.id()).is_none());
+        assert!(mpooldb.get(&accepted.id()).is_some());
+
+        let record = mpooldb.get_rejected(&rejected.id()).unwrap();
+        assert_eq!(record.txn, rejected);
+        assert_eq!(record.status, TxnStatus::Rejected);
+        assert_eq!(mpooldb.rejected_len(), 1);
+    }
+
     #[tokio::test]
     async fn add_and_retrieve_txn() {
         let keypair = KeyPair::random();
@@ -537,4 +617,192 @@ mod tests {
                 handle.join().unwrap();
             });
     }
+
+    #[test]
+    fn read_handle_staleness_resets_on_refresh() {
+        let keypair = KeyPair::random();
+        let recv_keypair = KeyPair::random();
+        let mut lrmpooldb = LeftRightMempool::new();
+        let factory = lrmpooldb.factory();
+
+        let txn = TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(*keypair.get_miner_public_key()))
+            .sender_public_key(*keypair.get_miner_public_key())
+            .receiver_address(Address::new(*recv_keypair.get_miner_public_key()))
+            .amount(100)
+            .validators(HashMap::<String, bool>::new())
+            .nonce(0)
+            .signature(mock_txn_signature())
+            .build_kind()
+            .expect("Failed to build transaction");
+
+        lrmpooldb.append(txn.clone()).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+
+        let staleness = factory.staleness();
+        assert!(staleness >= std::time::Duration::from_millis(20));
+        assert!(factory.get(&txn.id()).is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(factory.staleness() > staleness);
+
+        lrmpooldb.refresh();
+
+        assert!(factory.staleness() < staleness);
+        assert!(lrmpooldb.staleness() < staleness);
+        assert!(factory.get(&txn.id()).is_some());
+    }
+
+    fn transfers(amount: usize) -> Vec<TransactionKind> {
+        let keypair = KeyPair::random();
+        let recv_keypair = KeyPair::random();
+
+        let transfer_builder = TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(*keypair.get_miner_public_key()))
+            .sender_public_key(*keypair.get_miner_public_key())
+            .receiver_address(Address::new(*recv_keypair.get_miner_public_key()))
+            .validators(HashMap::<String, bool>::new())
+            .signature(mock_txn_signature());
+
+        (0..amount)
+            .map(|n| {
+                transfer_builder
+                    .clone()
+                    .amount(100)
+                    .nonce(n as u128)
+                    .build_kind()
+                    .expect("Failed to build transaction")
+            })
+            .collect()
+    }
+
+    fn bounded_mempool(max_capacity: usize) -> LeftRightMempool {
+        LeftRightMempool::with_config(MempoolConfig {
+            initial_capacity: max_capacity,
+            max_capacity: Some(max_capacity),
+        })
+    }
+
+    #[test]
+    fn full_mempool_rejects_insertions() {
+        let mut txns = transfers(4);
+        let overflow = txns.pop().unwrap();
+        let mut lrmpooldb = bounded_mempool(3);
+
+        for txn in txns.iter().cloned() {
+            lrmpooldb.insert(txn).unwrap();
+        }
+
+        assert_eq!(lrmpooldb.insert(overflow.clone()), Err(MempoolError::Full));
+        assert_eq!(lrmpooldb.append(overflow.clone()), Err(MempoolError::Full));
+        assert_eq!(lrmpooldb.size(), 3);
+        assert!(lrmpooldb.get(&overflow.id()).is_none());
+
+        // Re-inserting a pooled transaction doesn't grow the pool.
+        lrmpooldb.insert(txns[0].clone()).unwrap();
+
+        lrmpooldb.remove(&txns[0].id()).unwrap();
+        lrmpooldb.insert(overflow).unwrap();
+        assert_eq!(lrmpooldb.size(), 3);
+    }
+
+    #[test]
+    fn full_mempool_rejects_whole_batches() {
+        let txns = transfers(5);
+        let mut lrmpooldb = bounded_mempool(4);
+
+        lrmpooldb
+            .extend(txns[..2].iter().cloned().collect())
+            .unwrap();
+
+        assert_eq!(
+            lrmpooldb.extend(txns[2..].iter().cloned().collect()),
+            Err(MempoolError::Full)
+        );
+        assert_eq!(lrmpooldb.size(), 2);
+
+        lrmpooldb.append(txns[2].clone()).unwrap();
+        lrmpooldb.append(txns[3].clone()).unwrap();
+        assert_eq!(lrmpooldb.append(txns[4].clone()), Err(MempoolError::Full));
+
+        lrmpooldb.refresh();
+        assert_eq!(lrmpooldb.size(), 4);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn pending_for_sender_skips_txns_that_are_no_longer_pending() {
+        let txns = transfers(1);
+        let sender = txns[0].sender_address();
+
+        let mut lrmpooldb = LeftRightMempool::new();
+        lrmpooldb.add_txn(&txns[0], TxnStatus::Validated).unwrap();
+
+        assert_eq!(lrmpooldb.size(), 1);
+        assert!(lrmpooldb.pending_for_sender(&sender).is_empty());
+        assert!(lrmpooldb.factory().pending_for_sender(&sender).is_empty());
+    }
+
+    #[test]
+    fn pending_for_sender_returns_sender_txns_by_nonce() {
+        let mut sender_txns = transfers(4);
+        let other_txns = transfers(3);
+        let sender = sender_txns[0].sender_address();
+
+        let mut lrmpooldb = LeftRightMempool::new();
+        for txn in sender_txns.iter().rev().chain(other_txns.iter()) {
+            lrmpooldb.insert(txn.clone()).unwrap();
+        }
+
+        let pending: Vec<TransactionKind> = lrmpooldb
+            .pending_for_sender(&sender)
+            .into_iter()
+            .map(|record| record.txn)
+            .collect();
+
+        sender_txns.sort_by_key(|txn| txn.nonce());
+        assert_eq!(pending, sender_txns);
+        assert_eq!(
+            lrmpooldb.factory().pending_for_sender(&sender).len(),
+            sender_txns.len()
+        );
+    }
+
+    #[test]
+    fn mempool_built_from_pool_keeps_its_records() {
+        let txns = transfers(3);
+        let mut source = LeftRightMempool::new();
+        source.extend(txns.iter().cloned().collect()).unwrap();
+
+        let mut from_pool = LeftRightMempool::from(source.pool());
+        let mempool = source.read.enter().map(|guard| guard.clone()).unwrap();
+        let mut from_mempool = LeftRightMempool::from(mempool);
+
+        assert_eq!(from_pool.size(), txns.len());
+        assert_eq!(from_mempool.size(), txns.len());
+        for txn in txns {
+            assert_eq!(from_pool.get(&txn.id()), source.get(&txn.id()));
+            assert_eq!(from_mempool.get(&txn.id()), source.get(&txn.id()));
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn inserted_txns_are_pending() {
+        let txns = transfers(2);
+        let mut lrmpooldb = LeftRightMempool::new();
+
+        lrmpooldb.insert(txns[0].clone()).unwrap();
+        lrmpooldb.add_txn(&txns[1], TxnStatus::Validated).unwrap();
+
+        let record = lrmpooldb.get(&txns[0].id()).unwrap();
+        assert_eq!(record.txn, txns[0]);
+        assert_eq!(record.status, TxnStatus::Pending);
+        assert_eq!(
+            lrmpooldb.get(&txns[1].id()).unwrap().status,
+            TxnStatus::Validated
+        );
+    }
 }
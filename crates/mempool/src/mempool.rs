@@ -2,15 +2,21 @@ use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
     result::Result as StdResult,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
 use fxhash::FxBuildHasher;
 use indexmap::IndexMap;
 use left_right::{Absorb, ReadHandle, ReadHandleFactory, WriteHandle};
+use primitives::Address;
+use ritelinked::LinkedHashMap;
 use serde::{Deserialize, Serialize};
+pub use vrrb_config::{MempoolConfig, DEFAULT_INITIAL_MEMPOOL_CAPACITY};
 use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, TxTimestamp};
 
 use super::error::MempoolError;
+use crate::validator::MempoolValidator;
 
 pub type Result<T> = StdResult<T, MempoolError>;
 
@@ -27,13 +33,21 @@ pub struct TxnRecord {
 }
 
 impl TxnRecord {
+    /// Creates the record of a newly added transaction, as
+    /// `TxnStatus::Pending`.
     pub fn new(txn: TransactionKind) -> TxnRecord {
+        Self::with_status(txn, TxnStatus::Pending)
+    }
+
+    /// Creates the record of a newly added transaction with `status`.
+    pub fn with_status(txn: TransactionKind, status: TxnStatus) -> TxnRecord {
         let added_timestamp = chrono::offset::Utc::now().timestamp();
         let timestamp = txn.timestamp();
 
         TxnRecord {
             txn_id: txn.id(),
             txn,
+            status,
             timestamp,
             added_timestamp,
             ..Default::default()
@@ -65,20 +79,23 @@ pub struct Mempool {
     pool: PoolType,
 }
 
-pub const DEFAULT_INITIAL_MEMPOOL_CAPACITY: usize = 10000;
+/// Number of rejected transactions a `LeftRightMempool` remembers. The oldest
+/// rejections are forgotten first.
+pub const MAX_REJECTED_TXNS: usize = 10_000;
 
 impl Default for Mempool {
     fn default() -> Self {
-        Mempool {
-            pool: PoolType::with_capacity_and_hasher(
-                DEFAULT_INITIAL_MEMPOOL_CAPACITY,
-                <_>::default(),
-            ),
-        }
+        Mempool::with_capacity(DEFAULT_INITIAL_MEMPOOL_CAPACITY)
     }
 }
 
 impl Mempool {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Mempool {
+            pool: PoolType::with_capacity_and_hasher(capacity, <_>::default()),
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.pool.len()
     }
@@ -86,6 +103,24 @@ impl Mempool {
     pub fn is_empty(&self) -> bool {
         self.pool.is_empty()
     }
+
+    /// Returns the records of the pending transactions sent by `address`,
+    /// ordered by nonce. Transactions being or already validated are left
+    /// out.
+    pub fn pending_for_sender(&self, address: &Address) -> Vec<TxnRecord> {
+        let mut records: Vec<TxnRecord> = self
+            .pool
+            .values()
+            .filter(|record| {
+                matches!(record.status, TxnStatus::Pending)
+                    && &record.txn.sender_address() == address
+            })
+            .cloned()
+            .collect();
+
+        records.sort_by_key(|record| record.txn.nonce());
+        records
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -136,17 +171,59 @@ impl FetchFiltered for ReadHandle<Mempool> {
     }
 }
 
+/// Time at which the writer of a `LeftRightMempool` last published its
+/// changes to the readers.
+#[derive(Clone, Debug)]
+pub struct PublishClock {
+    last_published: Arc<RwLock<Instant>>,
+}
+
+impl Default for PublishClock {
+    fn default() -> Self {
+        Self {
+            last_published: Arc::new(RwLock::new(Instant::now())),
+        }
+    }
+}
+
+impl PublishClock {
+    /// Returns the time of the last publish.
+    pub fn last_published(&self) -> Instant {
+        self.last_published
+            .read()
+            .map(|last_published| *last_published)
+            .unwrap_or_else(|_| Instant::now())
+    }
+
+    /// Returns how long ago readers last received the writer's changes.
+    pub fn staleness(&self) -> Duration {
+        self.last_published().elapsed()
+    }
+
+    fn tick(&self) {
+        if let Ok(mut last_published) = self.last_published.write() {
+            *last_published = Instant::now();
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct LeftRightMempool {
     pub read: ReadHandle<Mempool>,
     pub write: WriteHandle<Mempool, MempoolOp>,
+    clock: PublishClock,
+    max_capacity: Option<usize>,
+    /// Number of additions appended since the last publish, which readers
+    /// and therefore the capacity checks can't see yet.
+    pending_additions: usize,
+    validator: Option<Arc<dyn MempoolValidator>>,
+    /// Transactions turned down by the validator, kept apart from the pool.
+    rejected: LinkedHashMap<TransactionDigest, TxnRecord>,
 }
 
 impl Default for LeftRightMempool {
     fn default() -> Self {
-        let (write, read) = left_right::new::<Mempool, MempoolOp>();
-
-        LeftRightMempool { read, write }
+        Self::with_config(MempoolConfig::default())
     }
 }
 
@@ -156,6 +233,45 @@ impl LeftRightMempool {
         Self::default()
     }
 
+    /// Creates a new Mempool DB sized according to `config`.
+    pub fn with_config(config: MempoolConfig) -> Self {
+        let (write, read) = left_right::new_from_empty::<Mempool, MempoolOp>(
+            Mempool::with_capacity(config.initial_capacity),
+        );
+
+        LeftRightMempool {
+            read,
+            write,
+            clock: PublishClock::default(),
+            max_capacity: config.max_capacity,
+            pending_additions: 0,
+            validator: None,
+            rejected: LinkedHashMap::new(),
+        }
+    }
+
+    /// Screens every transaction added from now on with `validator`.
+    /// Transactions it rejects are kept out of the pool and recorded as
+    /// rejected instead.
+    pub fn set_validator(&mut self, validator: impl MempoolValidator + 'static) {
+        self.validator = Some(Arc::new(validator));
+    }
+
+    /// Returns the record of a transaction the validator rejected.
+    pub fn get_rejected(&self, txn_id: &TransactionDigest) -> Option<&TxnRecord> {
+        self.rejected.get(txn_id)
+    }
+
+    /// Number of rejected transactions remembered.
+    pub fn rejected_len(&self) -> usize {
+        self.rejected.len()
+    }
+
+    /// Maximum number of transactions this mempool holds, if bounded.
+    pub fn max_capacity(&self) -> Option<usize> {
+        self.max_capacity
+    }
+
     /// Getter for Mempool DB
     pub fn pool(&self) -> PoolType {
         self.read
@@ -175,7 +291,10 @@ impl LeftRightMempool {
     pub fn factory(&self) -> MempoolReadHandleFactory {
         let factory = self.read.factory();
 
-        MempoolReadHandleFactory { factory }
+        MempoolReadHandleFactory {
+            factory,
+            clock: self.clock.clone(),
+        }
     }
 
     /// Return the number of key-value pairs in the map.
@@ -188,30 +307,102 @@ impl LeftRightMempool {
         self.pool().is_empty()
     }
 
-    /// Adds a new transaction, makes sure it is unique in db.
-    /// Pushes to the ReadHandle.
+    /// Returns the pending transactions sent by `address`, ordered by nonce.
+    pub fn pending_for_sender(&self, address: &Address) -> Vec<TxnRecord> {
+        self.read
+            .enter()
+            .map(|guard| guard.pending_for_sender(address))
+            .unwrap_or_default()
+    }
+
+    /// Adds a new transaction with the given status, makes sure it is unique
+    /// in db. Pushes to the ReadHandle.
     #[deprecated(note = "use Self::insert instead")]
-    pub fn add_txn(&mut self, txn: &TransactionKind, _status: TxnStatus) -> Result<()> {
-        self.insert(txn.to_owned())?;
-        Ok(())
+    pub fn add_txn(&mut self, txn: &TransactionKind, status: TxnStatus) -> Result<()> {
+        let txn_record = TxnRecord::with_status(txn.to_owned(), status);
+
+        self.extend_with_records(HashSet::from([txn_record]))
     }
 
+    /// Adds a new transaction as `TxnStatus::Pending` and pushes it to the
+    /// ReadHandle. Returns the size of the pool in kilobytes.
     pub fn insert(&mut self, txn: TransactionKind) -> Result<usize> {
-        let txn_record = TxnRecord::new(txn);
-        self.write
-            .append(MempoolOp::Add(Box::new(txn_record)))
-            .publish();
+        self.append(txn)?;
+        self.publish();
 
         Ok(self.size_in_kilobytes())
     }
 
+    /// Adds a new transaction without publishing it. Readers won't see it
+    /// until the next call to `refresh` or to any method that publishes.
+    pub fn append(&mut self, txn: TransactionKind) -> Result<()> {
+        self.ensure_capacity_for([txn.id()].iter())?;
+        self.screen(&txn)?;
+
+        let txn_record = TxnRecord::new(txn);
+        self.write.append(MempoolOp::Add(Box::new(txn_record)));
+        self.pending_additions += 1;
+
+        Ok(())
+    }
+
+    /// Fails with `MempoolError::Full` if adding the transactions identified
+    /// by `ids` would grow the pool past its maximum capacity. Transactions
+    /// that are already pooled don't count towards it, since adding them
+    /// again only replaces their record.
+    fn ensure_capacity_for<'a>(
+        &self,
+        ids: impl Iterator<Item = &'a TransactionDigest>,
+    ) -> Result<()> {
+        let Some(max_capacity) = self.max_capacity else {
+            return Ok(());
+        };
+
+        let (pooled, additions) = self
+            .read
+            .enter()
+            .map(|guard| {
+                let additions = ids.filter(|id| !guard.pool.contains_key(*id)).count();
+                (guard.len(), additions)
+            })
+            .unwrap_or_default();
+
+        if pooled + self.pending_additions + additions > max_capacity {
+            return Err(MempoolError::Full);
+        }
+
+        Ok(())
+    }
+
+    /// Runs `txn` through the validator, if any, recording it as rejected
+    /// when it fails.
+    fn screen(&mut self, txn: &TransactionKind) -> Result<()> {
+        let Some(validator) = &self.validator else {
+            return Ok(());
+        };
+
+        if let Err(err) = validator.validate(txn) {
+            let record = TxnRecord {
+                status: TxnStatus::Rejected,
+                rejected_timestamp: chrono::offset::Utc::now().timestamp(),
+                ..TxnRecord::new(txn.to_owned())
+            };
+
+            self.rejected.insert(record.txn_id.clone(), record);
+            while self.rejected.len() > MAX_REJECTED_TXNS {
+                self.rejected.pop_front();
+            }
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
     /// Retrieves a single transaction identified by id, makes sure it exists in
-    /// db
+    /// db. Records store the transaction itself, so it's returned as is.
     pub fn get_txn(&mut self, txn_hash: &TransactionDigest) -> Option<TransactionKind> {
-        if let Some(record) = self.get(txn_hash) {
-            return Some(record.txn);
-        }
-        None
+        self.get(txn_hash).map(|record| record.txn)
     }
 
     /// Getter for an entire pending Txn record
@@ -256,10 +447,18 @@ impl LeftRightMempool {
         self.extend(txn_batch.clone())
     }
 
+    /// Adds a batch of transactions. If the pool has no room for the entire
+    /// batch, none of them is added. Transactions the validator rejects are
+    /// left out of the pool while the rest of the batch is added.
     pub fn extend(&mut self, txn_batch: HashSet<TransactionKind>) -> Result<()> {
+        let ids: Vec<TransactionDigest> = txn_batch.iter().map(|txn| txn.id()).collect();
+        self.ensure_capacity_for(ids.iter())?;
+
         txn_batch.into_iter().for_each(|t| {
-            self.write
-                .append(MempoolOp::Add(Box::new(TxnRecord::new(t))));
+            if self.screen(&t).is_ok() {
+                self.write
+                    .append(MempoolOp::Add(Box::new(TxnRecord::new(t))));
+            }
         });
 
         self.publish();
@@ -267,8 +466,12 @@ impl LeftRightMempool {
     }
 
     pub fn extend_with_records(&mut self, record_batch: HashSet<TxnRecord>) -> Result<()> {
+        self.ensure_capacity_for(record_batch.iter().map(|record| &record.txn_id))?;
+
         record_batch.into_iter().for_each(|t| {
-            self.write.append(MempoolOp::Add(Box::new(t)));
+            if self.screen(&t.txn).is_ok() {
+                self.write.append(MempoolOp::Add(Box::new(t)));
+            }
         });
 
         self.publish();
@@ -277,9 +480,8 @@ impl LeftRightMempool {
 
     /// Removes a single transaction by [`TransactionDigest`].
     pub fn remove(&mut self, id: &TransactionDigest) -> Result<()> {
-        self.write
-            .append(MempoolOp::Remove(id.to_owned()))
-            .publish();
+        self.write.append(MempoolOp::Remove(id.to_owned()));
+        self.publish();
         Ok(())
     }
 
@@ -319,16 +521,30 @@ impl LeftRightMempool {
         (mempool_items * txn_size_factor) / 1024
     }
 
+    /// Returns how long ago changes were last pushed to the readers.
+    pub fn staleness(&self) -> Duration {
+        self.clock.staleness()
+    }
+
+    /// Forces any pending changes to be pushed to the readers.
+    pub fn refresh(&mut self) {
+        self.publish();
+    }
+
     /// Pushes changes to Reader.
     fn publish(&mut self) {
         self.write.publish();
+        self.clock.tick();
+        self.pending_additions = 0;
     }
 }
 
 impl From<PoolType> for LeftRightMempool {
     fn from(pool: PoolType) -> Self {
-        let (write, read) = left_right::new::<Mempool, MempoolOp>();
-        let mut mempool_db = Self { read, write };
+        let mut mempool_db = Self::with_config(MempoolConfig {
+            initial_capacity: pool.len().max(DEFAULT_INITIAL_MEMPOOL_CAPACITY),
+            max_capacity: None,
+        });
 
         let records = pool.values().cloned().collect::<HashSet<TxnRecord>>();
 
@@ -338,18 +554,43 @@ impl From<PoolType> for LeftRightMempool {
     }
 }
 
+impl From<Mempool> for LeftRightMempool {
+    fn from(mempool: Mempool) -> Self {
+        Self::from(mempool.pool)
+    }
+}
+
 impl Clone for LeftRightMempool {
     fn clone(&self) -> Self {
-        Self::from(self.pool())
+        let mut mempool_db = Self::from(self.pool());
+        mempool_db.max_capacity = self.max_capacity;
+        mempool_db.validator = self.validator.clone();
+        mempool_db.rejected = self.rejected.clone();
+
+        mempool_db
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct MempoolReadHandleFactory {
     factory: ReadHandleFactory<Mempool>,
+    clock: PublishClock,
 }
 
 impl MempoolReadHandleFactory {
+    /// Returns the time at which the mempool's writer last published changes
+    /// visible through this factory.
+    pub fn last_published(&self) -> Instant {
+        self.clock.last_published()
+    }
+
+    /// Returns how long ago the view served by this factory was last
+    /// refreshed. Consumers that need a recent view, like block builders,
+    /// can compare it against a bound before reading.
+    pub fn staleness(&self) -> Duration {
+        self.clock.staleness()
+    }
+
     pub fn handle(&self) -> PoolType {
         self.factory
             .handle()
@@ -377,6 +618,15 @@ impl MempoolReadHandleFactory {
             .collect()
     }
 
+    /// Returns the pending transactions sent by `address`, ordered by nonce.
+    pub fn pending_for_sender(&self, address: &Address) -> Vec<TxnRecord> {
+        self.factory
+            .handle()
+            .enter()
+            .map(|guard| guard.pending_for_sender(address))
+            .unwrap_or_default()
+    }
+
     pub fn get(&self, digest: &TransactionDigest) -> Option<TxnRecord> {
         if let Some(record) = self.handle().get(digest) {
             return Some(record.clone());
@@ -0,0 +1,12 @@
+use vrrb_core::transactions::TransactionKind;
+
+use crate::mempool::Result;
+
+/// Checks transactions before a `LeftRightMempool` admits them.
+///
+/// Implementations decide which transactions a node is willing to pool,
+/// without the mempool depending on any particular validator.
+pub trait MempoolValidator: std::fmt::Debug + Send + Sync {
+    /// Fails with the reason `txn` can't be pooled.
+    fn validate(&self, txn: &TransactionKind) -> Result<()>;
+}
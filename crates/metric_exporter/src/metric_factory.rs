@@ -1,4 +1,5 @@
 use crate::render::RenderToPrometheus;
+use hyper::header::{HeaderValue, CONTENT_TYPE};
 use hyper::server::conn::AddrIncoming;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Response, Server};
@@ -305,7 +306,11 @@ impl PrometheusFactory {
             PrometheusFactory::append_base_metrics(HashMap::new(), &mut factory)?;
         }
         let response_body = factory.render_metrics()?;
-        Ok(Response::new(Body::from(response_body)))
+        let mut response = Response::new(Body::from(response_body));
+        if let Ok(content_type) = HeaderValue::from_str(&TextEncoder::new().format_type()) {
+            response.headers_mut().insert(CONTENT_TYPE, content_type);
+        }
+        Ok(response)
     }
 
     pub async fn serve(
@@ -432,4 +437,48 @@ mod tests {
         factory.reset_registry();
         assert_eq!(factory.gather_metrics().len(), 0);
     }
+
+    #[tokio::test]
+    async fn exporter_renders_registered_metrics_in_text_format() {
+        let factory = PrometheusFactory::new(
+            String::from("127.0.0.1"),
+            0,
+            false,
+            HashMap::new(),
+            "examples/sample.rsa".to_string(),
+            "examples/sample.pem".to_string(),
+            CancellationToken::new(),
+        )
+        .unwrap();
+        let counter = factory
+            .build_int_counter(
+                "exporter_test_requests_total",
+                "No of requests handled by the test",
+                HashMap::new(),
+            )
+            .unwrap();
+        counter.inc_by(3);
+
+        // Metrics can only be registered once per factory
+        assert!(factory
+            .build_int_counter(
+                "exporter_test_requests_total",
+                "No of requests handled by the test",
+                HashMap::new(),
+            )
+            .is_err());
+
+        let response = PrometheusFactory::handle_request(Request::new(Body::empty()), factory)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            TextEncoder::new().format_type()
+        );
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("# TYPE exporter_test_requests_total counter"));
+        assert!(body.contains("exporter_test_requests_total 3"));
+    }
 }
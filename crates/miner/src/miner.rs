@@ -369,21 +369,6 @@ impl Miner {
         proposals.iter().map(|b| b.hash.clone()).collect()
     }
 
-    /// Hashes and returns a hexadecimal string representation of the hash of
-    /// the consolidated `Txn`s
-    pub(crate) fn get_txn_hash(&self, txns: &ConsolidatedTxns) -> String {
-        let mut txn_hasher = Sha256::new();
-
-        let txns_hash = {
-            if let Ok(serialized_txns) = serde_json::to_string(txns) {
-                txn_hasher.update(serialized_txns.as_bytes());
-            }
-            txn_hasher.finalize()
-        };
-
-        format!("{txns_hash:x}")
-    }
-
     /// Hashes and returns a hexadecimal string representation of the hash of
     /// the consolidated `Claim`s
     pub(crate) fn get_claim_hash(&self, claims: &ConsolidatedClaims) -> String {
@@ -447,27 +432,6 @@ impl Miner {
         }
     }
 
-    /// Hashes the current `ConvergenceBlock` being mined using
-    /// the fields from the `BlockHeader`
-    pub(crate) fn hash_block(&self, header: &BlockHeader) -> String {
-        let block_hash = hash_data!(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature
-        );
-
-        format!("{block_hash:x}")
-    }
-
     /// Gets the current election `seed` from the
     /// `last_block.header.next_block_seed` field
     pub(crate) fn get_seed(&self) -> u64 {
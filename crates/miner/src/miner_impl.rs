@@ -4,8 +4,8 @@ use std::{
 };
 
 use block::{
-    header::BlockHeader, Block, Conflict, ConflictList, ConvergenceBlock, InnerBlock,
-    ProposalBlock, RefHash,
+    hash_block_header, hash_consolidated_txns, header::BlockHeader, txn_merkle_root, Block,
+    Conflict, ConflictList, ConvergenceBlock, InnerBlock, ProposalBlock, RefHash,
 };
 use bulldag::vertex::{Direction, Vertex};
 use ethereum_types::U256;
@@ -42,10 +42,11 @@ impl BlockBuilder for Miner {
             let txns = self.consolidate_txns(&resolved);
             let claims = self.consolidate_claims(&resolved);
             let ref_hashes = self.get_ref_hashes(&resolved);
-            let txns_hash = self.get_txn_hash(&txns);
+            let txns_hash = hash_consolidated_txns(&txns);
             let claims_hash = self.get_claim_hash(&claims);
-            let header = self.build_header(ref_hashes, txns_hash, claims_hash)?;
-            let hash = self.hash_block(&header);
+            let mut header = self.build_header(ref_hashes, txns_hash, claims_hash)?;
+            header.txn_root = Some(txn_merkle_root(&txns));
+            let hash = hash_block_header(&header);
 
             Some(ConvergenceBlock {
                 header,
@@ -1,8 +1,9 @@
 use std::net::SocketAddr;
 
+use block::ChainHeadReadHandle;
 use events::{Event, EventPublisher, EventSubscriber};
 use mempool::MempoolReadHandleFactory;
-use storage::vrrbdb::VrrbDbReadHandle;
+use storage::vrrbdb::{StateQueryHandle, VrrbDbReadHandle};
 use telemetry::info;
 use tokio::task::JoinHandle;
 use vrrb_config::NodeConfig;
@@ -14,7 +15,9 @@ pub async fn setup_rpc_api_server(
     config: &NodeConfig,
     events_tx: EventPublisher,
     vrrbdb_read_handle: VrrbDbReadHandle,
+    state_query_handle: StateQueryHandle,
     mempool_read_handle_factory: MempoolReadHandleFactory,
+    chain_head_read_handle: ChainHeadReadHandle,
     mut jsonrpc_events_rx: EventSubscriber,
 ) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
     let jsonrpc_server_config = JsonRpcServerConfig {
@@ -22,7 +25,10 @@ pub async fn setup_rpc_api_server(
         node_type: config.node_type,
         events_tx,
         vrrbdb_read_handle,
+        state_query_handle,
         mempool_read_handle_factory,
+        chain_head_read_handle,
+        admin_address: config.admin_jsonrpc_server_address,
     };
 
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) =
@@ -30,12 +36,26 @@ pub async fn setup_rpc_api_server(
             .await
             .map_err(|err| NodeError::Other(format!("unable to start JSON-RPC server: {err}")))?;
 
+    let admin_server = JsonRpcServer::run_admin(&jsonrpc_server_config)
+        .await
+        .map_err(|err| NodeError::Other(format!("unable to start admin JSON-RPC server: {err}")))?;
+
+    let admin_server_handle = admin_server.map(|(handle, addr)| {
+        info!("Admin JSON-RPC server started at {addr}");
+        handle
+    });
+
     let jsonrpc_server_handle = tokio::spawn(async move {
         if let Ok(evt) = jsonrpc_events_rx.recv().await {
             if let Event::Stop = evt.into() {
                 jsonrpc_server_handle.stop().map_err(|err| {
                     NodeError::Other(format!("JSON-RPC event has stopped: {err}"))
                 })?;
+                if let Some(admin_server_handle) = admin_server_handle {
+                    admin_server_handle.stop().map_err(|err| {
+                        NodeError::Other(format!("admin JSON-RPC server has stopped: {err}"))
+                    })?;
+                }
                 return Ok(());
             }
         }
@@ -0,0 +1,229 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use block::{BlockHash, Certificate};
+use ritelinked::LinkedHashSet;
+use sha2::{Digest, Sha256};
+use signer::engine::SignerEngine;
+
+use crate::{NodeError, Result};
+
+/// Maximum number of verified certificates remembered at once. The least
+/// recently seen certificates are evicted first.
+pub const MAX_VERIFIED_CERTIFICATES: usize = 1_024;
+
+/// Keeps track of the certificates whose signatures were already verified
+/// against the current quorum membership, so that certificates re-received
+/// through gossip or during reorgs don't have to be verified again.
+///
+/// Entries are keyed by `(block_hash, signatures_hash)` and are dropped
+/// whenever the quorum membership of the `SignerEngine` changes. The
+/// signatures themselves are checked by
+/// `SignerEngine::verify_harvester_quorum_signatures`, the same check
+/// `DagModule::verify_certificate` relies on.
+#[derive(Debug, Clone, Default)]
+pub struct VerifiedCertificateCache {
+    verified: LinkedHashSet<(BlockHash, String)>,
+    membership_fingerprint: u64,
+    verifications: usize,
+}
+
+impl VerifiedCertificateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifies that `certificate` carries enough valid harvester signatures
+    /// over its block hash, skipping the check if the same certificate was
+    /// already verified under the current quorum membership.
+    ///
+    /// Returns `true` if the certificate was verified by this call and
+    /// `false` if it was served from the cache.
+    pub fn verify(&mut self, certificate: &Certificate, sig_engine: &SignerEngine) -> Result<bool> {
+        let mut hasher = DefaultHasher::new();
+        sig_engine.quorum_members().hash(&mut hasher);
+        let membership_fingerprint = hasher.finish();
+
+        if membership_fingerprint != self.membership_fingerprint {
+            self.verified.clear();
+            self.membership_fingerprint = membership_fingerprint;
+        }
+
+        let key = (certificate.block_hash.clone(), signatures_hash(certificate));
+        if self.verified.remove(&key) {
+            self.verified.insert(key);
+            return Ok(false);
+        }
+
+        self.verifications += 1;
+
+        sig_engine
+            .verify_harvester_quorum_signatures(&certificate.signatures, &certificate.block_hash)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        self.verified.insert(key);
+        while self.verified.len() > MAX_VERIFIED_CERTIFICATES {
+            self.verified.pop_front();
+        }
+
+        Ok(true)
+    }
+
+    /// Number of certificates that had their signatures verified, as opposed
+    /// to being served from the cache.
+    pub fn verifications(&self) -> usize {
+        self.verifications
+    }
+
+    pub fn len(&self) -> usize {
+        self.verified.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verified.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.verified.clear();
+    }
+}
+
+/// Hashes a certificate's signatures independently of the order in which
+/// they were collected.
+fn signatures_hash(certificate: &Certificate) -> String {
+    let mut signatures = certificate.signatures.clone();
+    signatures.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut hasher = Sha256::new();
+    for (node_id, signature) in signatures.iter() {
+        hasher.update(node_id.as_bytes());
+        hasher.update(signature.serialize_compact());
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::{NodeId, QuorumKind};
+
+    use super::*;
+    use crate::test_utils::{create_sig_engine, create_sig_engines};
+
+    fn harvesters(n: usize) -> Vec<(NodeId, SignerEngine)> {
+        create_sig_engines("harvester", n)
+    }
+
+    fn engine_for(harvesters: &[(NodeId, SignerEngine)]) -> SignerEngine {
+        let mut engine = create_sig_engine();
+        let members = harvesters
+            .iter()
+            .map(|(id, engine)| (id.clone(), engine.public_key()))
+            .collect();
+        engine.set_quorum_members(vec![(QuorumKind::Harvester, members)]);
+        engine
+    }
+
+    fn certificate(harvesters: &mut [(NodeId, SignerEngine)], block_hash: &str) -> Certificate {
+        let signatures = harvesters
+            .iter_mut()
+            .map(|(id, engine)| (id.clone(), engine.sign(block_hash).unwrap()))
+            .collect();
+
+        Certificate {
+            signatures,
+            inauguration: None,
+            root_hash: String::from("root"),
+            block_hash: block_hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn verified_certificates_are_not_verified_again() {
+        let mut harvesters = harvesters(3);
+        let sig_engine = engine_for(&harvesters);
+        let certificate = certificate(&mut harvesters, "block-1");
+        let mut cache = VerifiedCertificateCache::new();
+
+        assert!(cache.verify(&certificate, &sig_engine).unwrap());
+        assert!(!cache.verify(&certificate, &sig_engine).unwrap());
+
+        assert_eq!(cache.verifications(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn invalid_certificates_are_not_cached() {
+        let mut harvesters = harvesters(3);
+        let sig_engine = engine_for(&harvesters);
+        let mut certificate = certificate(&mut harvesters, "block-1");
+        certificate.block_hash = String::from("block-2");
+        let mut cache = VerifiedCertificateCache::new();
+
+        assert!(cache.verify(&certificate, &sig_engine).is_err());
+        assert!(cache.verify(&certificate, &sig_engine).is_err());
+
+        assert_eq!(cache.verifications(), 2);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn membership_change_forces_reverification() {
+        let mut harvesters = harvesters(3);
+        let mut sig_engine = engine_for(&harvesters);
+        let certificate = certificate(&mut harvesters, "block-1");
+        let mut cache = VerifiedCertificateCache::new();
+
+        cache.verify(&certificate, &sig_engine).unwrap();
+
+        let mut members: Vec<(NodeId, _)> = harvesters
+            .iter()
+            .map(|(id, engine)| (id.clone(), engine.public_key()))
+            .collect();
+        members.pop();
+        sig_engine.set_quorum_members(vec![(QuorumKind::Harvester, members)]);
+
+        // the certificate is now signed by a node outside of the quorum
+        assert!(cache.verify(&certificate, &sig_engine).is_err());
+        assert_eq!(cache.verifications(), 2);
+    }
+
+    #[test]
+    fn certificates_with_duplicate_or_unknown_signers_are_rejected() {
+        let mut harvesters = harvesters(3);
+        let sig_engine = engine_for(&harvesters);
+        let mut cache = VerifiedCertificateCache::new();
+
+        let mut duplicated = certificate(&mut harvesters, "block-1");
+        let signature = duplicated.signatures[0].clone();
+        duplicated.signatures = vec![signature.clone(), signature];
+        assert!(cache.verify(&duplicated, &sig_engine).is_err());
+
+        let mut outsiders = self::harvesters(1);
+        let (_, outsider) = &mut outsiders[0];
+        let mut foreign = certificate(&mut harvesters, "block-1");
+        foreign.signatures[2] = (String::from("outsider"), outsider.sign("block-1").unwrap());
+        assert!(cache.verify(&foreign, &sig_engine).is_err());
+
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn cache_is_bounded() {
+        let mut harvesters = harvesters(1);
+        let sig_engine = engine_for(&harvesters);
+        let mut cache = VerifiedCertificateCache::new();
+
+        for idx in 0..MAX_VERIFIED_CERTIFICATES + 1 {
+            let certificate = certificate(&mut harvesters, &format!("block-{idx}"));
+            cache.verify(&certificate, &sig_engine).unwrap();
+        }
+        assert_eq!(cache.len(), MAX_VERIFIED_CERTIFICATES);
+
+        // the oldest certificate was evicted and has to be verified again
+        let oldest = certificate(&mut harvesters, "block-0");
+        assert!(cache.verify(&oldest, &sig_engine).unwrap());
+    }
+}
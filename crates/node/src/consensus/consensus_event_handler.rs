@@ -1,4 +1,4 @@
-use super::ConsensusModule;
+use super::{election_seed, ConsensusModule};
 use crate::{NodeError, Result};
 use block::{header::BlockHeader, Block, ConvergenceBlock, InnerBlock, ProposalBlock};
 use bulldag::graph::BullDag;
@@ -202,13 +202,35 @@ impl ConsensusModule {
         Ok(quorum)
     }
 
+    /// Elects the miner of the block following `block`. The election is
+    /// seeded with the beacon value of the block's certificate, so every node
+    /// holding the block derives the same seed. Blocks without a valid
+    /// certificate can't seed an election.
     pub fn handle_miner_election_started(
         &mut self,
-        header: BlockHeader,
+        block: &Block,
         claims: HashMap<String, Claim>,
     ) -> Result<BTreeMap<U256, Claim>> {
+        let certificate = block.certificate().ok_or_else(|| {
+            NodeError::Other(format!(
+                "cannot seed miner election: block {} is not certified",
+                block.hash()
+            ))
+        })?;
+
+        if certificate.block_hash != block.hash() {
+            return Err(NodeError::Other(format!(
+                "cannot seed miner election: certificate of block {} is for block {}",
+                block.hash(),
+                certificate.block_hash
+            )));
+        }
+
+        self.verify_certificate(certificate)?;
+
+        let block_seed = election_seed(certificate);
         let election_results: BTreeMap<U256, Claim> =
-            self.quorum_driver.elect_miner(claims, header.block_seed);
+            self.quorum_driver.elect_miner(claims, block_seed);
         self.miner_election_results = Some(election_results.clone());
         Ok(election_results)
     }
@@ -362,16 +384,33 @@ impl ConsensusModule {
         block: ConvergenceBlock,
         // TODO: use last_confirmed_block_header for seed & round
         // for conflict resolution
-        _last_confirmed_block_header: BlockHeader,
+        last_confirmed_block_header: BlockHeader,
         resolver: R,
         dag: Arc<RwLock<BullDag<Block, String>>>,
     ) -> Result<(bool, bool)> {
         self.is_harvester()?;
+        self.precheck_convergence_block_reward(&block, &last_confirmed_block_header)?;
         self.precheck_convergence_block_miner_is_winner(block.clone())?;
         let proposal_block_hashes = block.header.ref_hashes.clone();
         self.precheck_convergence_block_transactions(block, proposal_block_hashes, resolver, dag)
     }
 
+    /// Checks that the block claims the reward announced by the last
+    /// confirmed block as its `next_block_reward`.
+    pub fn precheck_convergence_block_reward(
+        &self,
+        block: &ConvergenceBlock,
+        last_confirmed_block_header: &BlockHeader,
+    ) -> Result<()> {
+        last_confirmed_block_header
+            .next_block_reward
+            .validate_block_reward(&block.header.block_reward)
+            .map_err(|source| NodeError::InvalidBlockReward {
+                block_hash: block.hash.clone(),
+                source,
+            })
+    }
+
     pub fn precheck_convergence_block_miner_is_winner(
         &self,
         block: ConvergenceBlock,
@@ -1,4 +1,4 @@
-use super::{QuorumModule, QuorumModuleConfig};
+use super::{QuorumModule, QuorumModuleConfig, VerifiedCertificateCache};
 use crate::{NodeError, Result};
 use block::{
     header::BlockHeader, Block, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock,
@@ -9,13 +9,13 @@ use events::{SyncPeerData, Vote};
 use mempool::MempoolReadHandleFactory;
 use miner::conflict_resolver::Resolver;
 use primitives::{
-    NodeId, NodeType, NodeTypeBytes, PKShareBytes, PayloadBytes, PublicKey, QuorumId, QuorumKind,
-    QuorumPublicKey, RawSignature, Signature,
+    FarmerQuorumThreshold, NodeId, NodeType, NodeTypeBytes, PKShareBytes, PayloadBytes, PublicKey,
+    QuorumId, QuorumKind, QuorumPublicKey, RawSignature, Signature,
 };
 use prometheus::IntGauge;
 use secp256k1::Message;
 use serde::{Deserialize, Serialize};
-use signer::engine::{QuorumData, SignerEngine, VALIDATION_THRESHOLD};
+use signer::engine::{quorum_threshold, QuorumData, SignerEngine};
 use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use storage::vrrbdb::{ClaimStoreReadHandleFactory, StateStoreReadHandleFactory};
@@ -84,6 +84,7 @@ pub struct ConsensusModule {
     pub(crate) node_config: NodeConfig,
     pub(crate) quorum_membership: Option<QuorumId>,
     pub(crate) quorum_kind: Option<QuorumKind>,
+    pub(crate) verified_certificates: VerifiedCertificateCache,
     pub votes_pool: HashMap<QuorumId, HashMap<TransactionDigest, HashSet<Vote>>>,
     pub(crate) validator_core_manager: ValidatorCoreManager,
     pub miner_election_results: Option<BTreeMap<U256, Claim>>,
@@ -123,6 +124,7 @@ impl ConsensusModule {
             node_config: cfg.node_config.clone(),
             quorum_membership: None,
             quorum_kind: None,
+            verified_certificates: VerifiedCertificateCache::new(),
             validator_core_manager,
             votes_pool: Default::default(),
             miner_election_results: None,
@@ -130,6 +132,41 @@ impl ConsensusModule {
         })
     }
 
+    /// Verifies the harvester signatures of a certificate, skipping
+    /// certificates already verified under the current quorum membership.
+    pub fn verify_certificate(&mut self, certificate: &Certificate) -> Result<()> {
+        self.verified_certificates
+            .verify(certificate, &self.sig_engine)?;
+
+        Ok(())
+    }
+
+    /// Swaps the active quorums for the ones inaugurated by `certificate`,
+    /// once the current harvester quorum certified the block carrying them.
+    /// Every node accepting the certified block swaps, so the network agrees
+    /// on who certifies the blocks that follow. A malformed inauguration is
+    /// rejected and the current quorums are kept.
+    ///
+    /// Returns `true` if the quorums were swapped.
+    pub fn inaugurate_quorums(&mut self, certificate: &Certificate) -> Result<bool> {
+        let inauguration = match certificate.inauguration.clone() {
+            Some(inauguration) => inauguration,
+            None => return Ok(false),
+        };
+
+        self.verify_certificate(certificate)?;
+        self.sig_engine
+            .inaugurate_quorum_members(inauguration)
+            .map_err(|err| {
+                NodeError::Other(format!(
+                    "failed to inaugurate quorums certified by block {}: {err}",
+                    certificate.block_hash
+                ))
+            })?;
+
+        Ok(true)
+    }
+
     pub fn sig_engine(&self) -> SignerEngine {
         self.sig_engine.clone()
     }
@@ -239,6 +276,26 @@ impl ConsensusModule {
             .validate_transaction_kind(digest, mempool_reader, state_reader)
     }
 
+    pub fn validate_transaction_batch(
+        &mut self,
+        digests: &[TransactionDigest],
+        mempool_reader: MempoolReadHandleFactory,
+        state_reader: StateStoreReadHandleFactory,
+    ) -> Result<
+        Vec<(
+            TransactionDigest,
+            validator::txn_validator::Result<TransactionKind>,
+        )>,
+    > {
+        self.is_farmer()?;
+
+        Ok(self.validator_core_manager.validate_transaction_batch(
+            digests,
+            mempool_reader,
+            state_reader,
+        ))
+    }
+
     pub fn cast_vote_on_transaction_kind(
         &mut self,
         transaction: TransactionKind,
@@ -273,14 +330,20 @@ impl ConsensusModule {
         let receiver_farmer_id = self.node_config.id.clone();
         let farmer_node_id = self.node_config.id.clone();
 
-        let txn_bytes = bincode::serialize(&transaction.clone()).ok()?;
-        let signature = self.sig_engine.sign(txn_bytes).ok()?;
+        let (quorum_id, _) = self.get_node_quorum_id(&farmer_node_id)?;
+        let quorum_threshold = self.farmer_quorum_threshold(&quorum_id).ok()?;
+
+        let payload =
+            Vote::build_signing_payload(&transaction, &quorum_id, quorum_threshold).ok()?;
+        let signature = self.sig_engine.sign(payload).ok()?;
 
         Some(Vote {
             farmer_id: receiver_farmer_id.clone(),
             farmer_node_id: farmer_node_id.clone(),
             signature,
             txn: transaction.clone(),
+            quorum_id,
+            quorum_threshold,
             execution_result: None,
             is_txn_valid: valid,
         })
@@ -370,7 +433,7 @@ impl ConsensusModule {
                 .map(|vote| (vote.farmer_node_id.clone(), vote.signature))
                 .collect();
 
-            let data = bincode::serialize(&vote.txn.clone()).map_err(|err| {
+            let data = vote.signing_payload().map_err(|err| {
                 NodeError::Other(format!(
                     "unable to serialize txn: {} to verify vote signature. err: {}",
                     &vote.txn.id(),
@@ -401,7 +464,19 @@ impl ConsensusModule {
         set: &HashSet<Vote>,
         quorum_members: QuorumData,
     ) -> bool {
-        set.len() >= (quorum_members.members.len() as f64 * VALIDATION_THRESHOLD) as usize
+        set.len() >= Self::vote_threshold(&quorum_members)
+    }
+
+    /// Returns the number of votes the Farmer quorum `quorum_id` needs to
+    /// certify a transaction.
+    fn farmer_quorum_threshold(&self, quorum_id: &QuorumId) -> Result<FarmerQuorumThreshold> {
+        let quorum_members = self.get_quorum_members(quorum_id)?;
+
+        Ok(Self::vote_threshold(&quorum_members))
+    }
+
+    fn vote_threshold(quorum_members: &QuorumData) -> FarmerQuorumThreshold {
+        quorum_threshold(quorum_members.members.len())
     }
 
     fn get_quorum_pending_votes_for_transaction(
@@ -458,7 +533,17 @@ impl ConsensusModule {
                 ))
             })?;
 
-        let data = bincode::serialize(&vote.txn.clone()).map_err(|err| {
+        if vote.quorum_id != *quorum_id
+            || vote.quorum_threshold != self.farmer_quorum_threshold(quorum_id)?
+        {
+            return Err(NodeError::Other(format!(
+                "vote by {} on transaction {} was cast for another quorum context",
+                voter.clone(),
+                vote.txn.id()
+            )));
+        }
+
+        let data = vote.signing_payload().map_err(|err| {
             NodeError::Other(format!(
                 "unable to serialize txn: {} to verify vote signature. err: {}",
                 &vote.txn.id(),
@@ -532,6 +617,59 @@ impl ConsensusModule {
         self.quorum_membership = Some(QuorumId::new(quorum_kind, members));
     }
 
+    /// Settles the local node's membership once its quorum has formed, using
+    /// the quorums installed in the `SignerEngine`. Nodes that were only told
+    /// about their own assignment get their quorum installed from their
+    /// membership config first. Returns the kind of quorum the node belongs
+    /// to, or `None` if it wasn't assigned to any.
+    pub fn finalize_quorum_membership(&mut self) -> Option<QuorumKind> {
+        if self.get_node_quorum_id(&self.node_config.id).is_none() {
+            self.install_local_quorum_members();
+        }
+
+        let (quorum_id, quorum_kind) = self.get_node_quorum_id(&self.node_config.id)?;
+
+        self.quorum_membership = Some(quorum_id);
+        self.quorum_kind = Some(quorum_kind.clone());
+
+        Some(quorum_kind)
+    }
+
+    /// Adds the local node's quorum, as described by its membership config,
+    /// to the quorums installed in the `SignerEngine`.
+    fn install_local_quorum_members(&mut self) {
+        let Some(membership_config) = self.quorum_driver.membership_config.clone() else {
+            return;
+        };
+
+        let mut local_members = membership_config
+            .quorum_members
+            .values()
+            .map(|member| (member.node_id.clone(), member.validator_public_key))
+            .collect::<Vec<(NodeId, PublicKey)>>();
+        local_members.push((
+            self.node_config.id.clone(),
+            self.keypair.validator_public_key_owned(),
+        ));
+        local_members.sort();
+        local_members.dedup();
+
+        let mut quorums = self
+            .sig_engine
+            .quorum_members()
+            .0
+            .into_values()
+            .map(|quorum_data| {
+                let mut members = quorum_data.members.into_iter().collect::<Vec<_>>();
+                members.sort();
+                (quorum_data.quorum_kind, members)
+            })
+            .collect::<Vec<_>>();
+        quorums.push((membership_config.quorum_kind, local_members));
+
+        self.sig_engine.set_quorum_members(quorums);
+    }
+
     pub fn is_bootstrap_node(&self) -> bool {
         self.node_config.node_type == NodeType::Bootstrap
     }
@@ -1,7 +1,11 @@
+mod certificate_cache;
 mod consensus_event_handler;
 mod consensus_module;
 
 mod quorum_module;
+mod random_beacon;
 
+pub use certificate_cache::*;
 pub use consensus_module::*;
 pub use quorum_module::*;
+pub use random_beacon::*;
@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use block::header::BlockHeader;
 use ethereum_types::U256;
@@ -8,6 +8,9 @@ use quorum::{
     election::Election,
     quorum::{Quorum, QuorumError},
 };
+use rand::seq::SliceRandom;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use signer::engine::quorum_threshold;
 use theater::{ActorId, ActorState};
 use vrrb_config::{BootstrapConfig, NodeConfig, QuorumMembershipConfig};
 use vrrb_core::claim::{Claim, Eligibility};
@@ -24,6 +27,35 @@ pub struct QuorumModule {
     pub(crate) bootstrap_quorum_available_nodes: HashMap<NodeId, (PeerData, bool)>,
 }
 
+/// A node's role in consensus: the kind of quorum it belongs to, the members
+/// of that quorum and the number of them whose votes or signatures are needed
+/// to reach a decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumInfo {
+    pub quorum_kind: QuorumKind,
+    pub members: Vec<NodeId>,
+    pub threshold: usize,
+}
+
+impl QuorumInfo {
+    /// Derives the quorum info of `node_id` from its membership config. The
+    /// node is counted as a member of its own quorum.
+    pub fn new(node_id: &NodeId, membership: &QuorumMembershipConfig) -> Self {
+        let members: BTreeSet<NodeId> = membership
+            .quorum_members
+            .keys()
+            .cloned()
+            .chain(std::iter::once(node_id.clone()))
+            .collect();
+
+        Self {
+            quorum_kind: membership.quorum_kind(),
+            threshold: quorum_threshold(members.len()),
+            members: members.into_iter().collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QuorumModuleConfig {
     pub membership_config: Option<QuorumMembershipConfig>,
@@ -100,25 +132,37 @@ impl QuorumModule {
         //
         // TODO: override autoassignment if config is provided
         //
-        let unassigned_miner_peers = peer_list
+        let mut unassigned_miner_peers = peer_list
             .iter()
             .filter(|(_, (peer_data, _))| peer_data.node_type == NodeType::Miner)
             .map(|(_, (peer_data, _))| peer_data)
             .cloned()
             .collect::<Vec<PeerData>>();
 
-        let unassigned_peers = peer_list
+        let mut unassigned_peers = peer_list
             .iter()
             .filter(|(_, (peer_data, _))| peer_data.node_type == NodeType::Validator)
             .map(|(_, (peer_data, _))| peer_data)
             .cloned()
             .collect::<Vec<PeerData>>();
 
+        // NOTE: every bootstrap node must come up with the same memberships, so
+        // the assignment only depends on the sorted peers and the configured
+        // seed, never on the iteration order of the peer list
+        unassigned_miner_peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        unassigned_peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+
+        let seed = self
+            .bootstrap_config
+            .as_ref()
+            .map(|config| config.quorum_assignment_seed)
+            .unwrap_or_default();
+        unassigned_peers.shuffle(&mut ChaCha20Rng::seed_from_u64(seed));
+
         // NOTE: select 30% of nodes to be harvester nodes and make the rest farmers
         let unassigned_peers_count = unassigned_peers.len();
         let harvester_count = (unassigned_peers_count as f64 * 0.3).ceil() as usize;
 
-        // TODO: pick nodes at random
         let harvester_peers = unassigned_peers
             .clone()
             .into_iter()
@@ -225,3 +269,93 @@ impl QuorumModule {
         first
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use primitives::KademliaPeerId;
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn quorum_module(quorum_assignment_seed: u64) -> QuorumModule {
+        let node_config = NodeConfig {
+            node_type: NodeType::Bootstrap,
+            bootstrap_config: Some(BootstrapConfig {
+                quorum_assignment_seed,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        QuorumModule::new(QuorumModuleConfig {
+            membership_config: None,
+            node_config,
+        })
+    }
+
+    fn peer_list(validators: usize, miners: usize) -> Vec<(NodeId, (PeerData, bool))> {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let node_types = std::iter::repeat(NodeType::Validator)
+            .take(validators)
+            .chain(std::iter::repeat(NodeType::Miner).take(miners));
+
+        node_types
+            .enumerate()
+            .map(|(idx, node_type)| {
+                let peer = PeerData {
+                    node_id: format!("node-{idx}"),
+                    node_type,
+                    kademlia_peer_id: KademliaPeerId::rand(),
+                    udp_gossip_addr: addr,
+                    raptorq_gossip_addr: addr,
+                    kademlia_liveness_addr: addr,
+                    validator_public_key: Keypair::random().validator_public_key_owned(),
+                };
+
+                (peer.node_id.clone(), (peer, true))
+            })
+            .collect()
+    }
+
+    async fn quorum_kinds(
+        module: &QuorumModule,
+        peers: impl IntoIterator<Item = (NodeId, (PeerData, bool))>,
+    ) -> BTreeMap<NodeId, QuorumKind> {
+        module
+            .assign_peer_list_to_quorums(peers.into_iter().collect())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(node_id, assignment)| (node_id, assignment.quorum_kind))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn quorum_assignment_only_depends_on_peers_and_seed() {
+        let peers = peer_list(8, 1);
+        let mut reversed = peers.clone();
+        reversed.reverse();
+
+        let first = quorum_kinds(&quorum_module(7), peers.clone()).await;
+        let second = quorum_kinds(&quorum_module(7), reversed).await;
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first
+                .values()
+                .filter(|kind| **kind == QuorumKind::Harvester)
+                .count(),
+            3
+        );
+        assert_eq!(
+            first
+                .values()
+                .filter(|kind| **kind == QuorumKind::Farmer)
+                .count(),
+            5
+        );
+        assert_eq!(first.get("node-8"), Some(&QuorumKind::Miner));
+    }
+}
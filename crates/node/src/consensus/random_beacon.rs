@@ -0,0 +1,121 @@
+//! Per-block randomness agreed upon by the network.
+//!
+//! Beacon values are derived from the harvester quorum's certificate over a
+//! block hash, so no single node can predict the value before the quorum
+//! signs the block. They only depend on the certificate the block carries,
+//! so every node holding the block derives the same values, regardless of
+//! what it verified or kept in memory before. The value is not grinding
+//! resistant though: secp256k1 signatures aren't unique, and whoever
+//! assembles a certificate picks which of the collected signatures it
+//! carries, so they can choose among several candidate values. Removing that
+//! bias takes a unique threshold signature or a VRF output, which the signer
+//! engine doesn't provide yet.
+//!
+//! Miner elections following a block are seeded with `election_seed` of its
+//! certificate, while other randomness for the block following it should be
+//! drawn from `beacon_rng`.
+
+use block::Certificate;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use sha2::{Digest, Sha256};
+
+/// Hashes the certified block hash together with the quorum signatures over
+/// it, independently of the order in which the signatures were collected.
+pub fn derive_beacon_value(certificate: &Certificate) -> [u8; 32] {
+    let mut signatures = certificate.signatures.clone();
+    signatures.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut hasher = Sha256::new();
+    hasher.update(certificate.block_hash.as_bytes());
+    for (node_id, signature) in signatures.iter() {
+        hasher.update(node_id.as_bytes());
+        hasher.update(signature.serialize_compact());
+    }
+
+    hasher.finalize().into()
+}
+
+/// Returns the seed miner elections following the block certified by
+/// `certificate` are run with, taken from the first 8 bytes of its beacon
+/// value.
+pub fn election_seed(certificate: &Certificate) -> u64 {
+    let value = derive_beacon_value(certificate);
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&value[..8]);
+
+    u64::from_le_bytes(seed)
+}
+
+/// Returns a random number generator seeded with the beacon value of
+/// `certificate`. Every node holding the same certificate draws the same
+/// sequence of values from it.
+pub fn beacon_rng(certificate: &Certificate) -> ChaCha20Rng {
+    ChaCha20Rng::from_seed(derive_beacon_value(certificate))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use reward::reward::{MAX_BASELINE_REWARD, MIN_BASELINE_REWARD};
+    use signer::engine::SignerEngine;
+
+    use super::*;
+    use crate::test_utils::create_sig_engine;
+
+    fn certificate(block_hash: &str, signers: &mut [SignerEngine]) -> Certificate {
+        let signatures = signers
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, engine)| (format!("harvester-{idx}"), engine.sign(block_hash).unwrap()))
+            .collect();
+
+        Certificate {
+            signatures,
+            inauguration: None,
+            root_hash: String::from("root"),
+            block_hash: block_hash.to_string(),
+        }
+    }
+
+    fn signers(n: usize) -> Vec<SignerEngine> {
+        (0..n).map(|_| create_sig_engine()).collect()
+    }
+
+    #[test]
+    fn beacon_is_deterministic_and_differs_per_block() {
+        let mut signers = signers(3);
+        let first = certificate("block-1", &mut signers);
+        let second = certificate("block-2", &mut signers);
+
+        let mut shuffled = first.clone();
+        shuffled.signatures.reverse();
+
+        let value = derive_beacon_value(&first);
+
+        assert_eq!(derive_beacon_value(&shuffled), value);
+        assert_eq!(certificate("block-1", &mut signers), first);
+        assert_ne!(derive_beacon_value(&second), value);
+
+        assert_eq!(
+            election_seed(&first),
+            u64::from_le_bytes(value[..8].try_into().unwrap())
+        );
+        assert_eq!(election_seed(&shuffled), election_seed(&first));
+        assert_ne!(election_seed(&second), election_seed(&first));
+    }
+
+    #[test]
+    fn beacon_seeds_reward_sampling_reproducibly() {
+        let mut signers = signers(3);
+        let certificate = certificate("block-1", &mut signers);
+
+        let sample = |certificate: &Certificate| -> Vec<u128> {
+            let mut rng = beacon_rng(certificate);
+            (0..8)
+                .map(|_| rng.gen_range(MIN_BASELINE_REWARD..=MAX_BASELINE_REWARD))
+                .collect()
+        };
+
+        assert_eq!(sample(&certificate), sample(&certificate.clone()));
+    }
+}
@@ -21,6 +21,7 @@ pub use result::*;
 pub use runtime::*;
 pub use runtime_component::*;
 pub use runtime_module::*;
+pub use storage::vrrbdb::{StateQueryHandle, DEFAULT_STATE_QUERY_CONCURRENCY};
 
 pub use crate::node::*;
 
@@ -4,7 +4,7 @@ use primitives::RUNTIME_TOPIC_STR;
 use telemetry::info;
 use theater::{ActorId, ActorLabel, ActorState, Handler, TheaterError};
 
-use super::NetworkModule;
+use super::{NetworkEvent, NetworkModule};
 
 #[async_trait]
 impl Handler<EventMessage> for NetworkModule {
@@ -97,6 +97,34 @@ impl Handler<EventMessage> for NetworkModule {
                 self.broadcast_block(block).await?;
             }
 
+            Event::BroadcastTxn(txn, signature) => {
+                self.broadcast_txn(txn, signature).await?;
+            }
+
+            Event::StateSyncRequested {
+                from,
+                at_height,
+                signature,
+            } if from == self.node_id => {
+                info!("Requesting state sync from peers");
+                self.broadcast_state_sync_request(at_height, signature)
+                    .await?;
+            }
+
+            Event::StateSyncResponse {
+                to,
+                height,
+                state_root_hash,
+            } if to != self.node_id => {
+                info!("Answering state sync request from {to}");
+                self.broadcast_state_sync_response(NetworkEvent::StateSyncResponse {
+                    to,
+                    height,
+                    state_root_hash,
+                })
+                .await?;
+            }
+
             _ => {}
         }
 
@@ -8,11 +8,11 @@ use dyswarm::{
 use events::{AssignedQuorumMembership, EventPublisher, Vote};
 use hbbft::sync_key_gen::{Ack, Part};
 use kademlia_dht::{Node as KademliaNode, NodeData};
-use primitives::{ConvergencePartialSig, KademliaPeerId, NodeId, NodeType, PublicKey};
+use primitives::{ConvergencePartialSig, KademliaPeerId, NodeId, NodeType, PublicKey, Signature};
 use telemetry::info;
 use theater::{ActorId, ActorState};
 use vrrb_config::{NodeConfig, QuorumMembershipConfig};
-use vrrb_core::claim::Claim;
+use vrrb_core::{claim::Claim, transactions::TransactionKind};
 
 use super::NetworkEvent;
 use crate::{network::DyswarmHandler, result::Result, NodeError, DEFAULT_ERASURE_COUNT};
@@ -428,6 +428,71 @@ impl NetworkModule {
         Ok(())
     }
 
+    /// Gossips a transaction to peers, tagged with this node's id and the
+    /// runtime's signature over it, so that receivers can authenticate
+    /// where it came from.
+    pub async fn broadcast_txn(
+        &mut self,
+        txn: TransactionKind,
+        signature: Signature,
+    ) -> Result<()> {
+        let message = dyswarm::types::Message::new(NetworkEvent::ForwardedTxn {
+            node_id: self.node_id.clone(),
+            txn: Box::new(txn),
+            signature,
+        });
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Asks peers for their state on behalf of this node, which has state up
+    /// to `at_height`, if any, and signed the request with `signature`.
+    pub async fn broadcast_state_sync_request(
+        &mut self,
+        at_height: Option<u128>,
+        signature: Signature,
+    ) -> Result<()> {
+        let message = dyswarm::types::Message::new(NetworkEvent::StateSyncRequested {
+            node_id: self.node_id.clone(),
+            at_height,
+            signature,
+        });
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends a state sync response. Peers other than `to` drop it on
+    /// receipt.
+    pub async fn broadcast_state_sync_response(&mut self, response: NetworkEvent) -> Result<()> {
+        let message = dyswarm::types::Message::new(response);
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn broadcast_block(&mut self, block: Block) -> Result<()> {
         let closest_nodes = self
             .node_ref()
@@ -3,10 +3,11 @@ use std::net::SocketAddr;
 use block::{Block, Certificate, ConvergenceBlock};
 use events::{AssignedQuorumMembership, Vote};
 use hbbft::sync_key_gen::{Ack, Part};
-use mempool::TxnRecord;
-use primitives::{ConvergencePartialSig, KademliaPeerId, NodeId, NodeType, PeerId, PublicKey};
+use primitives::{
+    ConvergencePartialSig, KademliaPeerId, NodeId, NodeType, PeerId, PublicKey, Signature,
+};
 use serde::{Deserialize, Serialize};
-use vrrb_core::claim::Claim;
+use vrrb_core::{claim::Claim, transactions::TransactionKind};
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 /// Represents data trasmitted over the VRRB network by nodes that participate
@@ -51,7 +52,27 @@ pub enum NetworkEvent {
 
     BlockCreated(Block),
 
-    ForwardedTxn(Box<TxnRecord>),
+    /// Transaction gossiped by the node `node_id`
+    ForwardedTxn {
+        node_id: NodeId,
+        txn: Box<TransactionKind>,
+        signature: Signature,
+    },
+
+    /// State sync requested by the lagging node `node_id`, which has state
+    /// up to `at_height`, if any, and signed the request
+    StateSyncRequested {
+        node_id: NodeId,
+        at_height: Option<u128>,
+        signature: Signature,
+    },
+
+    /// State sync response addressed to the node `to`
+    StateSyncResponse {
+        to: NodeId,
+        height: u128,
+        state_root_hash: String,
+    },
 
     PartCommitmentCreated(NodeId, Part),
     PartCommitmentAcknowledged {
@@ -123,6 +123,48 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.send_event_to_runtime(evt).await?;
             }
 
+            NetworkEvent::ForwardedTxn {
+                node_id,
+                txn,
+                signature,
+            } => {
+                let evt = Event::NewTxnForwarded(node_id, *txn, signature);
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
+            NetworkEvent::StateSyncRequested {
+                node_id,
+                at_height,
+                signature,
+            } => {
+                if node_id != self.node_id {
+                    let evt = Event::StateSyncRequested {
+                        from: node_id,
+                        at_height,
+                        signature,
+                    };
+
+                    self.send_event_to_runtime(evt).await?;
+                }
+            }
+
+            NetworkEvent::StateSyncResponse {
+                to,
+                height,
+                state_root_hash,
+            } => {
+                if to == self.node_id {
+                    let evt = Event::StateSyncResponse {
+                        to,
+                        height,
+                        state_root_hash,
+                    };
+
+                    self.send_event_to_runtime(evt).await?;
+                }
+            }
+
             _ => {}
         }
 
@@ -7,7 +7,7 @@ use primitives::{
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use storage::vrrbdb::VrrbDbReadHandle;
+use storage::vrrbdb::{StateQueryHandle, VrrbDbReadHandle};
 use telemetry::{info, tracing};
 use tokio::{
     signal,
@@ -34,6 +34,7 @@ pub struct Node {
     runtime_control_handle: JoinHandle<Result<()>>,
     db_read_handle: VrrbDbReadHandle,
     mempool_read_handle: MempoolReadHandleFactory,
+    state_query_handle: StateQueryHandle,
 }
 
 pub type UnboundedControlEventReceiver = UnboundedReceiver<Event>;
@@ -84,15 +85,20 @@ impl Node {
             .unwrap(),
         );
 
-        let (runtime_component_manager, updated_node_config, db_read_handle, mempool_read_handle) =
-            setup_runtime_components(
-                &config,
-                &router,
-                events_tx.clone(),
-                factory.clone(),
-                labels.clone(),
-            )
-            .await?;
+        let (
+            runtime_component_manager,
+            updated_node_config,
+            db_read_handle,
+            mempool_read_handle,
+            state_query_handle,
+        ) = setup_runtime_components(
+            &config,
+            &router,
+            events_tx.clone(),
+            factory.clone(),
+            labels.clone(),
+        )
+        .await?;
 
         // TODO: report error from handle
         let router_handle = tokio::spawn(async move { router.start(&mut events_rx).await });
@@ -112,6 +118,7 @@ impl Node {
             runtime_control_handle,
             db_read_handle,
             mempool_read_handle,
+            state_query_handle,
         })
     }
 
@@ -123,6 +130,27 @@ impl Node {
                     .to_string(),
             ));
         }
+
+        node_config
+            .threshold_config
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
+
+        node_config
+            .proposal_fairness
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
+
+        node_config
+            .mempool
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
+
+        node_config
+            .mempool_pressure
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
+
         Ok(())
     }
 
@@ -252,4 +280,9 @@ impl Node {
     pub fn mempool_read_handle(&self) -> MempoolReadHandleFactory {
         self.mempool_read_handle.clone()
     }
+
+    /// Handle used to query state without contending with block application.
+    pub fn state_query_handle(&self) -> StateQueryHandle {
+        self.state_query_handle.clone()
+    }
 }
@@ -4,10 +4,12 @@ use std::net::AddrParseError;
 use dyswarm::types::DyswarmError;
 use events::EventMessage;
 use miner::result::MinerError;
+use primitives::{Address, NodeId};
 use theater::TheaterError;
 use thiserror::Error;
 use tokio::sync::mpsc::error::TryRecvError;
 use vrrb_core::claim::ClaimError;
+use vrrb_core::transactions::TransactionDigest;
 
 #[derive(Debug, Error)]
 pub enum NodeError {
@@ -38,12 +40,17 @@ pub enum NodeError {
     #[error("{0}")]
     MpscSend(#[from] tokio::sync::mpsc::error::SendError<EventMessage>),
 
+    #[error("event channel was still full after {0:?}")]
+    EventSendTimeout(std::time::Duration),
+
     #[error("{0}")]
     TaskJoin(#[from] tokio::task::JoinError),
 
     #[error("{0}")]
     JsonRpc(#[from] vrrb_rpc::ApiError),
 
+    /// Errors of the event router. They are kept as `messr::Error` rather
+    /// than flattened into a message, so callers can match on its variants.
     #[error("{0}")]
     Messr(#[from] messr::Error),
 
@@ -56,6 +63,36 @@ pub enum NodeError {
     #[error("Error while creating claim for node: {0}")]
     Claim(#[from] ClaimError),
 
+    #[error("certificate for block {0} timed out before reaching the signature threshold")]
+    CertificateTimeout(String),
+
+    #[error("convergence block {block_hash} carries an invalid reward: {source}")]
+    InvalidBlockReward {
+        block_hash: String,
+        source: reward::reward::RewardError,
+    },
+
+    #[error("node {node_id} already signed block {block_hash} with another signature")]
+    ConflictingSignature { node_id: NodeId, block_hash: String },
+
+    #[error("account {0} already exists")]
+    AccountAlreadyExists(Address),
+
+    #[error("credits or debits of account {0} overflow")]
+    BalanceOverflow(Address),
+
+    #[error("account {0} has insufficient balance")]
+    InsufficientBalance(Address),
+
+    #[error("stake {0} was already applied")]
+    StakeAlreadyApplied(TransactionDigest),
+
+    #[error("stake {0} is not signed by its sender")]
+    UnauthorizedStake(TransactionDigest),
+
+    #[error("stake {0} is not certified by a farmer quorum")]
+    UncertifiedStake(TransactionDigest),
+
     // #[error("DKG error: {0}")]
     // Dkg(#[from] DkgError),
     #[error("{0}")]
@@ -1,5 +1,6 @@
 use crate::{node_runtime::NodeRuntime, NodeError, RuntimeComponent, RuntimeComponentHandle};
-use events::{EventPublisher, EventSubscriber};
+use block::ChainHeadReadHandle;
+use events::{Event, EventPublisher, EventSubscriber};
 use mempool::MempoolReadHandleFactory;
 use metric_exporter::metric_factory::PrometheusFactory;
 use std::collections::HashMap;
@@ -8,6 +9,7 @@ use std::time::Duration;
 use storage::vrrbdb::VrrbDbReadHandle;
 use theater::{Actor, ActorImpl};
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use vrrb_config::NodeConfig;
 
 #[derive(Debug)]
@@ -22,6 +24,7 @@ pub struct NodeRuntimeComponentResolvedData {
     pub node_config: NodeConfig,
     pub state_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub chain_head_read_handle: ChainHeadReadHandle,
 }
 
 #[async_trait::async_trait]
@@ -34,7 +37,8 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
         labels: HashMap<String, String>,
     ) -> crate::Result<RuntimeComponentHandle<NodeRuntimeComponentResolvedData>> {
         let mut events_rx = args.events_rx;
-        let node_runtime = NodeRuntime::new(
+        let sweep_events_tx = args.events_tx.clone();
+        let mut node_runtime = NodeRuntime::new(
             &args.config,
             args.events_tx,
             factory.clone(),
@@ -43,8 +47,11 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
         .await
         .map_err(|err| NodeError::Other(err.to_string()))?;
 
+        node_runtime.replay_event_wal().await?;
+
         let state_read_handle = node_runtime.state_read_handle();
         let mempool_read_handle_factory = node_runtime.mempool_read_handle_factory();
+        let chain_head_read_handle = node_runtime.chain_head_read_handle();
         let unvoted_pending_transactions = factory
             .build_int_gauge(
                 "unvoted_pending_transactions",
@@ -61,13 +68,40 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
                 }
             }
         });
+        // Signature collections are checked for timeouts as often as they
+        // can time out, for as long as the node runtime is running.
+        let sweep_cancel_token = CancellationToken::new();
+        let sweep_handle = tokio::spawn({
+            let sweep_interval = args.config.signature_collection_deadline;
+            let cancel_token = sweep_cancel_token.clone();
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = cancel_token.cancelled() => break,
+                        _ = sleep(sweep_interval) => {}
+                    }
+
+                    let event = Event::SignatureCollectionSweepRequested.into();
+                    if sweep_events_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
         let mut node_runtime_actor = ActorImpl::new(node_runtime);
 
         let node_runtime_handle = tokio::spawn(async move {
-            node_runtime_actor
+            let result = node_runtime_actor
                 .start(&mut events_rx)
                 .await
-                .map_err(|err| NodeError::Other(err.to_string()))
+                .map_err(|err| NodeError::Other(err.to_string()));
+
+            sweep_cancel_token.cancel();
+            if let Err(err) = sweep_handle.await {
+                telemetry::error!("signature collection sweep task failed: {err}");
+            }
+
+            result
         });
 
         telemetry::info!("NodeRuntime module is operational");
@@ -76,6 +110,7 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
             node_config: args.config,
             state_read_handle,
             mempool_read_handle_factory,
+            chain_head_read_handle,
         };
 
         let component_handle = RuntimeComponentHandle::new(
@@ -5,13 +5,17 @@ use events::{AccountBytes, AssignedQuorumMembership, Event, PeerData, Vote};
 use miner::conflict_resolver::Resolver;
 use primitives::{Address, NodeId, PublicKey, QuorumId, QuorumKind, Signature};
 use signer::engine::{QuorumData, QuorumMembers as InaugaratedMembers};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use storage::vrrbdb::ApplyBlockResult;
-use vrrb_core::transactions::TransactionDigest;
+use vrrb_core::{
+    account::{Account, UpdateArgs},
+    transactions::TransactionDigest,
+};
 
 use crate::{
     node_runtime::NodeRuntime,
     result::{NodeError, Result},
+    state_manager::MAX_SIGNATURE_COLLECTION_RESTARTS,
 };
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
@@ -27,12 +31,28 @@ impl NodeRuntime {
 
     fn handle_genesis_block_received(&mut self, block: GenesisBlock) -> Result<ApplyBlockResult> {
         self.verify_genesis_block_origin(block.clone())?;
+        self.verify_genesis_rewards(&block)?;
 
-        let apply_result = self.state_driver.apply_block(Block::Genesis { block })?;
+        let apply_result = self
+            .state_driver
+            .append_genesis(&block, &self.consensus_driver.sig_engine)
+            .map_err(|err| {
+                NodeError::Other(format!("Could not append genesis block to DAG: {err:?}"))
+            })?
+            .ok_or(NodeError::Other(format!(
+                "genesis block {} is pending certification",
+                block.hash
+            )))?;
+
+        // NOTE: the next convergence block is mined on top of the genesis block
+        self.mining_driver.last_block = Some(Arc::new(block));
 
         Ok(apply_result)
     }
 
+    /// Appends a proposal block to the DAG, where it waits for the
+    /// convergence block of its round. Confirmed state is left untouched, so
+    /// the returned result carries the current root hashes.
     // TODO:
     // check if from valid harvester
     // whoever sent the proposal block must be a valid harvester
@@ -62,16 +82,41 @@ impl NodeRuntime {
         self.consensus_driver.is_harvester()?;
         let apply_result = self
             .state_driver
-            .append_convergence(&block)
+            .append_convergence(&block, &self.consensus_driver.sig_engine)
             .map_err(|err| {
                 NodeError::Other(format!(
                     "Could not append convergence block to DAG: {err:?}"
                 ))
             })?;
 
+        if let Some(certificate) = &block.certificate {
+            self.inaugurate_certified_quorums(certificate);
+        }
+
+        self.mining_driver.last_block = Some(Arc::new(block));
+
         Ok(apply_result)
     }
 
+    /// Swaps in the quorums inaugurated by the certificate of a block this
+    /// node accepted. The pending quorum is dropped once it took over, so it
+    /// isn't inaugurated a second time.
+    fn inaugurate_certified_quorums(&mut self, certificate: &Certificate) {
+        match self.consensus_driver.inaugurate_quorums(certificate) {
+            Ok(true) => {
+                if self.pending_quorum == certificate.inauguration {
+                    self.pending_quorum = None;
+                }
+                telemetry::info!(
+                    "quorums inaugurated by block {} took over",
+                    certificate.block_hash
+                );
+            }
+            Ok(false) => {}
+            Err(err) => telemetry::error!("keeping the current quorums: {err}"),
+        }
+    }
+
     pub async fn handle_harvester_signature_received(
         &mut self,
         block_hash: String,
@@ -82,28 +127,67 @@ impl NodeRuntime {
             .sig_engine
             .verify(&node_id, &sig, &block_hash)
             .map_err(|err| NodeError::Other(err.to_string()))?;
-        let set = self
-            .state_driver
-            .dag
-            .add_signer_to_block(
-                block_hash.clone(),
-                sig,
-                node_id,
-                &self.consensus_driver.sig_engine,
-            )
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        let set = self.state_driver.dag.add_signer_to_block(
+            block_hash.clone(),
+            sig,
+            node_id,
+            &self.consensus_driver.sig_engine,
+        )?;
         let sig_set = set.into_iter().collect();
         let cert = self
-            .form_convergence_certificate(block_hash, sig_set)
+            .form_convergence_certificate(block_hash.clone(), sig_set)
             .map_err(|err| NodeError::Other(err.to_string()))?;
+        self.state_driver
+            .dag
+            .prune_signature_collection(&block_hash);
 
-        self.events_tx
-            .send(Event::BlockCertificateCreated(cert.clone()).into())
+        self.publish(Event::BlockCertificateCreated(cert.clone()).into())
             .await
             .map_err(|err| NodeError::Other(err.to_string()))?;
         Ok(cert)
     }
 
+    /// Escalates the signature collections that timed out. Signatures for
+    /// a stalled block are re-requested by gossiping the block again and
+    /// restarting its collection window, up to
+    /// `MAX_SIGNATURE_COLLECTION_RESTARTS` times, after which the block is
+    /// given up on and its collected signatures are dropped.
+    ///
+    /// Returns the events re-requesting signatures.
+    pub fn handle_signature_collection_sweep(&mut self) -> Vec<Event> {
+        let timed_out = self
+            .state_driver
+            .dag
+            .timed_out_signature_collections(&self.consensus_driver.sig_engine);
+
+        let mut events = vec![];
+        for block_hash in timed_out {
+            let dag = &mut self.state_driver.dag;
+            let pending_block = dag.get_pending_convergence_block_mut(&block_hash).cloned();
+
+            match pending_block {
+                Some(block)
+                    if dag.signature_collection_restarts(&block_hash)
+                        < MAX_SIGNATURE_COLLECTION_RESTARTS =>
+                {
+                    telemetry::warn!(
+                        "Certificate for block {block_hash} timed out, re-requesting signatures"
+                    );
+                    dag.restart_signature_collection(&block_hash);
+                    events.push(Event::BlockCreated(Block::Convergence { block }));
+                }
+                _ => {
+                    telemetry::error!(
+                        "Certificate for block {block_hash} timed out, giving up on it as stalled"
+                    );
+                    dag.prune_signature_collection(&block_hash);
+                }
+            }
+        }
+
+        events
+    }
+
     pub fn form_convergence_certificate(
         &mut self,
         block_hash: String,
@@ -123,26 +207,25 @@ impl NodeRuntime {
         {
             let root_hash = block.header.txn_hash.clone();
             let block_hash = block.hash.clone();
-            let inauguration = self.pending_quorum.as_ref().cloned();
+            // A malformed pending quorum is left out of the certificate, so
+            // other nodes are never handed an inauguration that can't take
+            // over certifying blocks. It stays pending until the next election
+            // replaces it.
+            let inauguration = self.pending_quorum.clone().filter(|quorum_members| {
+                if let Err(err) = quorum_members.validate() {
+                    telemetry::error!("not inaugurating pending quorum: {err}");
+                    return false;
+                }
+                true
+            });
+            // The inauguration only takes over once a node accepts the
+            // certified block, see `inaugurate_certified_quorums`.
             let cert = Certificate {
                 signatures: sigs,
-                //TODO: handle inauguration blocks
-                inauguration: inauguration.clone(),
+                inauguration,
                 root_hash,
                 block_hash: block_hash.clone(),
             };
-            //            if let Some(quorum_members) = inauguration {
-            //                self.consensus_driver.sig_engine.set_quorum_members(
-            //                    quorum_members
-            //                        .0
-            //                        .into_iter()
-            //                        .map(|(_, data)| {
-            //                            (data.quorum_kind, data.members.clone().into_iter().collect())
-            //                        })
-            //                        .collect(),
-            //                );
-            //                self.pending_quorum = None;
-            //            }
             Ok(cert)
         } else {
             Err(NodeError::Other(format!(
@@ -162,10 +245,19 @@ impl NodeRuntime {
             .await
     }
 
+    /// Finalizes the local node's quorum membership once its quorum has
+    /// formed. Nodes that weren't assigned to a quorum, like bootstrap and
+    /// miner nodes, have nothing to finalize.
     pub async fn handle_quorum_formed(&mut self) -> Result<()> {
-        // This is probably where we want to put the logic for
-        // taking a pending quorum and applying it
-        todo!();
+        if let Some(quorum_kind) = self.consensus_driver.finalize_quorum_membership() {
+            telemetry::info!(
+                "{} is ready to serve in a {} quorum",
+                self.config.id,
+                quorum_kind
+            );
+        }
+
+        Ok(())
     }
 
     // recieve cert from network
@@ -180,6 +272,7 @@ impl NodeRuntime {
             .ok_or(NodeError::Other(
                 "certificate not appended to convergence block".to_string(),
             ))?;
+        self.inaugurate_certified_quorums(&certificate);
 
         Ok(block.clone())
     }
@@ -197,34 +290,23 @@ impl NodeRuntime {
                 "certificate not appended to genesis block".to_string(),
             ))?;
 
-        Ok(block.clone())
+        // NOTE: the next convergence block is mined on top of the genesis block
+        self.mining_driver.last_block = Some(Arc::new(block.clone()));
+
+        Ok(block)
     }
 
     pub fn verify_certificate(&mut self, certificate: &Certificate) -> Result<()> {
-        let cert_sigs = certificate.signatures.clone();
-        if cert_sigs.len()
-            < self
-                .consensus_driver
-                .sig_engine
-                .quorum_members()
-                .get_harvester_threshold()
-        {
-            return Err(NodeError::Other("threshold not reached".to_string()));
-        }
-        self.consensus_driver
-            .sig_engine
-            .verify_batch(&certificate.signatures, &certificate.block_hash)
-            .map_err(|err| NodeError::Other(err.to_string()))?;
-
-        Ok(())
+        self.consensus_driver.verify_certificate(certificate)
     }
 
     pub fn append_certificate_to_convergence_block(
         &mut self,
         certificate: &Certificate,
     ) -> Result<Option<ConvergenceBlock>> {
+        let sig_engine = self.consensus_driver.sig_engine();
         self.state_driver
-            .append_certificate_to_convergence_block(certificate)
+            .append_certificate_to_convergence_block(certificate, &sig_engine)
             .map_err(|err| NodeError::Other(format!("{:?}", err)))
     }
 
@@ -233,8 +315,9 @@ impl NodeRuntime {
         block_hash: &str,
         certificate: &Certificate,
     ) -> Result<Option<GenesisBlock>> {
+        let sig_engine = self.consensus_driver.sig_engine();
         self.state_driver
-            .append_certificate_to_genesis_block(block_hash, certificate)
+            .append_certificate_to_genesis_block(block_hash, certificate, &sig_engine)
             .map_err(|err| NodeError::Other(format!("{:?}", err)))
     }
 
@@ -318,13 +401,12 @@ impl NodeRuntime {
             self.state_driver.dag.dag(),
         ) {
             Ok((true, true)) => {
-                self.events_tx
-                    .send(Event::SignConvergenceBlock(block.clone()).into())
+                self.publish(Event::SignConvergenceBlock(block.clone()).into())
                     .await
                     .map_err(|err| NodeError::Other(err.to_string()))?;
                 Ok(())
             }
-            Err(err) => Err(NodeError::Other(err.to_string())),
+            Err(err) => Err(err),
             _ => Err(NodeError::Other(
                 "convergence block is not valid".to_string(),
             )),
@@ -337,6 +419,7 @@ impl NodeRuntime {
         block: ConvergenceBlock,
     ) -> Result<Signature> {
         self.consensus_driver.is_harvester()?;
+        self.ensure_not_in_maintenance_mode("sign convergence block")?;
         self.consensus_driver
             .sig_engine
             .sign(&block.hash)
@@ -351,6 +434,8 @@ impl NodeRuntime {
 
     pub async fn handle_sign_genesis_block(&mut self, block: &GenesisBlock) -> Result<Signature> {
         self.consensus_driver.is_harvester()?;
+        self.ensure_not_in_maintenance_mode("sign genesis block")?;
+        self.verify_genesis_rewards(block)?;
         self.consensus_driver
             .sig_engine
             .sign(&block.hash)
@@ -436,6 +521,65 @@ impl NodeRuntime {
             NodeError::Other(format!("unable to deserialize account bytes: {err}"))
         })?;
 
+        if self.state_driver.get_account(&address).is_ok() {
+            return Err(NodeError::AccountAlreadyExists(address));
+        }
+
         self.state_driver.insert_account(address, account)
     }
+
+    /// Applies a requested account update to an existing account, returning
+    /// the updated account. Updates to addresses that are not in state are
+    /// rejected rather than creating a new account.
+    pub fn handle_account_update_requested(
+        &mut self,
+        address: Address,
+        account_bytes: AccountBytes,
+    ) -> Result<Account> {
+        let requested: Account = bincode::deserialize(&account_bytes).map_err(|err| {
+            NodeError::Other(format!("unable to deserialize account bytes: {err}"))
+        })?;
+
+        let existing = self.state_driver.get_account(&address).map_err(|err| {
+            NodeError::Other(format!("cannot update unknown account {address}: {err}"))
+        })?;
+
+        let update_args = account_update_args(address.clone(), &existing, &requested)?;
+        self.state_driver.update_account(update_args)?;
+
+        self.state_driver.get_account(&address)
+    }
+}
+
+/// Builds the `UpdateArgs` that move `existing` to `requested`. Balances
+/// only ever change by applying transactions, so requests carrying credits
+/// or debits that differ from the account's are rejected, and the nonce
+/// can't go back.
+fn account_update_args(
+    address: Address,
+    existing: &Account,
+    requested: &Account,
+) -> Result<UpdateArgs> {
+    if requested.credits() != existing.credits() || requested.debits() != existing.debits() {
+        return Err(NodeError::Other(format!(
+            "balance of account {address} cannot be updated directly"
+        )));
+    }
+
+    if requested.nonce() < existing.nonce() {
+        return Err(NodeError::Other(format!(
+            "nonce of account {address} cannot decrease"
+        )));
+    }
+
+    Ok(UpdateArgs {
+        nonce: (requested.nonce() > existing.nonce()).then_some(requested.nonce()),
+        credits: None,
+        debits: None,
+        storage: (requested.storage() != existing.storage()).then(|| requested.storage().clone()),
+        package_address: (requested.package_address() != existing.package_address())
+            .then(|| requested.package_address().clone()),
+        digests: Some(requested.digests().clone()),
+        address,
+    })
 }
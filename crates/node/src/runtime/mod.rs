@@ -3,28 +3,47 @@ pub mod handler_helpers;
 pub mod node_runtime;
 pub mod node_runtime_handler;
 mod setup;
+mod txn_selection;
 
 pub use handler_helpers::*;
 pub use setup::*;
+pub use txn_selection::*;
 
 #[cfg(test)]
 mod tests {
 
     use crate::node_runtime::NodeRuntime;
     use crate::test_utils::{
-        create_node_runtime_network, create_quorum_assigned_node_runtime_network,
-        create_sender_receiver_addresses, create_txn_from_accounts,
-        create_txn_from_accounts_invalid_signature, create_txn_from_accounts_invalid_timestamp,
-        setup_network, setup_whitelisted_nodes,
+        create_node_runtime, create_node_runtime_network,
+        create_quorum_assigned_node_runtime_network, create_sender_receiver_addresses,
+        create_sig_engine, create_txn_from_accounts, create_txn_from_accounts_invalid_signature,
+        create_txn_from_accounts_invalid_timestamp, setup_network, setup_whitelisted_nodes,
     };
     use crate::NodeError;
-    use block::{Block, GenesisReceiver};
+    use block::{Block, Certificate, ConvergenceBlock, GenesisBlock, GenesisReceiver};
     use events::{AssignedQuorumMembership, PeerData, Vote, DEFAULT_BUFFER};
     use primitives::{generate_account_keypair, Address, NodeId, NodeType, QuorumKind};
+    use reward::reward::GENESIS_REWARD;
     use storage::storage_utils::remove_vrrb_data_dir;
-    use vrrb_core::account::{Account, AccountField};
+    use vrrb_config::{GenesisAllocation, NodeConfig};
+    use vrrb_core::account::{Account, AccountField, UpdateArgs};
     use vrrb_core::transactions::Transaction;
 
+    /// Credits `address` in the node's state directly, since account update
+    /// requests can't change balances.
+    fn credit_account(node: &mut NodeRuntime, address: &Address, credits: u128) {
+        node.update_account(UpdateArgs {
+            address: address.clone(),
+            nonce: None,
+            credits: Some(credits),
+            debits: None,
+            storage: None,
+            package_address: None,
+            digests: None,
+        })
+        .unwrap();
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn bootstrap_node_runtime_cannot_be_assigned_to_quorum() {
@@ -76,6 +95,86 @@ mod tests {
         assert!(node.quorum_membership().is_some());
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn farmer_node_runtime_reports_its_quorum_info() {
+        let (node_0, farmers, _harvesters, _miners) = setup_network(8).await;
+        assert!(node_0.quorum_info().is_none());
+
+        for (node_id, node) in farmers.iter() {
+            let info = node.quorum_info().unwrap();
+
+            assert_eq!(info.quorum_kind, QuorumKind::Farmer);
+            assert_eq!(info.members.len(), farmers.len());
+            assert!(info.members.contains(node_id));
+            assert!(farmers.keys().all(|farmer| info.members.contains(farmer)));
+            assert_eq!(info.threshold, 3);
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_finalizes_quorum_membership_when_quorum_is_formed() {
+        use events::Event;
+        use theater::{ActorState, Handler};
+
+        let (_node_0, mut farmers, _harvesters, _miners) = setup_network(8).await;
+
+        for (node_id, node) in farmers.iter_mut() {
+            let status = node.handle(Event::QuorumFormed.into()).await.unwrap();
+
+            assert_eq!(status, ActorState::Running);
+            assert_eq!(
+                node.consensus_driver.quorum_kind(),
+                Some(QuorumKind::Farmer)
+            );
+            assert!(node.consensus_driver.quorum_membership.is_some());
+            assert!(node.quorum_info().unwrap().members.contains(node_id));
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn quorum_formed_installs_a_single_assignment_in_the_signer_engine() {
+        use events::Event;
+        use theater::{ActorState, Handler};
+
+        remove_vrrb_data_dir();
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let mut node = nodes.pop_front().unwrap();
+
+        let assigned_membership = AssignedQuorumMembership {
+            quorum_kind: QuorumKind::Farmer,
+            node_id: node.id.clone(),
+            pub_key: node.config.keypair.validator_public_key_owned(),
+            kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
+            peers: vec![],
+        };
+        node.handle_quorum_membership_assigment_created(assigned_membership)
+            .unwrap();
+        assert!(node
+            .consensus_driver
+            .sig_engine
+            .quorum_members()
+            .0
+            .is_empty());
+
+        let status = node.handle(Event::QuorumFormed.into()).await.unwrap();
+
+        assert_eq!(status, ActorState::Running);
+        assert!(node.consensus_driver.quorum_membership.is_some());
+        assert_eq!(
+            node.consensus_driver
+                .sig_engine
+                .quorum_members()
+                .get_farmer_public_key(&node.id),
+            Some(node.config.keypair.validator_public_key_owned())
+        );
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn bootstrap_node_runtime_can_assign_quorum_memberships_to_available_nodes() {
@@ -89,21 +188,108 @@ mod tests {
     #[serial_test::serial]
     async fn bootstrap_node_runtime_can_produce_genesis_reward() {
         let (node_0, farmers, harvesters, miners) = setup_network(8).await;
-        assert!(node_0.distribute_genesis_reward(vec![]).is_err());
+        assert!(node_0.distribute_genesis_reward().is_err());
 
         for (_, node) in farmers.iter() {
-            assert!(node.distribute_genesis_reward(vec![]).is_err());
+            assert!(node.distribute_genesis_reward().is_err());
         }
 
         for (_, node) in harvesters.iter() {
-            assert!(node.distribute_genesis_reward(vec![]).is_err());
+            assert!(node.distribute_genesis_reward().is_err());
         }
 
         for (_, node) in miners.iter() {
-            assert!(node.distribute_genesis_reward(vec![]).is_ok());
+            assert!(node.distribute_genesis_reward().is_ok());
         }
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn genesis_reward_must_add_up_to_total_supply() {
+        let (_node_0, _farmers, _harvesters, miners) = setup_network(8).await;
+        let mut miner = miners.into_values().next().unwrap();
+
+        let allocations = |n: usize| -> Vec<GenesisAllocation> {
+            (0..n)
+                .map(|_| {
+                    let (_, pk) = generate_account_keypair();
+                    GenesisAllocation {
+                        address: Address::new(pk),
+                        amount: 10000,
+                    }
+                })
+                .collect()
+        };
+
+        miner.config_mut().genesis_total_supply = Some(2 * 10000 + GENESIS_REWARD);
+
+        miner.config_mut().genesis_allocations = allocations(2);
+        let genesis_rewards = miner.distribute_genesis_reward().unwrap();
+        assert_eq!(genesis_rewards.total_allocated(), Some(2 * 10000));
+
+        miner.config_mut().genesis_allocations = allocations(3);
+        let over = miner.distribute_genesis_reward().unwrap_err();
+        assert!(over.to_string().contains("but the total is"));
+
+        miner.config_mut().genesis_allocations = allocations(1);
+        let under = miner.distribute_genesis_reward().unwrap_err();
+        assert!(under.to_string().contains("but the total is"));
+    }
+
+    #[test]
+    fn genesis_transactions_are_a_pure_function_of_the_config() {
+        let allocations: Vec<GenesisAllocation> = (0..4)
+            .map(|n| {
+                let (_, pk) = generate_account_keypair();
+                GenesisAllocation {
+                    address: Address::new(pk),
+                    amount: 1000 * (n + 1),
+                }
+            })
+            .collect();
+
+        let mut config = NodeConfig::default();
+        config.genesis_allocations = allocations.clone();
+        config.genesis_total_supply = Some(10000 + GENESIS_REWARD);
+
+        let first = NodeRuntime::genesis_transactions_for(&config).unwrap();
+        let second = NodeRuntime::genesis_transactions_for(&config).unwrap();
+
+        assert_eq!(
+            bincode::serialize(&first).unwrap(),
+            bincode::serialize(&second).unwrap()
+        );
+
+        let mut reordered = config.clone();
+        reordered.genesis_allocations.reverse();
+        let third = NodeRuntime::genesis_transactions_for(&reordered).unwrap();
+
+        assert_eq!(
+            bincode::serialize(&first).unwrap(),
+            bincode::serialize(&third).unwrap()
+        );
+
+        config.genesis_allocations.pop();
+        assert!(NodeRuntime::genesis_transactions_for(&config).is_err());
+    }
+
+    #[test]
+    fn genesis_reward_goes_to_the_miner_without_genesis_receivers() {
+        let mut config = NodeConfig::default();
+        let miner = Address::new(config.keypair.miner_public_key_owned());
+
+        let genesis_rewards = NodeRuntime::genesis_transactions_for(&config).unwrap();
+        assert_eq!(genesis_rewards.0.len(), 1);
+        assert_eq!(
+            genesis_rewards.0.get(&GenesisReceiver::new(miner)),
+            Some(&GENESIS_REWARD)
+        );
+
+        // a total supply can only be reached through configured receivers
+        config.genesis_total_supply = Some(GENESIS_REWARD);
+        assert!(NodeRuntime::genesis_transactions_for(&config).is_err());
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn assigned_quorum_members_exist_in_sig_engine() {
@@ -150,27 +336,16 @@ mod tests {
 
         let miner_id = miner_ids.first().unwrap();
 
-        let miner_node = miners.get(miner_id).unwrap();
-        let receiver = GenesisReceiver(Address::new(
-            farmers
-                .iter()
-                .last()
-                .unwrap()
-                .1
-                .config
-                .keypair
-                .miner_public_key_owned(),
-        ));
-        let genesis_rewards = miner_node
-            .distribute_genesis_reward(vec![receiver])
-            .unwrap();
         let miner_node = miners.get_mut(miner_id).unwrap();
+        miner_node.config_mut().whitelisted_nodes = whitelisted_nodes.clone();
+
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
+        assert!(!genesis_rewards.0.is_empty());
 
         let harvester_ids = harvesters.keys().cloned().collect::<Vec<NodeId>>();
         let harvester_id = harvester_ids.first().unwrap();
         let mut harvester = harvesters.get(harvester_id).unwrap().clone();
-
-        miner_node.config_mut().whitelisted_nodes = whitelisted_nodes;
+        harvester.config_mut().whitelisted_nodes = whitelisted_nodes;
 
         assert!(node_0.mine_genesis_block(genesis_rewards.clone()).is_err());
 
@@ -191,6 +366,35 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn genesis_blocks_must_carry_the_configured_genesis_rewards() {
+        let (_node_0, _farmers, mut harvesters, miners) = setup_network(8).await;
+
+        let miner_node = miners.values().next().unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
+        let mut genesis_block = miner_node.mine_genesis_block(genesis_rewards).unwrap();
+
+        // the rewards are not covered by the miner signature
+        let (_, pk) = generate_account_keypair();
+        genesis_block
+            .genesis_rewards
+            .0
+            .insert(GenesisReceiver::new(Address::new(pk)), 10000);
+        let genesis_block = certified_genesis_block(&mut harvesters, genesis_block);
+
+        let harvester = harvesters.values_mut().next().unwrap();
+        assert!(harvester
+            .handle_sign_genesis_block(&genesis_block)
+            .await
+            .is_err());
+        assert!(harvester
+            .handle_block_received(Block::Genesis {
+                block: genesis_block,
+            })
+            .is_err());
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn farmer_node_runtime_can_validate_transactions() {
@@ -221,22 +425,72 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn farmer_node_runtime_can_validate_transaction_batches() {
+        let (_node_0, mut farmers, _harvesters, _miners) = setup_network(8).await;
+        let farmer = farmers.values_mut().next().unwrap();
+
+        let (_, sender_public_key) = generate_account_keypair();
+        let sender_address = farmer.create_account(sender_public_key).unwrap();
+        let mut sender_account = farmer.get_account_by_address(&sender_address).unwrap();
+        sender_account
+            .update_field(AccountField::Credits(1_000_000))
+            .unwrap();
+        credit_account(farmer, &sender_address, 1_000_000);
+
+        let (_, receiver_public_key) = generate_account_keypair();
+        let receiver_address = farmer.create_account(receiver_public_key).unwrap();
+
+        let (_, unknown_public_key) = generate_account_keypair();
+        let unknown_address = Address::new(unknown_public_key);
+
+        let valid = create_txn_from_accounts(
+            (sender_address.clone(), Some(sender_account.clone())),
+            receiver_address.clone(),
+            vec![],
+        );
+        let invalid_signature = create_txn_from_accounts_invalid_signature(
+            (sender_address, Some(sender_account)),
+            receiver_address.clone(),
+            vec![],
+        );
+        let unknown_sender = create_txn_from_accounts(
+            (unknown_address.clone(), Some(Account::new(unknown_address))),
+            receiver_address,
+            vec![],
+        );
+
+        let txns = vec![
+            valid.clone(),
+            invalid_signature.clone(),
+            unknown_sender.clone(),
+        ];
+        farmer.extend_mempool(&txns).unwrap();
+
+        let digests: Vec<_> = txns.iter().map(|txn| txn.id()).collect();
+        let results = farmer
+            .validate_transaction_batch(
+                &digests,
+                farmer.mempool_read_handle_factory().clone(),
+                farmer.state_store_read_handle_factory().clone(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                (valid, true),
+                (invalid_signature, false),
+                (unknown_sender, false)
+            ]
+        );
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn harvester_node_runtime_can_propose_blocks() {
         let (mut node_0, farmers, mut harvesters, mut miners) = setup_network(8).await;
-        node_0.config.node_type = NodeType::Miner;
-        let receiver = GenesisReceiver(Address::new(
-            farmers
-                .iter()
-                .last()
-                .unwrap()
-                .1
-                .config
-                .keypair
-                .miner_public_key_owned(),
-        ));
-        let genesis_rewards = node_0.distribute_genesis_reward(vec![receiver]).unwrap();
 
         let whitelisted_nodes = setup_whitelisted_nodes(&farmers, &harvesters, &miners);
 
@@ -253,10 +507,14 @@ mod tests {
         let miner_id = miner_ids.first().unwrap();
 
         let mut miner_node = miners.get(miner_id).unwrap().to_owned();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
 
         let claim = miner_node.state_driver.dag.claim();
 
-        let genesis_block = miner_node.mine_genesis_block(genesis_rewards).unwrap();
+        let genesis_block = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
 
         // TODO: impl miner elections
         // TODO: create genesis block, certify it then append it to miner's dag
@@ -313,30 +571,139 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn harvester_in_maintenance_mode_applies_blocks_without_producing() {
+        use events::{Event, EventMessage};
+        use theater::Handler;
+
+        let (_node_0, _farmers, mut harvesters, miners) = setup_network(8).await;
+
+        let miner_node = miners.values().next().unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
+        let claim = miner_node.state_driver.dag.claim();
+        let genesis_block = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
+
+        let harvester = harvesters.values_mut().next().unwrap();
+        let sig_engine = harvester.consensus_driver.sig_engine.clone();
+
+        let event: EventMessage = Event::MaintenanceModeRequested(true).into();
+        harvester.handle(event).await.unwrap();
+        assert!(harvester.is_in_maintenance_mode());
+
+        harvester
+            .handle_block_received(Block::Genesis {
+                block: genesis_block.clone(),
+            })
+            .unwrap();
+
+        assert!(harvester
+            .mine_proposal_block(
+                genesis_block.hash.clone(),
+                Default::default(),
+                1,
+                1,
+                claim.clone(),
+                sig_engine.clone(),
+            )
+            .is_err());
+        assert!(harvester
+            .handle_sign_genesis_block(&genesis_block)
+            .await
+            .is_err());
+
+        let event: EventMessage = Event::MaintenanceModeRequested(false).into();
+        harvester.handle(event).await.unwrap();
+        assert!(!harvester.is_in_maintenance_mode());
+
+        assert!(harvester
+            .mine_proposal_block(
+                genesis_block.hash.clone(),
+                Default::default(),
+                1,
+                1,
+                claim,
+                sig_engine,
+            )
+            .is_ok());
+        assert!(harvester
+            .handle_sign_genesis_block(&genesis_block)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn harvester_can_receive_proposal_block() {
+        let (_node_0, _farmers, mut harvesters, miners) = setup_network(8).await;
+
+        let miner_node = miners.values().next().unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
+        let claim = miner_node.state_driver.dag.claim();
+        let genesis_block = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
+
+        let mut harvesters: Vec<NodeRuntime> = harvesters.into_values().collect();
+        for harvester in harvesters.iter_mut() {
+            harvester
+                .handle_block_received(Block::Genesis {
+                    block: genesis_block.clone(),
+                })
+                .unwrap();
+        }
+
+        let proposer = &mut harvesters[0];
+        let sig_engine = proposer.consensus_driver.sig_engine.clone();
+        let proposal = proposer
+            .mine_proposal_block(
+                genesis_block.hash.clone(),
+                Default::default(),
+                1,
+                1,
+                claim,
+                sig_engine,
+            )
+            .unwrap();
+
+        let harvester = &mut harvesters[1];
+        let state_root_hash = harvester.state_root_hash().unwrap();
+        let apply_result = harvester
+            .handle_block_received(Block::Proposal {
+                block: proposal.clone(),
+            })
+            .unwrap();
+
+        assert_eq!(apply_result.state_root_hash_str(), state_root_hash);
+        assert!(harvester
+            .state_driver
+            .dag
+            .read()
+            .unwrap()
+            .get_vertex(proposal.hash)
+            .is_some());
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn harvester_node_runtime_can_handle_genesis_block_created() {
-        let (mut node_0, farmers, mut harvesters, miners) = setup_network(8).await;
-        node_0.config.node_type = NodeType::Miner;
-        let receiver = GenesisReceiver(Address::new(
-            farmers
-                .iter()
-                .last()
-                .unwrap()
-                .1
-                .config
-                .keypair
-                .miner_public_key_owned(),
-        ));
-        let genesis_rewards = node_0.distribute_genesis_reward(vec![receiver]).unwrap();
+        let (_node_0, _farmers, mut harvesters, miners) = setup_network(8).await;
 
         let miner_ids = miners.clone().into_keys().collect::<Vec<NodeId>>();
 
         let miner_id = miner_ids.first().unwrap();
 
         let miner_node = miners.get(miner_id).unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
 
-        let genesis_block = miner_node.mine_genesis_block(genesis_rewards).unwrap();
+        let genesis_block = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
 
         let mut apply_results = Vec::new();
 
@@ -358,51 +725,339 @@ mod tests {
         }
     }
 
+    fn harvester_certificate(
+        harvesters: &mut std::collections::HashMap<NodeId, NodeRuntime>,
+        block_hash: &str,
+        root_hash: String,
+    ) -> Certificate {
+        Certificate {
+            signatures: harvesters
+                .iter_mut()
+                .map(|(node_id, harvester)| {
+                    let signature = harvester
+                        .consensus_driver
+                        .sig_engine
+                        .sign(block_hash)
+                        .unwrap();
+                    (node_id.clone(), signature)
+                })
+                .collect(),
+            inauguration: None,
+            root_hash,
+            block_hash: block_hash.to_string(),
+        }
+    }
+
+    fn certified_genesis_block(
+        harvesters: &mut std::collections::HashMap<NodeId, NodeRuntime>,
+        mut genesis: GenesisBlock,
+    ) -> GenesisBlock {
+        let certificate =
+            harvester_certificate(harvesters, &genesis.hash, genesis.header.txn_hash.clone());
+        genesis.certificate = Some(certificate);
+        genesis
+    }
+
+    /// Builds the quorums inaugurated when the farmers take over as the
+    /// harvester quorum.
+    fn farmers_as_harvesters(
+        farmers: &std::collections::HashMap<NodeId, NodeRuntime>,
+    ) -> signer::engine::QuorumMembers {
+        let members = farmers
+            .iter()
+            .map(|(node_id, farmer)| {
+                (
+                    node_id.clone(),
+                    farmer.consensus_driver.sig_engine.public_key(),
+                )
+            })
+            .collect();
+
+        let mut inauguration = signer::engine::QuorumMembers::default();
+        inauguration.set_quorum_members(vec![(QuorumKind::Harvester, members)]);
+        inauguration
+    }
+
     #[tokio::test]
     #[serial_test::serial]
-    #[ignore = "https://github.com/versatus/versatus/issues/488"]
-    async fn harvester_node_runtime_can_handle_convergence_block_created() {
-        let (node_0, farmers, mut harvesters, mut miners) = setup_network(8).await;
-        let receiver = GenesisReceiver(Address::new(
-            farmers
-                .iter()
-                .last()
+    async fn certified_inauguration_swaps_the_active_quorums() {
+        let (_node_0, mut farmers, mut harvesters, _miners) = setup_network(8).await;
+
+        let inauguration = farmers_as_harvesters(&farmers);
+
+        let mut inaugural = harvester_certificate(&mut harvesters, "inaugural", String::new());
+        inaugural.inauguration = Some(inauguration.clone());
+
+        let mut malformed = harvester_certificate(&mut harvesters, "malformed", String::new());
+        malformed.inauguration = Some(signer::engine::QuorumMembers::default());
+
+        let mut uncertified = harvester_certificate(&mut farmers, "uncertified", String::new());
+        uncertified.inauguration = Some(inauguration.clone());
+
+        let next = harvester_certificate(&mut harvesters, "next", String::new());
+        let next_by_farmers = harvester_certificate(&mut farmers, "next", String::new());
+
+        let harvester = harvesters.values_mut().next().unwrap();
+        let active_quorums = harvester.consensus_driver.sig_engine.quorum_members();
+
+        assert!(harvester
+            .consensus_driver
+            .inaugurate_quorums(&next)
+            .is_ok_and(|swapped| !swapped));
+        assert!(harvester
+            .consensus_driver
+            .inaugurate_quorums(&malformed)
+            .is_err());
+        assert!(harvester
+            .consensus_driver
+            .inaugurate_quorums(&uncertified)
+            .is_err());
+        assert_eq!(
+            harvester.consensus_driver.sig_engine.quorum_members(),
+            active_quorums
+        );
+
+        assert!(harvester
+            .consensus_driver
+            .inaugurate_quorums(&inaugural)
+            .unwrap());
+        assert_eq!(
+            harvester.consensus_driver.sig_engine.quorum_members(),
+            inauguration
+        );
+
+        // Blocks following the inauguration are certified by the new quorum.
+        assert!(harvester
+            .consensus_driver
+            .verify_certificate(&next)
+            .is_err());
+        assert!(harvester
+            .consensus_driver
+            .verify_certificate(&next_by_farmers)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn chain_head_summary_follows_last_confirmed_block() {
+        let (_node_0, _farmers, mut harvesters, miners) = setup_network(8).await;
+
+        let miner_node = miners.values().next().unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
+        let genesis = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
+
+        let convergence_hash = String::from("convergence");
+        let mut certificate =
+            harvester_certificate(&mut harvesters, &convergence_hash, String::new());
+
+        let harvester = harvesters.values_mut().next().unwrap();
+        let sig_engine = harvester.consensus_driver.sig_engine();
+        let chain_head = harvester.chain_head_read_handle();
+
+        assert!(harvester.chain_head_summary().is_err());
+
+        harvester
+            .state_driver
+            .append_genesis(&genesis, &sig_engine)
+            .unwrap();
+        harvester.publish_chain_head();
+
+        let summary = harvester.chain_head_summary().unwrap();
+        assert_eq!(summary.tip_hash, genesis.hash);
+        assert_eq!(summary.height, genesis.header.block_height);
+        assert_eq!(summary.timestamp, genesis.header.timestamp);
+        assert_eq!(summary.epoch, genesis.header.epoch);
+        assert_eq!(chain_head.summary(), summary);
+
+        let mut header = genesis.header.clone();
+        header.ref_hashes = vec![genesis.hash.clone()];
+        header.block_height += 1;
+        header.timestamp += 1;
+
+        let mut convergence = ConvergenceBlock {
+            header,
+            txns: Default::default(),
+            claims: Default::default(),
+            hash: convergence_hash,
+            certificate: None,
+        };
+        harvester
+            .state_driver
+            .dag
+            .append_convergence(&convergence, &sig_engine)
+            .unwrap();
+
+        certificate.root_hash = harvester.state_root_hash().unwrap();
+        convergence.certificate = Some(certificate);
+        harvester
+            .state_driver
+            .append_convergence(&convergence, &sig_engine)
+            .unwrap();
+        harvester.publish_chain_head();
+
+        let summary = chain_head.summary();
+        assert_eq!(summary.tip_hash, convergence.hash);
+        assert_eq!(summary.height, genesis.header.block_height + 1);
+        assert_eq!(summary.timestamp, convergence.header.timestamp);
+        assert_eq!(
+            summary.state_root_hash,
+            harvester.state_root_hash().unwrap()
+        );
+        assert_eq!(
+            summary.transactions_root_hash,
+            harvester.transactions_root_hash().unwrap()
+        );
+        assert_eq!(
+            summary.claims_root_hash,
+            harvester.claims_root_hash().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn inclusion_proof_verifies_against_harvester_quorum() {
+        use block::{hash_block_header, hash_consolidated_txns, txn_merkle_root, ConsolidatedTxns};
+        use vrrb_core::transactions::TransactionDigest;
+
+        let (_node_0, _farmers, mut harvesters, miners) = setup_network(8).await;
+        let miner_node = miners.values().next().unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
+        let genesis = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
+
+        let included = TransactionDigest::from(vec![1u8; 32]);
+        let unincluded = TransactionDigest::from(vec![2u8; 32]);
+
+        let mut txns = ConsolidatedTxns::new();
+        txns.insert(
+            String::from("proposal-1"),
+            [TransactionDigest::from(vec![3u8; 32]), included.clone()]
+                .into_iter()
+                .collect(),
+        );
+        txns.insert(
+            String::from("proposal-2"),
+            (4u8..9)
+                .map(|byte| TransactionDigest::from(vec![byte; 32]))
+                .collect(),
+        );
+
+        let mut header = genesis.header.clone();
+        header.ref_hashes = vec![genesis.hash.clone()];
+        header.block_height += 1;
+        header.timestamp += 1;
+        header.txn_hash = hash_consolidated_txns(&txns);
+
+        // Headers without a transaction root keep hashing the way they did
+        // before the root was added.
+        let legacy_hash = hash_block_header(&header);
+        header.txn_root = Some(txn_merkle_root(&txns));
+        assert_ne!(hash_block_header(&header), legacy_hash);
+
+        let mut convergence = ConvergenceBlock {
+            hash: hash_block_header(&header),
+            header,
+            txns,
+            claims: Default::default(),
+            certificate: None,
+        };
+        let certificate = harvester_certificate(
+            &mut harvesters,
+            &convergence.hash,
+            convergence.header.txn_hash.clone(),
+        );
+
+        let harvester = harvesters.values_mut().next().unwrap();
+        // the harvester quorum, as known to a light client
+        let light_client = harvester.consensus_driver.sig_engine();
+        harvester
+            .state_driver
+            .append_genesis(&genesis, &light_client)
+            .unwrap();
+        harvester
+            .state_driver
+            .dag
+            .append_convergence(&convergence, &light_client)
+            .unwrap();
+
+        convergence.certificate = Some(certificate);
+        harvester
+            .state_driver
+            .append_convergence(&convergence, &light_client)
+            .unwrap();
+
+        let proof = harvester.inclusion_proof(&included).unwrap();
+        assert_eq!(proof.block_hash, convergence.hash);
+        assert_eq!(proof.ref_hash, "proposal-1");
+        // 7 leaves take 3 levels to reach the root
+        assert_eq!(proof.txn_path.len(), 3);
+        proof.verify(&light_client).unwrap();
+
+        for digest in convergence.txns.values().flatten() {
+            harvester
+                .inclusion_proof(digest)
                 .unwrap()
-                .1
-                .config
-                .keypair
-                .miner_public_key_owned(),
-        ));
-        let genesis_rewards = node_0.distribute_genesis_reward(vec![receiver]).unwrap();
+                .verify(&light_client)
+                .unwrap();
+        }
+
+        assert!(harvester.inclusion_proof(&unincluded).is_err());
+
+        let mut forged = proof.clone();
+        forged.txn_digest = unincluded;
+        assert!(forged.verify(&light_client).is_err());
+
+        let mut forged = proof.clone();
+        forged.ref_hash = String::from("proposal-2");
+        assert!(forged.verify(&light_client).is_err());
+
+        let mut forged = proof.clone();
+        forged.txn_path.reverse();
+        assert!(forged.verify(&light_client).is_err());
+
+        let mut forged = proof.clone();
+        forged.header.txn_root = None;
+        assert!(forged.verify(&light_client).is_err());
+
+        let mut forged = proof;
+        forged.certificate.signatures.pop();
+        forged.certificate.signatures.pop();
+        assert!(forged.verify(&light_client).is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn harvester_node_runtime_can_handle_convergence_block_created() {
+        use block::{hash_consolidated_txns, txn_merkle_root};
+
+        let (_node_0, _farmers, mut harvesters, mut miners) = setup_network(8).await;
 
         let miner_ids = miners.clone().into_keys().collect::<Vec<NodeId>>();
 
         let miner_id = miner_ids.first().unwrap();
 
         let miner_node = miners.get_mut(miner_id).unwrap();
+        let genesis_rewards = miner_node.distribute_genesis_reward().unwrap();
 
-        let genesis_block = miner_node.mine_genesis_block(genesis_rewards).unwrap();
+        let genesis_block = certified_genesis_block(
+            &mut harvesters,
+            miner_node.mine_genesis_block(genesis_rewards).unwrap(),
+        );
 
         // TODO: impl miner elections
-        // TODO: create genesis block, certify it then append it to miner's dag
         // TODO: store DAG on disk, separate from ledger
 
-        let mut apply_results = Vec::new();
-        // let mut genesis_certs = Vec::new();
-
         for (_, harvester) in harvesters.iter_mut() {
-            let apply_result = harvester
+            harvester
                 .handle_block_received(Block::Genesis {
                     block: genesis_block.clone(),
                 })
                 .unwrap();
-
-            // let genesis_cert = harvester
-            //     .certify_genesis_block(genesis_block.clone())
-            //     .unwrap();
-
-            apply_results.push(apply_result);
-            // genesis_certs.push(genesis_cert);
         }
 
         miner_node
@@ -411,7 +1066,21 @@ mod tests {
             })
             .unwrap();
 
-        let convergence_block = miner_node.mine_convergence_block().unwrap();
+        let mut convergence_block = miner_node.mine_convergence_block().unwrap();
+        assert_eq!(
+            convergence_block.header.txn_hash,
+            hash_consolidated_txns(&convergence_block.txns)
+        );
+        assert_eq!(
+            convergence_block.header.txn_root,
+            Some(txn_merkle_root(&convergence_block.txns))
+        );
+
+        convergence_block.certificate = Some(harvester_certificate(
+            &mut harvesters,
+            &convergence_block.hash,
+            genesis_block.header.txn_hash.clone(),
+        ));
 
         let mut apply_results = Vec::new();
 
@@ -432,8 +1101,17 @@ mod tests {
                 assert_eq!(txn_trie_root_hash, res.transactions_root_hash_str());
                 assert_eq!(state_trie_root_hash, res.state_root_hash_str());
             }
+
+            assert_eq!(
+                harvester
+                    .state_driver
+                    .dag
+                    .last_confirmed_block_header()
+                    .unwrap()
+                    .txn_hash,
+                convergence_block.header.txn_hash
+            );
         }
-        panic!();
     }
 
     #[tokio::test]
@@ -1162,4 +1840,538 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn harvesters_reject_convergence_blocks_with_unexpected_rewards() {
+        use reward::reward::RewardError;
+
+        let (events_tx, _rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let nodes = create_quorum_assigned_node_runtime_network(8, 3, events_tx.clone()).await;
+
+        let mut harvester = nodes
+            .into_iter()
+            .find(|nr| nr.consensus_driver.quorum_kind == Some(QuorumKind::Harvester))
+            .unwrap();
+
+        let block = crate::test_utils::dummy_convergence_block();
+        let mut last_confirmed_block_header = block.header.clone();
+        last_confirmed_block_header.next_block_reward.amount += 1;
+
+        let dag = harvester.state_driver.dag.dag();
+        let resolver = harvester.mining_driver.clone();
+        let result = harvester.consensus_driver.precheck_convergence_block(
+            block,
+            last_confirmed_block_header,
+            resolver,
+            dag,
+        );
+
+        assert!(matches!(
+            result,
+            Err(NodeError::InvalidBlockReward {
+                source: RewardError::AmountMismatch { .. },
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_signals_mempool_pressure_once_until_relieved() {
+        use events::{Event, EventMessage};
+        use theater::Handler;
+        use vrrb_config::MempoolPressureConfig;
+
+        remove_vrrb_data_dir();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+        node.state_driver
+            .set_mempool_pressure_config(MempoolPressureConfig {
+                high_watermark: 2,
+                low_watermark: 1,
+            });
+
+        let txns: Vec<_> = (0..4)
+            .map(|_| {
+                let ((sender_account, sender), receiver) = create_sender_receiver_addresses();
+                create_txn_from_accounts((sender, Some(sender_account)), receiver, vec![])
+            })
+            .collect();
+
+        fn pressure_events(
+            events_rx: &mut tokio::sync::mpsc::Receiver<EventMessage>,
+        ) -> Vec<Event> {
+            let mut events = vec![];
+            while let Ok(event) = events_rx.try_recv() {
+                let event: Event = event.into();
+                if matches!(
+                    event,
+                    Event::MempoolPressureReached { .. } | Event::MempoolPressureRelieved { .. }
+                ) {
+                    events.push(event);
+                }
+            }
+            events
+        }
+
+        for txn in txns.iter() {
+            node.handle(Event::NewTxnCreated(txn.clone()).into())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            pressure_events(&mut events_rx),
+            vec![Event::MempoolPressureReached {
+                occupancy: 3,
+                cutoff: txns[2].id(),
+            }]
+        );
+        assert!(node.state_driver.is_mempool_under_pressure());
+
+        for txn in txns.iter() {
+            node.handle(Event::TxnValidated(txn.clone()).into())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            pressure_events(&mut events_rx),
+            vec![Event::MempoolPressureRelieved { occupancy: 0 }]
+        );
+        assert!(!node.state_driver.is_mempool_under_pressure());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_applies_account_update_requested() {
+        use events::{Event, EventMessage};
+        use theater::{ActorState, Handler};
+
+        remove_vrrb_data_dir();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let (_, public_key) = generate_account_keypair();
+        let address = node.create_account(public_key).unwrap();
+
+        let mut requested = node.get_account_by_address(&address).unwrap();
+        requested
+            .update_field(AccountField::Storage(Some(String::from("storage"))))
+            .unwrap();
+        let account_bytes = bincode::serialize(&requested).unwrap();
+
+        let event: EventMessage =
+            Event::AccountUpdateRequested((address.clone(), account_bytes)).into();
+        let status = node.handle(event).await.unwrap();
+
+        assert_eq!(status, ActorState::Running);
+        assert_eq!(
+            node.get_account_by_address(&address).unwrap().storage(),
+            &Some(String::from("storage"))
+        );
+
+        let emitted: Event = events_rx.recv().await.unwrap().into();
+        assert!(matches!(emitted, Event::UpdatedAccount((updated, _)) if updated == address));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_does_not_overwrite_existing_accounts() {
+        remove_vrrb_data_dir();
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let (_, public_key) = generate_account_keypair();
+        let address = node.create_account(public_key).unwrap();
+
+        credit_account(&mut node, &address, 500);
+        let funded = node.get_account_by_address(&address).unwrap();
+        let account_bytes = bincode::serialize(&funded).unwrap();
+
+        let err = node.create_account(public_key).unwrap_err();
+        assert!(matches!(err, NodeError::AccountAlreadyExists(existing) if existing == address));
+
+        let err = node
+            .handle_create_account_requested(address.clone(), account_bytes)
+            .unwrap_err();
+        assert!(matches!(err, NodeError::AccountAlreadyExists(_)));
+
+        let account = node.get_or_create_account(public_key).unwrap();
+        assert_eq!(account.credits(), 500);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_rejects_account_update_changing_balances() {
+        use events::{Event, EventMessage};
+        use theater::Handler;
+
+        remove_vrrb_data_dir();
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let (_, public_key) = generate_account_keypair();
+        let address = node.create_account(public_key).unwrap();
+        credit_account(&mut node, &address, 500);
+
+        for field in [AccountField::Credits(1_000_000), AccountField::Debits(100)] {
+            let mut requested = node.get_account_by_address(&address).unwrap();
+            requested.update_field(field).unwrap();
+            let account_bytes = bincode::serialize(&requested).unwrap();
+
+            let event: EventMessage =
+                Event::AccountUpdateRequested((address.clone(), account_bytes)).into();
+            assert!(node.handle(event).await.is_err());
+        }
+
+        let account = node.get_account_by_address(&address).unwrap();
+        assert_eq!(account.credits(), 500);
+        assert_eq!(account.debits(), 0);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_rejects_account_update_for_unknown_address() {
+        use events::{Event, EventMessage};
+        use theater::Handler;
+
+        remove_vrrb_data_dir();
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let (_, public_key) = generate_account_keypair();
+        let address: Address = public_key.into();
+        let account_bytes = bincode::serialize(&Account::new(address.clone())).unwrap();
+
+        let event: EventMessage =
+            Event::AccountUpdateRequested((address.clone(), account_bytes)).into();
+
+        assert!(node.handle(event).await.is_err());
+        assert!(node.get_account_by_address(&address).is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn forwarded_txn_is_gossiped_onward_once() {
+        use crate::node_runtime::forwarded_txn_payload;
+        use events::Event;
+        use theater::Handler;
+        use vrrb_core::transactions::TransactionKind;
+
+        remove_vrrb_data_dir();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let peer_id = "peer-node".to_string();
+        let mut peer_sig_engine = create_sig_engine();
+        let peer_public_key = peer_sig_engine.public_key();
+        let own_id = node.config.id.clone();
+        node.consensus_driver.sig_engine.set_quorum_members(vec![(
+            QuorumKind::Farmer,
+            vec![
+                (peer_id.clone(), peer_public_key),
+                (own_id.clone(), *node.config.keypair.get_miner_public_key()),
+            ],
+        )]);
+
+        let funded_txn = |node: &mut NodeRuntime| {
+            let ((sender_account, sender), receiver) = create_sender_receiver_addresses();
+            node.state_driver
+                .insert_account(sender.clone(), sender_account.clone())
+                .unwrap();
+            create_txn_from_accounts((sender, Some(sender_account)), receiver, vec![])
+        };
+        let txn = funded_txn(&mut node);
+        let echoed = funded_txn(&mut node);
+        let forged = funded_txn(&mut node);
+        let unfunded = {
+            let ((sender_account, sender), receiver) = create_sender_receiver_addresses();
+            create_txn_from_accounts((sender, Some(sender_account)), receiver, vec![])
+        };
+
+        let mut sign = |origin: &NodeId, txn: &TransactionKind| {
+            peer_sig_engine
+                .sign(forwarded_txn_payload(origin, &txn.id()))
+                .unwrap()
+        };
+        let txn_signature = sign(&peer_id, &txn);
+        let echoed_signature = sign(&own_id, &echoed);
+        let forged_signature = sign(&"other-node".to_string(), &forged);
+        let unfunded_signature = sign(&peer_id, &unfunded);
+
+        for event in [
+            Event::NewTxnForwarded(peer_id.clone(), txn.clone(), txn_signature),
+            Event::NewTxnForwarded(peer_id.clone(), txn.clone(), txn_signature),
+            Event::NewTxnForwarded(own_id.clone(), echoed.clone(), echoed_signature),
+        ] {
+            node.handle(event.into()).await.unwrap();
+        }
+
+        for event in [
+            Event::NewTxnForwarded(peer_id.clone(), forged.clone(), forged_signature),
+            Event::NewTxnForwarded(peer_id, unfunded.clone(), unfunded_signature),
+        ] {
+            assert!(node.handle(event.into()).await.is_err());
+        }
+
+        let mut gossiped = vec![];
+        while let Ok(event) = events_rx.try_recv() {
+            let event: Event = event.into();
+            if let Event::BroadcastTxn(txn, signature) = event {
+                node.consensus_driver
+                    .sig_engine
+                    .verify(
+                        &own_id,
+                        &signature,
+                        &forwarded_txn_payload(&own_id, &txn.id()),
+                    )
+                    .unwrap();
+                gossiped.push(txn);
+            }
+        }
+
+        assert_eq!(gossiped, vec![txn.clone()]);
+
+        let mempool = node.state_driver.mempool_read_handle_factory();
+        assert!(mempool.get(&txn.id()).is_some());
+        assert!(mempool.get(&echoed.id()).is_none());
+        assert!(mempool.get(&forged.id()).is_none());
+        assert!(mempool.get(&unfunded.id()).is_none());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_publishes_once_a_full_event_channel_drains() {
+        use events::Event;
+
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(1);
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let node = nodes.pop_front().unwrap();
+
+        while events_rx.try_recv().is_ok() {}
+        events_tx.send(Event::NoOp.into()).await.unwrap();
+
+        let drain = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            let mut received = vec![];
+            for _ in 0..2 {
+                received.push(Event::from(events_rx.recv().await.unwrap()));
+            }
+            received
+        });
+
+        node.publish(Event::QuorumFormed.into()).await.unwrap();
+
+        assert_eq!(drain.await.unwrap(), vec![Event::NoOp, Event::QuorumFormed]);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn node_runtime_stops_publishing_on_a_stuck_or_closed_event_channel() {
+        use events::Event;
+
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(1);
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+        let timeout = std::time::Duration::from_millis(5);
+        node.config.event_send_timeout = Some(timeout);
+
+        while events_rx.try_recv().is_ok() {}
+        events_tx.send(Event::NoOp.into()).await.unwrap();
+
+        assert!(matches!(
+            node.publish(Event::QuorumFormed.into()).await,
+            Err(NodeError::EventSendTimeout(elapsed)) if elapsed == timeout
+        ));
+
+        drop(events_rx);
+
+        assert!(matches!(
+            node.publish(Event::QuorumFormed.into()).await,
+            Err(NodeError::MpscSend(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn state_sync_requests_from_known_peers_are_answered_with_the_state_root() {
+        use events::Event;
+        use theater::Handler;
+
+        use crate::node_runtime::state_sync_request_payload;
+
+        remove_vrrb_data_dir();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let peer_id = "lagging-peer".to_string();
+        let mut peer_sig_engine = create_sig_engine();
+        let peer_public_key = peer_sig_engine.public_key();
+        node.consensus_driver.sig_engine.set_quorum_members(vec![(
+            QuorumKind::Farmer,
+            vec![(peer_id.clone(), peer_public_key)],
+        )]);
+
+        let signature = peer_sig_engine
+            .sign(state_sync_request_payload(&peer_id, None))
+            .unwrap();
+        let request = Event::StateSyncRequested {
+            from: peer_id.clone(),
+            at_height: None,
+            signature,
+        };
+
+        // A request signed for another height is rejected, and repeated
+        // requests within the throttling interval go unanswered.
+        let forged = Event::StateSyncRequested {
+            from: peer_id.clone(),
+            at_height: Some(7),
+            signature,
+        };
+        assert!(node.handle(forged.into()).await.is_err());
+        node.handle(request.clone().into()).await.unwrap();
+        node.handle(request.into()).await.unwrap();
+
+        let mut responses = vec![];
+        while let Ok(event) = events_rx.try_recv() {
+            let event: Event = event.into();
+            if let Event::StateSyncResponse {
+                to,
+                height,
+                state_root_hash,
+            } = event
+            {
+                responses.push((to, height, state_root_hash));
+            }
+        }
+
+        assert_eq!(
+            responses,
+            vec![(peer_id, 0, node.state_root_hash().unwrap())]
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn unhandled_consensus_events_are_replayed_from_the_event_wal() {
+        use events::{Event, EventWal};
+        use theater::Handler;
+
+        remove_vrrb_data_dir();
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut config = nodes.pop_front().unwrap().config;
+        config.enable_event_wal = true;
+        config.data_dir = std::env::temp_dir().join(format!("event-wal-{}", uuid::Uuid::new_v4()));
+        config.db_path = config.data_dir.join("db");
+
+        let certified =
+            Event::ConvergenceBlockCertified(crate::test_utils::dummy_convergence_block());
+
+        std::fs::create_dir_all(&config.data_dir).unwrap();
+        EventWal::open_for_consensus(config.event_wal_path())
+            .unwrap()
+            .append(&certified)
+            .unwrap();
+
+        let mut node = create_node_runtime(&config, events_tx).await;
+
+        node.replay_event_wal().await.unwrap();
+        assert!(EventWal::open_for_consensus(config.event_wal_path())
+            .unwrap()
+            .replay_wal()
+            .unwrap()
+            .is_empty());
+
+        node.handle(certified.into()).await.unwrap();
+        assert_eq!(std::fs::metadata(config.event_wal_path()).unwrap().len(), 0);
+
+        std::fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn always_failing_events_stop_being_replayed_from_the_event_wal() {
+        use block::Certificate;
+        use events::{Event, EventWal, MAX_EVENT_ATTEMPTS};
+
+        remove_vrrb_data_dir();
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut config = nodes.pop_front().unwrap().config;
+        config.enable_event_wal = true;
+        config.data_dir = std::env::temp_dir().join(format!("event-wal-{}", uuid::Uuid::new_v4()));
+        config.db_path = config.data_dir.join("db");
+
+        // a certificate for an unknown block, without any signatures, never
+        // passes verification
+        let failing = Event::BlockCertificateCreated(Certificate {
+            signatures: vec![],
+            inauguration: None,
+            root_hash: String::from("root"),
+            block_hash: String::from("unknown-block"),
+        });
+
+        std::fs::create_dir_all(&config.data_dir).unwrap();
+        EventWal::open_for_consensus(config.event_wal_path())
+            .unwrap()
+            .append(&failing)
+            .unwrap();
+
+        let mut node = create_node_runtime(&config, events_tx).await;
+
+        for attempt in 1..=MAX_EVENT_ATTEMPTS {
+            node.replay_event_wal().await.unwrap();
+
+            let pending = EventWal::open_for_consensus(config.event_wal_path())
+                .unwrap()
+                .replay_wal()
+                .unwrap();
+            assert_eq!(pending.is_empty(), attempt == MAX_EVENT_ATTEMPTS);
+        }
+
+        let dead_letters =
+            std::fs::read_to_string(config.event_wal_path().with_extension("dead")).unwrap();
+        assert_eq!(dead_letters.lines().count(), 1);
+
+        std::fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn mempool_is_sized_from_the_node_config() {
+        use vrrb_config::MempoolConfig;
+
+        remove_vrrb_data_dir();
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut config = nodes.pop_front().unwrap().config;
+        config.mempool = MempoolConfig {
+            initial_capacity: 16,
+            max_capacity: Some(32),
+        };
+
+        let node = create_node_runtime(&config, events_tx).await;
+
+        assert_eq!(node.state_driver.mempool.max_capacity(), Some(32));
+    }
 }
@@ -1,15 +1,17 @@
 use crate::{
-    consensus::{ConsensusModule, ConsensusModuleConfig},
+    consensus::{ConsensusModule, ConsensusModuleConfig, QuorumInfo},
     result::{NodeError, Result},
+    runtime::{select_with_fairness, select_within_limits, BlockLimits, TXN_BASE_GAS},
     state_manager::{StateManager, StateManagerConfig},
 };
 
 use block::{
-    header::BlockHeader, Block, Certificate, ClaimHash, ConvergenceBlock, GenesisBlock,
-    GenesisReceiver, GenesisRewards, ProposalBlock, RefHash,
+    header::BlockHeader, txn_inclusion_path, Block, Certificate, ChainHeadReadHandle,
+    ChainHeadSummary, ClaimHash, ConvergenceBlock, GenesisBlock, GenesisReceiver, GenesisRewards,
+    InclusionProof, ProposalBlock, RefHash,
 };
 use bulldag::graph::BullDag;
-use events::{Event, EventMessage, EventPublisher, Vote};
+use events::{Event, EventMessage, EventPublisher, EventWal, Vote, WalSeq, MAX_EVENT_ATTEMPTS};
 use mempool::{LeftRightMempool, MempoolReadHandleFactory, TxnRecord};
 use metric_exporter::metric_factory::PrometheusFactory;
 use miner::{Miner, MinerConfig};
@@ -17,26 +19,48 @@ use primitives::{
     Address, Epoch, NodeId, NodeType, PublicKey, QuorumKind, Round, Signature, NETWORK_TOPIC_STR,
     RUNTIME_TOPIC_STR,
 };
-use ritelinked::LinkedHashMap;
+use reward::reward::{GenesisDistribution, GENESIS_REWARD};
+use ritelinked::{LinkedHashMap, LinkedHashSet};
 use secp256k1::{hashes::Hash, Message};
 use signer::engine::{QuorumMembers as InaugaratedMembers, SignerEngine};
 use std::{
     collections::HashMap,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
 };
 use storage::vrrbdb::{StateStoreReadHandleFactory, VrrbDbConfig, VrrbDbReadHandle};
 use theater::{ActorId, ActorState};
 use tokio::task::JoinHandle;
 use utils::payload::digest_data_to_bytes;
+use validator::txn_validator::TxnValidator;
 use vrrb_config::{NodeConfig, QuorumMembershipConfig};
 use vrrb_core::{
     account::{Account, UpdateArgs},
     claim::Claim,
-    transactions::{TransactionDigest, TransactionKind},
+    transactions::{Transaction, TransactionDigest, TransactionKind},
 };
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
 
+/// Number of recently gossiped transactions remembered to avoid gossiping
+/// them again.
+pub const MAX_FORWARDED_TXNS: usize = 10_000;
+
+/// Minimum time between two state sync requests answered for the same peer.
+pub const STATE_SYNC_MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum number of state sync requests answered within
+/// `STATE_SYNC_MIN_INTERVAL`, across all peers.
+pub const MAX_STATE_SYNC_RESPONSES: usize = 64;
+
+/// Number of peers remembered to throttle the state sync requests answered
+/// for them.
+pub const MAX_STATE_SYNC_PEERS: usize = 1024;
+
+/// Amount credited to each whitelisted node and additional genesis receiver
+/// when no genesis allocations are configured.
+pub const DEFAULT_GENESIS_ALLOCATION: u128 = 10_000;
+
 #[derive(Debug, Clone)]
 pub struct NodeRuntime {
     // TODO: reduce scope visibility of these
@@ -50,6 +74,10 @@ pub struct NodeRuntime {
     pub mining_driver: Miner,
     pub claim: Claim,
     pub pending_quorum: Option<InaugaratedMembers>,
+    chain_head: ChainHeadReadHandle,
+    forwarded_txns: LinkedHashSet<TransactionDigest>,
+    state_sync_served: LinkedHashMap<NodeId, Instant>,
+    event_wal: Option<Arc<Mutex<EventWal>>>,
 }
 
 impl NodeRuntime {
@@ -89,14 +117,18 @@ impl NodeRuntime {
         }
 
         let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
-        let mempool = LeftRightMempool::new();
+        let mempool = LeftRightMempool::with_config(config.mempool);
 
-        let state_driver = StateManager::new(StateManagerConfig {
+        let mut state_driver = StateManager::new(StateManagerConfig {
             database: database.clone(),
             mempool,
             dag: dag.clone(),
             claim: claim.clone(),
         });
+        state_driver
+            .dag
+            .set_signature_collection_deadline(config.signature_collection_deadline);
+        state_driver.set_mempool_pressure_config(config.mempool_pressure.clone());
 
         let (_, miner_secret_key) = config.keypair.get_secret_keys();
         let (_, miner_public_key) = config.keypair.get_public_keys();
@@ -132,6 +164,14 @@ impl NodeRuntime {
             certified_pending_transactions,
         )?;
 
+        let event_wal = if config.enable_event_wal {
+            std::fs::create_dir_all(config.data_dir())?;
+            let event_wal = EventWal::open_for_consensus(config.event_wal_path())?;
+            Some(Arc::new(Mutex::new(event_wal)))
+        } else {
+            None
+        };
+
         Ok(Self {
             id: uuid::Uuid::new_v4().to_string(),
             status: ActorState::Stopped,
@@ -142,9 +182,265 @@ impl NodeRuntime {
             mining_driver: miner,
             claim,
             pending_quorum: None,
+            chain_head: ChainHeadReadHandle::new(),
+            forwarded_txns: LinkedHashSet::new(),
+            state_sync_served: LinkedHashMap::new(),
+            event_wal,
+        })
+    }
+
+    /// Persists `event` to the write-ahead log before it is handled, if the
+    /// log is enabled and the event is consensus-critical. Returns the
+    /// sequence number to mark the event as processed with once handled.
+    pub(crate) fn append_to_event_wal(&self, event: &Event) -> Result<Option<WalSeq>> {
+        let Some(event_wal) = &self.event_wal else {
+            return Ok(None);
+        };
+
+        let mut event_wal = event_wal
+            .lock()
+            .map_err(|err| NodeError::Other(format!("event WAL lock poisoned: {err}")))?;
+
+        Ok(event_wal.append(event)?)
+    }
+
+    /// Marks the event appended to the write-ahead log under `seq` as
+    /// handled, so that it is not replayed on the next startup.
+    pub(crate) fn mark_event_processed(&self, seq: WalSeq) -> Result<()> {
+        let Some(event_wal) = &self.event_wal else {
+            return Ok(());
+        };
+
+        let mut event_wal = event_wal
+            .lock()
+            .map_err(|err| NodeError::Other(format!("event WAL lock poisoned: {err}")))?;
+
+        Ok(event_wal.mark_processed(seq)?)
+    }
+
+    /// Records a failed attempt at handling the event appended to the
+    /// write-ahead log under `seq`. See `EventWal::mark_failed`.
+    pub(crate) fn mark_event_failed(&self, seq: WalSeq, event: &Event) -> Result<()> {
+        let Some(event_wal) = &self.event_wal else {
+            return Ok(());
+        };
+
+        let mut event_wal = event_wal
+            .lock()
+            .map_err(|err| NodeError::Other(format!("event WAL lock poisoned: {err}")))?;
+
+        if event_wal.mark_failed(seq, event)? {
+            telemetry::error!(
+                "Event {seq} failed to be handled {MAX_EVENT_ATTEMPTS} times, moved it to {}",
+                event_wal.dead_letter_path().display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Handles the consensus-critical events the write-ahead log holds from
+    /// a previous run that stopped before handling them, in the order they
+    /// were received. Events that fail to be handled again are kept in the
+    /// log until they failed `MAX_EVENT_ATTEMPTS` times.
+    pub async fn replay_event_wal(&mut self) -> Result<()> {
+        let pending = match &self.event_wal {
+            Some(event_wal) => event_wal
+                .lock()
+                .map_err(|err| NodeError::Other(format!("event WAL lock poisoned: {err}")))?
+                .replay_wal()?,
+            None => return Ok(()),
+        };
+
+        for (seq, event) in pending {
+            telemetry::info!("Replaying event {seq} from the write-ahead log");
+
+            match self.handle_event(event.clone()).await {
+                Ok(_) => self.mark_event_processed(seq)?,
+                Err(err) => {
+                    telemetry::warn!(
+                        "Failed to replay event {seq} from the write-ahead log: {err}"
+                    );
+                    self.mark_event_failed(seq, &event)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles a transaction gossiped to this node by `origin`, who signed
+    /// it along with the transaction digest, see `forwarded_txn_payload`.
+    /// Transactions are only pooled and gossiped onward once the signature
+    /// checks out against the public key the origin is known by, and the
+    /// transaction itself is valid, including its sender's balance covering
+    /// it. The transaction is added to the mempool if it isn't pooled yet,
+    /// and the event gossiping it onward, signed by this node, is returned.
+    ///
+    /// No event is returned for transactions this node already gossiped, nor
+    /// for transactions that come back from this node itself, so that gossip
+    /// doesn't loop between peers.
+    pub fn handle_new_txn_forwarded(
+        &mut self,
+        origin: &NodeId,
+        txn: TransactionKind,
+        signature: &Signature,
+    ) -> Result<Option<Event>> {
+        let digest = txn.id();
+
+        if origin == &self.config.id || self.forwarded_txns.contains(&digest) {
+            return Ok(None);
+        }
+
+        self.consensus_driver
+            .sig_engine
+            .verify(origin, signature, &forwarded_txn_payload(origin, &digest))
+            .map_err(|err| {
+                NodeError::Other(format!(
+                    "transaction {digest} forwarded by unauthenticated peer {origin}: {err}"
+                ))
+            })?;
+
+        TxnValidator::new()
+            .validate(self.state_store_read_handle_factory(), &txn)
+            .map_err(|err| {
+                NodeError::Other(format!(
+                    "invalid transaction {digest} forwarded by {origin}: {err:?}"
+                ))
+            })?;
+
+        if self
+            .state_driver
+            .mempool_read_handle_factory()
+            .get(&digest)
+            .is_none()
+        {
+            self.state_driver.insert_txn_to_mempool(txn.clone())?;
+        }
+
+        self.forwarded_txns.insert(digest.clone());
+        while self.forwarded_txns.len() > MAX_FORWARDED_TXNS {
+            self.forwarded_txns.pop_front();
+        }
+
+        let signature = self
+            .consensus_driver
+            .sig_engine
+            .sign(forwarded_txn_payload(&self.config.id, &digest))
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(Some(Event::BroadcastTxn(txn, signature)))
+    }
+
+    /// Builds the request asking peers for the height and state root hash of
+    /// their last confirmed block, on behalf of this node, which has state up
+    /// to `at_height`, if any. The request is signed so that peers only
+    /// answer nodes they know.
+    pub fn state_sync_request(&mut self, at_height: Option<u128>) -> Result<Event> {
+        let from = self.config.id.clone();
+        let signature = self
+            .consensus_driver
+            .sig_engine
+            .sign(state_sync_request_payload(&from, at_height))
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(Event::StateSyncRequested {
+            from,
+            at_height,
+            signature,
         })
     }
 
+    /// Answers a state sync request from the peer `from`, which has state up
+    /// to `at_height`, if any, with the height and state root hash of the
+    /// last confirmed block.
+    ///
+    /// Requests are only answered once their signature checks out against
+    /// the public key `from` is known by. Requests from a peer are answered
+    /// at most once every `STATE_SYNC_MIN_INTERVAL`, and no more than
+    /// `MAX_STATE_SYNC_RESPONSES` requests are answered within that
+    /// interval overall. No response is returned for requests exceeding
+    /// those rates.
+    pub fn handle_state_sync_requested(
+        &mut self,
+        from: NodeId,
+        at_height: Option<u128>,
+        signature: &Signature,
+    ) -> Result<Option<Event>> {
+        self.consensus_driver
+            .sig_engine
+            .verify(
+                &from,
+                signature,
+                &state_sync_request_payload(&from, at_height),
+            )
+            .map_err(|err| {
+                NodeError::Other(format!(
+                    "state sync requested by unauthenticated peer {from}: {err}"
+                ))
+            })?;
+
+        let now = Instant::now();
+        if let Some(served_at) = self.state_sync_served.get(&from) {
+            if now.duration_since(*served_at) < STATE_SYNC_MIN_INTERVAL {
+                telemetry::warn!("Throttling state sync request from {from}");
+                return Ok(None);
+            }
+        }
+
+        let recently_served = self
+            .state_sync_served
+            .values()
+            .filter(|served_at| now.duration_since(**served_at) < STATE_SYNC_MIN_INTERVAL)
+            .count();
+        if recently_served >= MAX_STATE_SYNC_RESPONSES {
+            telemetry::warn!("Throttling state sync request from {from}: too many requests");
+            return Ok(None);
+        }
+
+        let height = self
+            .chain_head_summary()
+            .map(|summary| summary.height)
+            .unwrap_or_default();
+        let state_root_hash = self.state_root_hash()?;
+
+        self.state_sync_served.remove(&from);
+        self.state_sync_served.insert(from.clone(), now);
+        while self.state_sync_served.len() > MAX_STATE_SYNC_PEERS {
+            self.state_sync_served.pop_front();
+        }
+
+        Ok(Some(Event::StateSyncResponse {
+            to: from,
+            height,
+            state_root_hash,
+        }))
+    }
+
+    /// Compares the last confirmed block of a peer that answered this node's
+    /// state sync request with the local one, reporting whether this node
+    /// lags behind or its state diverges from the peer's.
+    pub fn handle_state_sync_response(&self, height: u128, state_root_hash: &str) -> Result<()> {
+        let local_height = self
+            .chain_head_summary()
+            .map(|summary| summary.height)
+            .unwrap_or_default();
+
+        if height > local_height {
+            telemetry::warn!(
+                "{} lags behind a peer at height {height}, local height is {local_height}",
+                self.config.id
+            );
+        } else if height == local_height && state_root_hash != self.state_root_hash()? {
+            telemetry::warn!(
+                "State of {} diverges from a peer's at height {height}",
+                self.config.id
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn certified_convergence_block_exists_within_dag(&self, block_hash: String) -> bool {
         if let Ok(guard) = self.state_driver.dag.read() {
             if let Some(vertex) = guard.get_vertex(block_hash) {
@@ -199,6 +495,26 @@ impl NodeRuntime {
         Ok(())
     }
 
+    pub fn is_in_maintenance_mode(&self) -> bool {
+        self.config.maintenance_mode
+    }
+
+    pub fn set_maintenance_mode(&mut self, enabled: bool) {
+        self.config.maintenance_mode = enabled;
+    }
+
+    /// Refuses block production and quorum signing while the node is in
+    /// maintenance mode.
+    pub fn ensure_not_in_maintenance_mode(&self, action: &str) -> Result<()> {
+        if self.config.maintenance_mode {
+            return Err(NodeError::Other(format!(
+                "Node {} is in maintenance mode and is not allowed to: {action}",
+                self.config.id
+            )));
+        }
+        Ok(())
+    }
+
     pub fn belongs_to_correct_quorum(
         &self,
         intended_quorum: QuorumKind,
@@ -231,10 +547,30 @@ impl NodeRuntime {
         self.send_event(RUNTIME_TOPIC_STR, event).await
     }
 
-    async fn send_event(&mut self, topic: &str, event: Event) -> Result<()> {
+    async fn send_event(&self, topic: &str, event: Event) -> Result<()> {
+        let is_stop = matches!(event, Event::Stop);
         let message = EventMessage::new(Some(topic.into()), event);
 
-        self.events_tx.send(message).await?;
+        // A stop signal must not be dropped, so it waits for room in the
+        // channel regardless of the configured timeout.
+        if is_stop {
+            self.events_tx.send(message).await?;
+            return Ok(());
+        }
+
+        self.publish(message).await
+    }
+
+    /// Publishes a message on the node's event channel, waiting for room
+    /// while the channel is full. The wait is bounded by
+    /// `NodeConfig::event_send_timeout` when one is configured.
+    pub(crate) async fn publish(&self, message: EventMessage) -> Result<()> {
+        match self.config.event_send_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.events_tx.send(message))
+                .await
+                .map_err(|_| NodeError::EventSendTimeout(timeout))??,
+            None => self.events_tx.send(message).await?,
+        }
 
         Ok(())
     }
@@ -246,6 +582,20 @@ impl NodeRuntime {
             .clone()
     }
 
+    /// Returns the kind, members and vote threshold of the quorum this node
+    /// belongs to, or `None` until it's assigned to one. Bootstrap nodes never
+    /// belong to a quorum.
+    pub fn quorum_info(&self) -> Option<QuorumInfo> {
+        if matches!(self.config.node_type, NodeType::Bootstrap) {
+            return None;
+        }
+
+        self.consensus_driver
+            .membership_config()
+            .as_ref()
+            .map(|membership| QuorumInfo::new(&self.config.id, membership))
+    }
+
     pub fn state_read_handle(&self) -> VrrbDbReadHandle {
         self.state_driver.read_handle()
     }
@@ -262,19 +612,103 @@ impl NodeRuntime {
         self.mempool_read_handle_factory().entries()
     }
 
-    // TODO: This should be a const function
-    pub fn distribute_genesis_reward(
-        &self,
-        receivers: Vec<GenesisReceiver>,
-    ) -> Result<GenesisRewards> {
+    /// Builds the rewards of the genesis block this miner is about to mine.
+    /// They only depend on the node's config, which is what every other node
+    /// checks them against when the genesis block reaches it.
+    pub fn distribute_genesis_reward(&self) -> Result<GenesisRewards> {
         self.has_required_node_type(NodeType::Miner, "produce genesis transactions")?;
-        Ok(GenesisRewards(
-            receivers.iter().map(|rc| (rc.to_owned(), 10000)).collect(),
-        ))
+        Self::genesis_transactions_for(&self.config)
+    }
+
+    /// Derives the genesis rewards from `config` alone, so that any node can
+    /// reproduce and check the genesis of the network offline. The miner key
+    /// of `config` is taken as the genesis miner.
+    pub fn genesis_transactions_for(config: &NodeConfig) -> Result<GenesisRewards> {
+        let miner = Address::new(config.keypair.miner_public_key_owned());
+
+        Self::genesis_transactions_mined_by(config, &miner)
+    }
+
+    /// Derives the rewards of a genesis block mined by `miner` from `config`.
+    ///
+    /// The `genesis_allocations` are used when there are any. Otherwise every
+    /// whitelisted node and additional genesis receiver is credited
+    /// `DEFAULT_GENESIS_ALLOCATION`. When there are none of those either and
+    /// no `genesis_total_supply` is set, the whole `GENESIS_REWARD` goes to
+    /// the miner.
+    ///
+    /// The allocations go through `GenesisDistribution`, so they are ordered
+    /// by address and allocations to the same address are added up, and the
+    /// result doesn't depend on how the list is ordered. When
+    /// `genesis_total_supply` is set, the allocations must add up to it minus
+    /// `GENESIS_REWARD`.
+    pub fn genesis_transactions_mined_by(
+        config: &NodeConfig,
+        miner: &Address,
+    ) -> Result<GenesisRewards> {
+        let receivers = Self::genesis_receivers_for(config);
+
+        if receivers.is_empty() && config.genesis_total_supply.is_none() {
+            return Ok(Self::genesis_rewards(&GenesisDistribution::single(
+                miner.clone(),
+            )));
+        }
+
+        let total = match config.genesis_total_supply {
+            Some(total_supply) => total_supply.checked_sub(GENESIS_REWARD).ok_or_else(|| {
+                NodeError::Other(format!(
+                    "genesis total supply {total_supply} is less than the genesis reward"
+                ))
+            })?,
+            None => receivers
+                .iter()
+                .try_fold(0u128, |total, (_, amount)| total.checked_add(*amount))
+                .ok_or_else(|| NodeError::Other("genesis allocations overflow".to_string()))?,
+        };
+
+        let distribution = GenesisDistribution::new(receivers, total)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(Self::genesis_rewards(&distribution))
+    }
+
+    fn genesis_rewards(distribution: &GenesisDistribution) -> GenesisRewards {
+        GenesisRewards(
+            distribution
+                .receivers()
+                .iter()
+                .map(|(address, amount)| (GenesisReceiver::new(address.clone()), *amount))
+                .collect(),
+        )
+    }
+
+    fn genesis_receivers_for(config: &NodeConfig) -> Vec<(Address, u128)> {
+        if !config.genesis_allocations.is_empty() {
+            return config
+                .genesis_allocations
+                .iter()
+                .map(|allocation| (allocation.address.clone(), allocation.amount))
+                .collect();
+        }
+
+        let additional_receivers = config
+            .bootstrap_config
+            .as_ref()
+            .and_then(|bootstrap_config| bootstrap_config.additional_genesis_receivers.clone())
+            .unwrap_or_default();
+
+        config
+            .whitelisted_nodes
+            .iter()
+            .map(|member| Address::new(member.validator_public_key))
+            .chain(additional_receivers)
+            .map(|address| (address, DEFAULT_GENESIS_ALLOCATION))
+            .collect()
     }
 
     pub fn mine_genesis_block(&self, genesis_rewards: GenesisRewards) -> Result<GenesisBlock> {
         self.has_required_node_type(NodeType::Miner, "mine genesis block")?;
+        self.ensure_not_in_maintenance_mode("mine genesis block")?;
 
         let claim = self.state_driver.dag.claim();
 
@@ -336,6 +770,22 @@ impl NodeRuntime {
         Ok(())
     }
 
+    /// Checks that a genesis block credits exactly the genesis rewards this
+    /// node derives from its own config for the miner of the block.
+    pub fn verify_genesis_rewards(&self, genesis_block: &GenesisBlock) -> Result<()> {
+        let miner = &genesis_block.header.miner_claim.address;
+        let expected = Self::genesis_transactions_mined_by(&self.config, miner)?;
+
+        if genesis_block.genesis_rewards != expected {
+            return Err(NodeError::Other(format!(
+                "genesis block {} does not carry the configured genesis rewards",
+                genesis_block.hash
+            )));
+        }
+
+        Ok(())
+    }
+
     fn hash_block_header(&self, header: &BlockHeader) -> secp256k1::hashes::sha256::Hash {
         let hashed = format!(
             "{:?}{:?}{:?}{:?}{:?}{:?}{:?}{:?}{:?}{:?}{:?}{:?}",
@@ -368,16 +818,12 @@ impl NodeRuntime {
             .sig_engine
             .verify(&node_id, &sig, &genesis.hash)
             .map_err(|err| NodeError::Other(err.to_string()))?;
-        let set = self
-            .state_driver
-            .dag
-            .add_signer_to_block(
-                genesis.hash.clone(),
-                sig,
-                node_id,
-                &self.consensus_driver.sig_engine,
-            )
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        let set = self.state_driver.dag.add_signer_to_block(
+            genesis.hash.clone(),
+            sig,
+            node_id,
+            &self.consensus_driver.sig_engine,
+        )?;
         let certificate = self
             .consensus_driver
             .certify_genesis_block(genesis, set.into_iter().collect())?;
@@ -395,11 +841,21 @@ impl NodeRuntime {
         sig_engine: SignerEngine,
     ) -> Result<ProposalBlock> {
         self.consensus_driver.is_harvester()?;
-        let txns = self
+        self.ensure_not_in_maintenance_mode("mine proposal block")?;
+        let candidates: Vec<(&TransactionDigest, &TransactionKind)> = self
             .consensus_driver
             .quorum_certified_txns
             .iter()
-            .take(PULL_TXN_BATCH_SIZE);
+            .map(|(digest, (txn, _cert))| (digest, txn))
+            .collect();
+
+        let txns = select_with_fairness(
+            candidates,
+            PULL_TXN_BATCH_SIZE,
+            &self.config.proposal_fairness,
+            |(_, txn)| txn.fee(),
+            |(_, txn)| txn.timestamp(),
+        );
 
         // NOTE: Read updated claims
         // let claim_map = self.vrrbdb_read_handle.claim_store_values();
@@ -408,19 +864,9 @@ impl NodeRuntime {
             .map(|from| (from.hash, from.clone()))
             .collect();
 
-        //TODO: variable _cert is not being used.
         let txns_list: LinkedHashMap<TransactionDigest, TransactionKind> = txns
             .into_iter()
-            .map(|(digest, (txn, _cert))| {
-                //                if let Err(err) = self
-                //                    .consensus_driver
-                //                    .certified_txns_filter
-                //                    .push(&txn.id().to_string())
-                //                {
-                //                    telemetry::error!("Error pushing txn to certified txns filter: {err}");
-                //                }
-                (digest.clone(), txn.clone())
-            })
+            .map(|(digest, txn)| (digest.clone(), txn.clone()))
             .collect();
 
         self.consensus_driver
@@ -431,8 +877,44 @@ impl NodeRuntime {
         ))
     }
 
+    /// Picks the mempool transactions to include in a block, highest fee
+    /// first, keeping the block within `max_count` transactions,
+    /// `max_size_bytes` of serialized transactions and `max_gas`.
+    pub fn select_block_transactions(
+        &self,
+        max_count: usize,
+        max_size_bytes: usize,
+        max_gas: u64,
+    ) -> Vec<TransactionKind> {
+        let limits = BlockLimits {
+            max_count,
+            max_size_bytes,
+            max_gas,
+        };
+
+        let candidates: Vec<TransactionKind> = self
+            .mempool_snapshot()
+            .into_values()
+            .map(|record| record.txn)
+            .collect();
+
+        select_within_limits(
+            candidates,
+            limits,
+            |txn| txn.fee(),
+            |txn| txn.timestamp(),
+            |txn| {
+                bincode::serialized_size(txn)
+                    .map(|size| size as usize)
+                    .unwrap_or(usize::MAX)
+            },
+            |_| TXN_BASE_GAS,
+        )
+    }
+
     pub fn mine_convergence_block(&mut self) -> Result<ConvergenceBlock> {
         self.has_required_node_type(NodeType::Miner, "mine convergence block")?;
+        self.ensure_not_in_maintenance_mode("mine convergence block")?;
         self.mining_driver
             .mine_convergence_block()
             .ok_or(NodeError::Other(
@@ -477,6 +959,82 @@ impl NodeRuntime {
         self.state_driver.state_root_hash()
     }
 
+    pub fn claims_root_hash(&self) -> Result<String> {
+        self.state_driver.claims_root_hash()
+    }
+
+    /// Summarizes the last confirmed block in the DAG along with the current
+    /// roots of the state, transaction and claim stores.
+    pub fn chain_head_summary(&self) -> Result<ChainHeadSummary> {
+        let (tip_hash, header) = match self.state_driver.dag.last_confirmed_block() {
+            Some(Block::Genesis { block }) => (block.hash, block.header),
+            Some(Block::Convergence { block }) => (block.hash, block.header),
+            Some(Block::Proposal { .. }) | None => {
+                return Err(NodeError::Other(format!(
+                    "Node {} does not have a last confirmed block",
+                    self.config.id
+                )))
+            }
+        };
+
+        Ok(ChainHeadSummary {
+            tip_hash,
+            height: header.block_height,
+            state_root_hash: self.state_root_hash()?,
+            transactions_root_hash: self.transactions_root_hash()?,
+            claims_root_hash: self.claims_root_hash()?,
+            timestamp: header.timestamp,
+            epoch: header.epoch,
+        })
+    }
+
+    /// Handle through which the latest published `ChainHeadSummary` can be
+    /// read, see `publish_chain_head`.
+    pub fn chain_head_read_handle(&self) -> ChainHeadReadHandle {
+        self.chain_head.clone()
+    }
+
+    /// Publishes the current `chain_head_summary` to the readers of
+    /// `chain_head_read_handle`. Does nothing until a block was confirmed.
+    pub fn publish_chain_head(&self) {
+        if let Ok(summary) = self.chain_head_summary() {
+            self.chain_head.publish(summary);
+        }
+    }
+
+    /// Builds a proof that `txn_digest` was included in a confirmed
+    /// convergence block, which clients can check with
+    /// `InclusionProof::verify` without trusting this node.
+    pub fn inclusion_proof(&self, txn_digest: &TransactionDigest) -> Result<InclusionProof> {
+        let block = self
+            .state_driver
+            .dag
+            .including_block(txn_digest)
+            .ok_or(NodeError::Other(format!(
+                "transaction {txn_digest} is not included in a confirmed block"
+            )))?;
+
+        let certificate = block.certificate.ok_or(NodeError::Other(format!(
+            "confirmed block {} has no certificate",
+            block.hash
+        )))?;
+
+        let (ref_hash, txn_path) =
+            txn_inclusion_path(&block.txns, txn_digest).ok_or(NodeError::Other(format!(
+                "confirmed block {} doesn't contain transaction {txn_digest}",
+                block.hash
+            )))?;
+
+        Ok(InclusionProof {
+            txn_digest: txn_digest.clone(),
+            block_hash: block.hash,
+            header: block.header,
+            certificate,
+            ref_hash,
+            txn_path,
+        })
+    }
+
     pub fn state_snapshot(&self) -> Result<HashMap<Address, Account>> {
         let handle = self.state_driver.read_handle();
         Ok(handle.state_store_values()?)
@@ -499,13 +1057,33 @@ impl NodeRuntime {
         todo!()
     }
 
+    /// Creates an empty account for `public_key`. Accounts that already exist
+    /// are left untouched and reported as `NodeError::AccountAlreadyExists`.
     pub fn create_account(&mut self, public_key: PublicKey) -> Result<Address> {
-        let account = Account::new(public_key.into());
+        let address: Address = public_key.into();
 
-        self.state_driver
-            .insert_account(public_key.into(), account)?;
+        if self.state_driver.get_account(&address).is_ok() {
+            return Err(NodeError::AccountAlreadyExists(address));
+        }
+
+        let account = Account::new(address.clone());
+
+        self.state_driver.insert_account(address.clone(), account)?;
+
+        Ok(address)
+    }
+
+    /// Returns the account of `public_key`, creating it first if it doesn't
+    /// exist yet.
+    pub fn get_or_create_account(&mut self, public_key: PublicKey) -> Result<Account> {
+        let address: Address = public_key.into();
 
-        Ok(public_key.into())
+        if let Ok(account) = self.state_driver.get_account(&address) {
+            return Ok(account);
+        }
+
+        self.create_account(public_key)?;
+        self.state_driver.get_account(&address)
     }
 
     pub fn update_account(&mut self, args: UpdateArgs) -> Result<()> {
@@ -577,12 +1155,59 @@ impl NodeRuntime {
         }
     }
 
+    /// Validates a batch of mempool transactions in one pass, reading each
+    /// sender account once. Digests that aren't in the mempool are left out
+    /// of the results.
+    pub fn validate_transaction_batch(
+        &mut self,
+        digests: &[TransactionDigest],
+        mempool_reader: MempoolReadHandleFactory,
+        state_reader: StateStoreReadHandleFactory,
+    ) -> Result<Vec<(TransactionKind, bool)>> {
+        self.has_required_node_type(NodeType::Validator, "validate transactions")?;
+        self.belongs_to_correct_quorum(QuorumKind::Farmer, "validate transactions")?;
+
+        let mempool = mempool_reader.clone();
+        let results = self.consensus_driver.validate_transaction_batch(
+            digests,
+            mempool_reader,
+            state_reader,
+        )?;
+
+        Ok(results
+            .into_iter()
+            .filter_map(|(digest, result)| match result {
+                Ok(txn) => Some((txn, true)),
+                Err(_) => mempool.get(&digest).map(|record| (record.txn, false)),
+            })
+            .collect())
+    }
+
     pub fn cast_vote_on_transaction_kind(
         &mut self,
         transaction: TransactionKind,
         validity: bool,
     ) -> Result<Vote> {
+        self.ensure_not_in_maintenance_mode("vote on transactions")?;
         self.consensus_driver
             .cast_vote_on_transaction_kind(transaction, validity)
     }
 }
+
+/// Payload a node signs when asking peers for their state, binding the
+/// request to the node `from` and the height it has state up to.
+pub fn state_sync_request_payload(from: &NodeId, at_height: Option<u128>) -> Vec<u8> {
+    [
+        b"state-sync".as_slice(),
+        &[u8::from(at_height.is_some())],
+        &at_height.unwrap_or_default().to_le_bytes(),
+        from.as_bytes(),
+    ]
+    .concat()
+}
+
+/// Payload a node signs when gossiping the transaction with `digest`,
+/// binding the transaction to the node that forwarded it.
+pub fn forwarded_txn_payload(origin: &NodeId, digest: &TransactionDigest) -> Vec<u8> {
+    [origin.as_bytes(), digest.to_string().as_bytes()].concat()
+}
@@ -1,12 +1,13 @@
 use crate::node_runtime::NodeRuntime;
 use async_trait::async_trait;
-use block::{Block, Certificate, GenesisReceiver};
+use block::{Block, Certificate};
 use events::{AssignedQuorumMembership, Event, EventMessage};
 use primitives::{
-    Address, ConvergencePartialSig, NodeType, QuorumKind, NETWORK_TOPIC_STR, RUNTIME_TOPIC_STR,
+    ConvergencePartialSig, NodeType, QuorumKind, NETWORK_TOPIC_STR, RUNTIME_TOPIC_STR,
 };
 use telemetry::info;
 use theater::{ActorId, ActorLabel, ActorState, Handler, TheaterError};
+use vrrb_core::transactions::Transaction;
 
 #[async_trait]
 impl Handler<EventMessage> for NodeRuntime {
@@ -35,7 +36,30 @@ impl Handler<EventMessage> for NodeRuntime {
     }
 
     async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
-        match event.into() {
+        let event: Event = event.into();
+        let Some(seq) = self.append_to_event_wal(&event)? else {
+            return self.handle_event(event).await;
+        };
+
+        match self.handle_event(event.clone()).await {
+            Ok(state) => {
+                self.mark_event_processed(seq)?;
+                Ok(state)
+            }
+            Err(err) => {
+                self.mark_event_failed(seq, &event)?;
+                Err(err)
+            }
+        }
+    }
+}
+
+impl NodeRuntime {
+    /// Handles a single event received by the runtime. Consensus-critical
+    /// events are persisted to the write-ahead log around this call, see
+    /// `NodeRuntime::replay_event_wal`.
+    pub(crate) async fn handle_event(&mut self, event: Event) -> theater::Result<ActorState> {
+        match event {
             Event::NodeAddedToPeerList(peer_data) => {
                 let assignments = self
                     .handle_node_added_to_peer_list(peer_data.clone())
@@ -52,8 +76,7 @@ impl Handler<EventMessage> for NodeRuntime {
                         Event::QuorumMembershipAssigmentsCreated(assignments),
                     );
 
-                    self.events_tx
-                        .send(event)
+                    self.publish(event)
                         .await
                         .map_err(|err| TheaterError::Other(err.to_string()))?;
                 }
@@ -64,34 +87,11 @@ impl Handler<EventMessage> for NodeRuntime {
                 if let Some(quorum_kind) = &self.consensus_driver.quorum_kind {
                     if *quorum_kind == QuorumKind::Miner && self.config.node_type == NodeType::Miner
                     {
-                        let mut genesis_receivers: Vec<GenesisReceiver> = self
-                            .config
-                            .whitelisted_nodes
-                            .iter()
-                            .map(|quorum_member| {
-                                GenesisReceiver::new(Address::new(
-                                    quorum_member.validator_public_key,
-                                ))
-                            })
-                            .collect();
-
-                        // add the addresses from config.bootstrap_config.additional_genesis_receivers to the genesis_receivers list
-                        if let Some(bootstrap_config) = &self.config.bootstrap_config {
-                            if let Some(additional_genesis_receivers) =
-                                &bootstrap_config.additional_genesis_receivers
-                            {
-                                for receiver in additional_genesis_receivers {
-                                    genesis_receivers.push(GenesisReceiver::new(receiver.clone()));
-                                }
-                            }
-                        }
-
                         let event = EventMessage::new(
                             Some(RUNTIME_TOPIC_STR.into()),
-                            Event::GenesisMinerElected { genesis_receivers },
+                            Event::GenesisMinerElected,
                         );
-                        self.events_tx
-                            .send(event)
+                        self.publish(event)
                             .await
                             .map_err(|err| TheaterError::Other(err.to_string()))?;
                     }
@@ -104,7 +104,7 @@ impl Handler<EventMessage> for NodeRuntime {
                 self.handle_quorum_election_started(header)
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
-            Event::MinerElectionStarted(header) => {
+            Event::MinerElectionStarted(block) => {
                 let claims = self
                     .state_driver
                     .read_handle()
@@ -113,7 +113,7 @@ impl Handler<EventMessage> for NodeRuntime {
 
                 let results = self
                     .consensus_driver
-                    .handle_miner_election_started(header, claims)
+                    .handle_miner_election_started(&block, claims)
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
                 let winner = results
@@ -126,8 +126,7 @@ impl Handler<EventMessage> for NodeRuntime {
 
                 let em = EventMessage::new(Some(NETWORK_TOPIC_STR.into()), event);
 
-                self.events_tx
-                    .send(em)
+                self.publish(em)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -155,8 +154,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     block_hash: block.hash,
                 };
 
-                self.events_tx
-                    .send(Event::ConvergenceBlockPartialSignComplete(partial_sig).into())
+                self.publish(Event::ConvergenceBlockPartialSignComplete(partial_sig).into())
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -166,37 +164,103 @@ impl Handler<EventMessage> for NodeRuntime {
                     .insert_txn_to_mempool(txn)
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
-                self.events_tx
-                    .send(Event::TxnAddedToMempool(txn_hash.clone()).into())
+                self.publish(Event::TxnAddedToMempool(txn_hash.clone()).into())
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if let Some(pressure) = self.state_driver.check_mempool_pressure(&txn_hash) {
+                    self.publish(pressure.into())
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            }
+
+            Event::NewTxnForwarded(origin, txn, signature) => {
+                let forwarded = self
+                    .handle_new_txn_forwarded(&origin, txn, &signature)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if let Some(event) = forwarded {
+                    let em = EventMessage::new(Some(NETWORK_TOPIC_STR.into()), event);
+
+                    self.publish(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            }
+
+            Event::StateSyncRequested {
+                from,
+                at_height,
+                signature,
+            } => {
+                let response = self
+                    .handle_state_sync_requested(from, at_height, &signature)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if let Some(response) = response {
+                    let em = EventMessage::new(Some(NETWORK_TOPIC_STR.into()), response);
+
+                    self.publish(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            }
+
+            Event::StateSyncResponse {
+                to,
+                height,
+                state_root_hash,
+            } if to == self.config.id => {
+                self.handle_state_sync_response(height, &state_root_hash)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
 
             Event::TxnValidated(txn) => {
+                let txn_hash = txn.id();
                 self.state_driver.handle_transaction_validated(txn).await?;
+
+                if let Some(relief) = self.state_driver.check_mempool_pressure(&txn_hash) {
+                    self.publish(relief.into())
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
             }
             Event::CreateAccountRequested((address, account_bytes)) => {
                 // I think we can get rid of this, as we now add accounts
                 // when they are a receiver of a transaction
                 self.handle_create_account_requested(address.clone(), account_bytes)?;
             }
-            Event::AccountUpdateRequested((_address, _account_bytes)) => {
-                todo!()
-                // This can occur as a result of block application
+            Event::AccountUpdateRequested((address, account_bytes)) => {
+                let account = self
+                    .handle_account_update_requested(address.clone(), account_bytes)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                let account_bytes = bincode::serialize(&account)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                self.publish(Event::UpdatedAccount((address, account_bytes)).into())
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            }
+            Event::MaintenanceModeRequested(enabled) => {
+                info!("{} setting maintenance mode to {enabled}", self.label());
+                self.set_maintenance_mode(enabled);
             }
             Event::UpdateState(block) => {
                 if let Err(err) = self.state_driver.update_state(block.hash.clone()) {
                     telemetry::error!("error updating state: {}", err);
                 } else {
-                    self.events_tx
-                        .send(Event::BuildProposalBlock(block).into())
+                    self.publish_chain_head();
+
+                    self.publish(Event::BuildProposalBlock(block).into())
                         .await
                         .map_err(|err| TheaterError::Other(err.to_string()))?;
                 }
             }
-            Event::GenesisMinerElected { genesis_receivers } => {
+            Event::GenesisMinerElected => {
                 let genesis_rewards = self
-                    .distribute_genesis_reward(genesis_receivers)
+                    .distribute_genesis_reward()
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
                 let block = self
@@ -208,8 +272,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     Event::BlockCreated(Block::Genesis { block }),
                 );
 
-                self.events_tx
-                    .send(event)
+                self.publish(event)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -219,8 +282,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
-                self.events_tx
-                    .send(Event::BroadcastProposalBlock(proposal_block).into())
+                self.publish(Event::BroadcastProposalBlock(proposal_block).into())
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -243,6 +305,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
                 let apply_result = self.handle_block_received(block)?;
+                self.publish_chain_head();
 
                 telemetry::info!(
                     "New state root hash: {}",
@@ -251,8 +314,7 @@ impl Handler<EventMessage> for NodeRuntime {
 
                 let em = EventMessage::new(Some(NETWORK_TOPIC_STR.into()), next_event);
 
-                self.events_tx
-                    .send(em)
+                self.publish(em)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -261,14 +323,22 @@ impl Handler<EventMessage> for NodeRuntime {
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
+            Event::SignatureCollectionSweepRequested => {
+                for event in self.handle_signature_collection_sweep() {
+                    let em = EventMessage::new(Some(NETWORK_TOPIC_STR.into()), event);
+
+                    self.publish(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            }
             Event::BlockCertificateCreated(certificate) => {
                 let confirmed_block = self
                     .handle_convergence_block_certificate_created(certificate)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
-                self.events_tx
-                    .send(Event::UpdateState(confirmed_block).into())
+                self.publish(Event::UpdateState(confirmed_block).into())
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -281,8 +351,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
-                self.events_tx
-                    .send(Event::UpdateState(confirmed_block).into())
+                self.publish(Event::UpdateState(confirmed_block).into())
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -300,8 +369,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     Event::BroadcastTransactionVote(vote),
                 );
 
-                self.events_tx
-                    .send(em)
+                self.publish(em)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
@@ -315,8 +383,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     Event::BroadcastTransactionVote(vote),
                 );
 
-                self.events_tx
-                    .send(em)
+                self.publish(em)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
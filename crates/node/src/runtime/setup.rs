@@ -1,11 +1,11 @@
-use events::{EventPublisher, EventRouter};
+use events::{EventAuditLog, EventPublisher, EventRouter};
 use mempool::MempoolReadHandleFactory;
 use metric_exporter::metric_factory::PrometheusFactory;
 use primitives::{JSON_RPC_API_TOPIC_STR, NETWORK_TOPIC_STR, RUNTIME_TOPIC_STR};
 use std::collections::HashMap;
 use std::sync::Arc;
-use storage::vrrbdb::VrrbDbReadHandle;
-use telemetry::info;
+use storage::vrrbdb::{StateQueryHandle, VrrbDbReadHandle, DEFAULT_STATE_QUERY_CONCURRENCY};
+use telemetry::{info, warn};
 use vrrb_config::NodeConfig;
 
 use crate::{
@@ -30,6 +30,7 @@ pub async fn setup_runtime_components(
     NodeConfig,
     VrrbDbReadHandle,
     MempoolReadHandleFactory,
+    StateQueryHandle,
 )> {
     let mut config = original_config.clone();
 
@@ -59,6 +60,7 @@ pub async fn setup_runtime_components(
 
     let mempool_read_handle_factory = handle_data.mempool_read_handle_factory;
     let state_read_handle = handle_data.state_read_handle;
+    let chain_head_read_handle = handle_data.chain_head_read_handle;
 
     runtime_manager.register_component(
         node_runtime_component_handle.label(),
@@ -93,11 +95,16 @@ pub async fn setup_runtime_components(
     config.raptorq_gossip_address = resolved_network_data.resolved_raptorq_gossip_address;
     config.kademlia_liveness_address = resolved_network_data.resolved_kademlia_liveness_address;
 
+    let state_query_handle =
+        StateQueryHandle::new(state_read_handle.clone(), DEFAULT_STATE_QUERY_CONCURRENCY);
+
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) = setup_rpc_api_server(
         &config,
         events_tx.clone(),
         state_read_handle.clone(),
+        state_query_handle.clone(),
         mempool_read_handle_factory.clone(),
+        chain_head_read_handle,
         jsonrpc_events_rx,
     )
     .await?;
@@ -119,6 +126,20 @@ pub async fn setup_runtime_components(
         // TODO: register indexer module handle
     }
 
+    if let Some(retention) = config.event_audit_log_retention {
+        std::fs::create_dir_all(config.data_dir())?;
+        let audit_events_rx = router.subscribe(None)?;
+        let audit_log =
+            EventAuditLog::with_default_variants(config.event_audit_log_path(), retention)?;
+
+        tokio::spawn(async move {
+            if let Err(err) = audit_log.run(audit_events_rx).await {
+                warn!("Event audit log stopped: {err}");
+            }
+        });
+        info!("Event audit log started");
+    }
+
     // TODO: value assigned to `node_gui_handle` is never read.
     let mut _node_gui_handle = None;
     if config.enable_ui {
@@ -131,5 +152,6 @@ pub async fn setup_runtime_components(
         config,
         state_read_handle.clone(),
         mempool_read_handle_factory.clone(),
+        state_query_handle,
     ))
 }
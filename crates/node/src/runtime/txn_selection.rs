@@ -0,0 +1,209 @@
+use vrrb_config::ProposalFairnessConfig;
+use vrrb_core::transactions::TxTimestamp;
+
+/// Picks up to `batch_size` candidates for a proposal block.
+///
+/// The share of the batch reserved by `config` goes to the oldest candidates
+/// regardless of fee, the rest is filled by fee, highest first. Ties are
+/// broken in favour of older candidates on the fee side and higher fees on
+/// the age side.
+pub fn select_with_fairness<T>(
+    candidates: Vec<T>,
+    batch_size: usize,
+    config: &ProposalFairnessConfig,
+    fee: impl Fn(&T) -> u128,
+    timestamp: impl Fn(&T) -> TxTimestamp,
+) -> Vec<T> {
+    let reserved = config.aged_slots(batch_size).min(batch_size);
+
+    let mut selected = candidates;
+    selected.sort_by(|a, b| {
+        timestamp(a)
+            .cmp(&timestamp(b))
+            .then_with(|| fee(b).cmp(&fee(a)))
+    });
+
+    let mut remaining = selected.split_off(reserved.min(selected.len()));
+    sort_by_fee(&mut remaining, &fee, &timestamp);
+
+    let open_slots = batch_size - selected.len();
+    selected.extend(remaining.into_iter().take(open_slots));
+
+    selected
+}
+
+/// Orders candidates by fee, highest first, older candidates first on ties.
+fn sort_by_fee<T>(
+    candidates: &mut [T],
+    fee: &impl Fn(&T) -> u128,
+    timestamp: &impl Fn(&T) -> TxTimestamp,
+) {
+    candidates.sort_by(|a, b| {
+        fee(b)
+            .cmp(&fee(a))
+            .then_with(|| timestamp(a).cmp(&timestamp(b)))
+    });
+}
+
+/// Gas charged for every transaction. Transactions aren't metered yet, so
+/// they all cost the same.
+pub const TXN_BASE_GAS: u64 = 21_000;
+
+/// Caps on the transactions packed into a single block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockLimits {
+    pub max_count: usize,
+    pub max_size_bytes: usize,
+    pub max_gas: u64,
+}
+
+/// Greedily fills a block with candidates, highest fee first, skipping those
+/// that would push the block over any of `limits`. Ties are broken in favour
+/// of older candidates.
+pub fn select_within_limits<T>(
+    candidates: Vec<T>,
+    limits: BlockLimits,
+    fee: impl Fn(&T) -> u128,
+    timestamp: impl Fn(&T) -> TxTimestamp,
+    size: impl Fn(&T) -> usize,
+    gas: impl Fn(&T) -> u64,
+) -> Vec<T> {
+    let mut candidates = candidates;
+    sort_by_fee(&mut candidates, &fee, &timestamp);
+
+    let mut selected = Vec::new();
+    let mut total_size = 0usize;
+    let mut total_gas = 0u64;
+
+    for candidate in candidates {
+        if selected.len() >= limits.max_count {
+            break;
+        }
+
+        let size = total_size.saturating_add(size(&candidate));
+        let gas = total_gas.saturating_add(gas(&candidate));
+
+        if size > limits.max_size_bytes || gas > limits.max_gas {
+            continue;
+        }
+
+        total_size = size;
+        total_gas = gas;
+        selected.push(candidate);
+    }
+
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (fee, timestamp)
+    type Candidate = (u128, TxTimestamp);
+
+    fn select(candidates: Vec<Candidate>, batch_size: usize, percent: u8) -> Vec<Candidate> {
+        let config = ProposalFairnessConfig {
+            aged_txn_reserve_percent: percent,
+        };
+
+        select_with_fairness(candidates, batch_size, &config, |c| c.0, |c| c.1)
+    }
+
+    #[test]
+    fn aged_low_fee_transactions_get_reserved_slots() {
+        let old_low_fee: Vec<Candidate> = (0..10).map(|ts| (1, ts)).collect();
+        let new_high_fee: Vec<Candidate> = (0..10).map(|i| (100 + i, 1_000 + i as i64)).collect();
+
+        let mut candidates = new_high_fee.clone();
+        candidates.extend(old_low_fee.clone());
+
+        let selected = select(candidates, 10, 30);
+
+        assert_eq!(selected.len(), 10);
+
+        let aged = selected.iter().filter(|c| old_low_fee.contains(c)).count();
+        assert_eq!(aged, 3);
+        assert_eq!(&selected[..3], &old_low_fee[..3]);
+
+        // the remaining slots go to the highest fees
+        let mut expected_by_fee = new_high_fee;
+        expected_by_fee.sort_by(|a, b| b.0.cmp(&a.0));
+        assert_eq!(&selected[3..], &expected_by_fee[..7]);
+    }
+
+    #[test]
+    fn zero_reserve_selects_purely_by_fee() {
+        let candidates = vec![(1, 0), (5, 10), (3, 5), (4, 1)];
+
+        let selected = select(candidates, 2, 0);
+
+        assert_eq!(selected, vec![(5, 10), (4, 1)]);
+    }
+
+    #[test]
+    fn selection_never_exceeds_available_candidates() {
+        let candidates = vec![(1, 0), (2, 1)];
+
+        let selected = select(candidates, 10, 50);
+
+        assert_eq!(selected.len(), 2);
+    }
+
+    /// (fee, timestamp, size)
+    type SizedCandidate = (u128, TxTimestamp, usize);
+
+    fn select_limited(
+        candidates: Vec<SizedCandidate>,
+        max_count: usize,
+        max_size_bytes: usize,
+    ) -> Vec<SizedCandidate> {
+        let limits = BlockLimits {
+            max_count,
+            max_size_bytes,
+            max_gas: u64::MAX,
+        };
+
+        select_within_limits(candidates, limits, |c| c.0, |c| c.1, |c| c.2, |_| 1)
+    }
+
+    #[test]
+    fn size_cap_binds_before_count_cap() {
+        let candidates = vec![(5, 0, 400), (4, 1, 400), (3, 2, 400), (2, 3, 100)];
+
+        let selected = select_limited(candidates, 10, 900);
+
+        // the third largest fee doesn't fit, the smaller last candidate does
+        assert_eq!(selected, vec![(5, 0, 400), (4, 1, 400), (2, 3, 100)]);
+    }
+
+    #[test]
+    fn count_cap_binds_before_size_cap() {
+        let candidates = vec![(1, 0, 10), (5, 1, 10), (3, 2, 10), (4, 3, 10)];
+
+        let selected = select_limited(candidates, 2, 1_000);
+
+        assert_eq!(selected, vec![(5, 1, 10), (4, 3, 10)]);
+    }
+
+    #[test]
+    fn gas_cap_bounds_the_selection() {
+        let limits = BlockLimits {
+            max_count: 10,
+            max_size_bytes: usize::MAX,
+            max_gas: 3 * TXN_BASE_GAS,
+        };
+        let candidates: Vec<SizedCandidate> = (0..5).map(|i| (i, i as i64, 1)).collect();
+
+        let selected = select_within_limits(
+            candidates,
+            limits,
+            |c| c.0,
+            |c| c.1,
+            |c| c.2,
+            |_| TXN_BASE_GAS,
+        );
+
+        assert_eq!(selected, vec![(4, 4, 1), (3, 3, 1), (2, 2, 1)]);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::HashSet,
     sync::{Arc, RwLock, RwLockReadGuard},
+    time::{Duration, Instant},
 };
 
 use block::{
@@ -16,7 +17,8 @@ use indexmap::IndexMap;
 use primitives::{HarvesterQuorumThreshold, NodeId, PublicKey, Signature, SignatureType};
 use signer::engine::{QuorumMembers, SignerEngine};
 use signer::types::{SignerError, SignerResult};
-use vrrb_core::claim::Claim;
+use vrrb_config::DEFAULT_SIGNATURE_COLLECTION_DEADLINE;
+use vrrb_core::{claim::Claim, transactions::TransactionDigest};
 
 use crate::{NodeError, Result};
 
@@ -24,6 +26,24 @@ pub type Edge = (Vertex<Block, String>, Vertex<Block, String>);
 pub type Edges = Vec<Edge>;
 pub type GraphResult<T> = std::result::Result<T, GraphError>;
 
+/// How far, in seconds, a block's timestamp may lag behind its parent's.
+/// Header timestamps have second resolution, so blocks mined within the same
+/// second as their parent share its timestamp.
+pub const BLOCK_TIMESTAMP_TOLERANCE_SECS: i64 = 0;
+
+/// Maximum number of proposal blocks kept around while waiting for the block
+/// they reference to be appended to the DAG.
+pub const MAX_ORPHAN_PROPOSALS: usize = 1024;
+
+/// Maximum number of transactions whose including block is remembered for
+/// building inclusion proofs. Transactions of the oldest blocks are forgotten
+/// first, after which proofs for them can no longer be built.
+pub const MAX_INDEXED_TXN_INCLUSIONS: usize = 100_000;
+
+/// Number of times the signature collection of a block is restarted after
+/// timing out before the block is given up on as stalled.
+pub const MAX_SIGNATURE_COLLECTION_RESTARTS: usize = 3;
+
 ///
 /// The runtime module that manages the DAG, both exposing
 /// data within and appending blocks to it.
@@ -53,10 +73,22 @@ pub struct DagModule {
     _harvester_quorum_threshold: Option<HarvesterQuorumThreshold>,
     last_confirmed_block_header: Option<BlockHeader>,
     last_confirmed_block: Option<Block>,
-    // String in next 2 fields represent the block hash
+    // String in next 3 fields represent the block hash
+    pending_genesis_blocks: IndexMap<String, GenesisBlock>,
     pending_convergence_blocks: IndexMap<String, ConvergenceBlock>,
     _pending_certificates: IndexMap<String, Certificate>,
     partial_certificate_signatures: IndexMap<String, HashSet<(NodeId, Signature)>>,
+    // When the first partial signature was received for a block
+    signature_collection_started: IndexMap<String, Instant>,
+    // How many times the signature collection of a block timed out and was
+    // restarted
+    signature_collection_restarts: IndexMap<String, usize>,
+    signature_collection_deadline: Duration,
+    // Hash of the confirmed convergence block each transaction was included
+    // in, bounded by `MAX_INDEXED_TXN_INCLUSIONS`
+    txn_inclusions: IndexMap<TransactionDigest, String>,
+    // Proposal blocks keyed by the hash of the missing block they reference
+    orphan_proposals: IndexMap<String, Vec<ProposalBlock>>,
     // TODO: Why is the Claim here?
     // TODO: Move this elsewhere, should not be in the DAG
     claim: Claim,
@@ -70,9 +102,15 @@ impl DagModule {
             _harvester_quorum_threshold: None,
             last_confirmed_block_header: None,
             last_confirmed_block: None,
+            pending_genesis_blocks: IndexMap::new(),
             pending_convergence_blocks: IndexMap::new(),
             _pending_certificates: IndexMap::new(),
             partial_certificate_signatures: IndexMap::new(),
+            signature_collection_started: IndexMap::new(),
+            signature_collection_restarts: IndexMap::new(),
+            signature_collection_deadline: DEFAULT_SIGNATURE_COLLECTION_DEADLINE,
+            txn_inclusions: IndexMap::new(),
+            orphan_proposals: IndexMap::new(),
             claim,
         }
     }
@@ -95,10 +133,80 @@ impl DagModule {
         self.last_confirmed_block_header.clone()
     }
 
+    pub fn last_confirmed_block(&self) -> Option<Block> {
+        self.last_confirmed_block.clone()
+    }
+
+    /// Returns the confirmed convergence block that included `txn_digest`.
+    pub fn including_block(&self, txn_digest: &TransactionDigest) -> Option<ConvergenceBlock> {
+        let block_hash = self.txn_inclusions.get(txn_digest)?;
+        let guard = self.read().ok()?;
+
+        match guard.get_vertex(block_hash.clone())?.get_data() {
+            Block::Convergence { block } => Some(block),
+            _ => None,
+        }
+    }
+
     pub fn set_quorum_members(&mut self, quorum_members: QuorumMembers) {
         self.quorum_members = Some(quorum_members);
     }
 
+    pub fn signature_collection_deadline(&self) -> Duration {
+        self.signature_collection_deadline
+    }
+
+    /// Sets how long a block may keep collecting partial signatures before
+    /// `check_certificate_threshold_reached` reports its certificate as timed
+    /// out.
+    pub fn set_signature_collection_deadline(&mut self, deadline: Duration) {
+        self.signature_collection_deadline = deadline;
+    }
+
+    /// Restarts the signature collection window of `block_hash`, e.g. after
+    /// re-requesting signatures from the harvesters that did not respond.
+    pub fn restart_signature_collection(&mut self, block_hash: &str) {
+        if let Some(started) = self.signature_collection_started.get_mut(block_hash) {
+            *started = Instant::now();
+            *self
+                .signature_collection_restarts
+                .entry(block_hash.to_string())
+                .or_default() += 1;
+        }
+    }
+
+    /// Returns how many times the signature collection of `block_hash` was
+    /// restarted.
+    pub fn signature_collection_restarts(&self, block_hash: &str) -> usize {
+        self.signature_collection_restarts
+            .get(block_hash)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Forgets the partial signatures collected for `block_hash`, once its
+    /// certificate formed or the block was given up on.
+    pub fn prune_signature_collection(&mut self, block_hash: &str) {
+        self.partial_certificate_signatures.remove(block_hash);
+        self.signature_collection_started.remove(block_hash);
+        self.signature_collection_restarts.remove(block_hash);
+    }
+
+    /// Returns the hashes of the blocks whose signature collection is past
+    /// its deadline without reaching the harvester threshold.
+    pub fn timed_out_signature_collections(&self, sig_engine: &SignerEngine) -> Vec<String> {
+        self.signature_collection_started
+            .keys()
+            .filter(|block_hash| {
+                matches!(
+                    self.check_certificate_threshold_reached(block_hash, sig_engine),
+                    Err(NodeError::CertificateTimeout(_))
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn get_pending_convergence_block_mut(
         &mut self,
         key: &String,
@@ -109,6 +217,7 @@ impl DagModule {
     pub fn append_certificate_to_convergence_block(
         &mut self,
         certificate: &Certificate,
+        sig_engine: &SignerEngine,
     ) -> GraphResult<Option<ConvergenceBlock>> {
         let mut block = self
             .get_pending_convergence_block_mut(&certificate.block_hash)
@@ -121,61 +230,66 @@ impl DagModule {
             .append_certificate(certificate)
             .map_err(|err| GraphError::Other(err.to_string()))?;
 
-        self.append_convergence(&block)
+        self.append_convergence(&block, sig_engine)
             .map_err(|err| GraphError::Other(format!("{:?}", err)))
     }
 
-    fn get_genesis_block(&self, block_hash: &str) -> GraphResult<GenesisBlock> {
-        let guard = self
-            .dag
-            .read()
-            .map_err(|err| GraphError::Other(format!("{err:?}")))?;
-        let block = guard
-            .get_vertex(block_hash.to_owned())
-            .ok_or_else(|| GraphError::Other("could not find genesis block in DAG".to_string()))?;
-        match block.get_data() {
-            Block::Genesis { block } => Ok(block),
-            block => Err(GraphError::Other(format!("block found in DAG for block hash \"{block_hash}\" is not a GenesisBlock, block: {block:?}"))),
-        }
-    }
-
     pub fn append_certificate_to_genesis_block(
         &mut self,
         block_hash: &str,
         certificate: &Certificate,
+        sig_engine: &SignerEngine,
     ) -> GraphResult<Option<GenesisBlock>> {
-        let mut genesis_block = self.get_genesis_block(block_hash)?;
+        let mut genesis_block = self
+            .pending_genesis_blocks
+            .get(block_hash)
+            .cloned()
+            .ok_or_else(|| GraphError::Other("unable to find pending genesis block".to_string()))?;
         genesis_block
             .append_certificate(certificate)
             .map_err(|err| GraphError::Other(format!("{err:?}")))?;
-        self.append_genesis(&genesis_block)
-            .map_err(|err| GraphError::Other(format!("{err:?}")))?;
-        Ok(Some(genesis_block))
+        self.append_genesis(&genesis_block, sig_engine)
     }
 
-    pub fn append_genesis(&mut self, genesis: &GenesisBlock) -> GraphResult<()> {
-        // TODO: re-enable checking genesis block certificates
-        //
-        // let valid = self.check_valid_genesis(genesis);
-        // if !valid {
-        //     return Err(GraphError::Other(format!(
-        //         "invalid genesis block: {}",
-        //         genesis.hash,
-        //     )));
-        // }
+    /// Appends a genesis block to the DAG once it carries a certificate
+    /// signed by the harvester quorum known to `sig_engine`. Uncertified
+    /// genesis blocks are kept pending until their certificate arrives,
+    /// while blocks whose certificate fails verification are rejected.
+    pub fn append_genesis(
+        &mut self,
+        genesis: &GenesisBlock,
+        sig_engine: &SignerEngine,
+    ) -> GraphResult<Option<GenesisBlock>> {
+        if genesis.certificate.is_none() {
+            self.pending_genesis_blocks
+                .entry(genesis.hash.clone())
+                .or_insert(genesis.clone());
+
+            return Ok(None);
+        }
+
+        if !self.check_valid_genesis(genesis, sig_engine) {
+            return Err(GraphError::Other(format!(
+                "invalid certificate for genesis block: {}",
+                genesis.hash,
+            )));
+        }
 
-        // if valid {
         let block: Block = genesis.clone().into();
         let vtx: Vertex<Block, String> = block.clone().into();
         self.write_genesis(&vtx)?;
 
         self.last_confirmed_block_header = Some(genesis.header.clone());
         self.last_confirmed_block = Some(block);
-        // }
+        self.pending_genesis_blocks.remove(&genesis.hash);
+        self.try_connect_orphans(&genesis.hash)?;
 
-        Ok(())
+        Ok(Some(genesis.clone()))
     }
 
+    /// Appends a proposal block to the DAG. If the block it references is
+    /// not in the DAG yet, the proposal is buffered as an orphan until that
+    /// block is appended, and `GraphError::NonExistentSource` is returned.
     pub fn append_proposal(
         &mut self,
         proposal: &ProposalBlock,
@@ -189,7 +303,9 @@ impl DagModule {
                 let vtx: Vertex<Block, String> = block.into();
                 let edge = (&ref_block, &vtx);
                 self.write_edge(edge)?;
+                self.try_connect_orphans(&proposal.hash)?;
             } else {
+                self.buffer_orphan(proposal.clone());
                 return Err(GraphError::NonExistentSource);
             }
         }
@@ -197,13 +313,81 @@ impl DagModule {
         Ok(())
     }
 
+    /// Appends the buffered proposals that reference `newly_added`, now that
+    /// it is part of the DAG, returning the hashes of the blocks connected.
+    pub fn try_connect_orphans(&mut self, newly_added: &str) -> GraphResult<Vec<String>> {
+        let mut connected = vec![];
+        let mut parents = vec![newly_added.to_string()];
+
+        while let Some(parent) = parents.pop() {
+            if let Some(orphans) = self.orphan_proposals.shift_remove(&parent) {
+                let ref_block = self.get_reference_block(&parent)?;
+                for orphan in orphans {
+                    let block: Block = orphan.clone().into();
+                    let vtx: Vertex<Block, String> = block.into();
+                    self.write_edge((&ref_block, &vtx))?;
+
+                    parents.push(orphan.hash.clone());
+                    connected.push(orphan.hash);
+                }
+            }
+        }
+
+        Ok(connected)
+    }
+
+    /// Number of proposal blocks waiting for the block they reference.
+    pub fn orphan_count(&self) -> usize {
+        self.orphan_proposals.values().map(Vec::len).sum()
+    }
+
+    fn buffer_orphan(&mut self, proposal: ProposalBlock) {
+        let already_buffered = self
+            .orphan_proposals
+            .get(&proposal.ref_block)
+            .map(|orphans| orphans.iter().any(|orphan| orphan.hash == proposal.hash))
+            .unwrap_or(false);
+
+        if already_buffered {
+            return;
+        }
+
+        // evict the oldest orphans first to keep the pool bounded
+        while self.orphan_count() >= MAX_ORPHAN_PROPOSALS {
+            let Some((_, oldest)) = self.orphan_proposals.get_index_mut(0) else {
+                break;
+            };
+            oldest.remove(0);
+            if oldest.is_empty() {
+                self.orphan_proposals.shift_remove_index(0);
+            }
+        }
+
+        self.orphan_proposals
+            .entry(proposal.ref_block.clone())
+            .or_default()
+            .push(proposal);
+    }
+
+    /// Appends a convergence block to the DAG once it carries a certificate
+    /// signed by the harvester quorum known to `sig_engine`. Uncertified
+    /// blocks are kept pending until their certificate arrives, while blocks
+    /// whose certificate fails verification are rejected.
     pub fn append_convergence(
         &mut self,
         convergence: &ConvergenceBlock,
+        sig_engine: &SignerEngine,
     ) -> GraphResult<Option<ConvergenceBlock>> {
-        let valid = self.check_valid_convergence(convergence);
+        self.check_timestamp_after_parent(&convergence.header)?;
+
+        if convergence.certificate.is_some() {
+            if !self.check_valid_convergence(convergence, sig_engine) {
+                return Err(GraphError::Other(format!(
+                    "invalid certificate for convergence block: {}",
+                    convergence.hash,
+                )));
+            }
 
-        if valid {
             let ref_blocks: Vec<Vertex<Block, String>> =
                 self.get_convergence_reference_blocks(convergence);
             //dbg!(&ref_blocks);
@@ -215,17 +399,28 @@ impl DagModule {
                 .map(|ref_block| (ref_block.clone(), vtx.clone()))
                 .collect();
             self.extend_edges(edges)?;
+            self.try_connect_orphans(&convergence.hash)?;
 
             self.last_confirmed_block_header = Some(convergence.header.clone());
             self.last_confirmed_block = Some(Block::Convergence {
                 block: convergence.clone(),
             });
 
-            self.pending_convergence_blocks
-                .remove(&convergence.hash)
-                .ok_or(GraphError::Other(
-                    "unable to find pending convergence block".to_string(),
-                ))?;
+            for digest in convergence.txns.values().flatten() {
+                self.txn_inclusions
+                    .insert(digest.clone(), convergence.hash.clone());
+            }
+
+            // forget the transactions of the oldest blocks first
+            let excess = self
+                .txn_inclusions
+                .len()
+                .saturating_sub(MAX_INDEXED_TXN_INCLUSIONS);
+            self.txn_inclusions.drain(..excess);
+
+            // NOTE: blocks received along with their certificate were never pending
+            self.pending_convergence_blocks.remove(&convergence.hash);
+            self.prune_signature_collection(&convergence.hash);
 
             return Ok(Some(convergence.clone()));
         } else {
@@ -304,29 +499,62 @@ impl DagModule {
         Err(GraphError::Other("Error getting write guard".to_string()))
     }
 
-    fn check_valid_proposal(&self, block: &ProposalBlock, _sig_engine: SignerEngine) -> bool {
-        if let Ok(_validation_data) = block.get_validation_data() {
-            todo!();
+    /// Checks that a genesis block carries a certificate for its own hash
+    /// that `verify_certificate` accepts.
+    pub fn check_valid_genesis(&self, genesis: &GenesisBlock, sig_engine: &SignerEngine) -> bool {
+        match &genesis.certificate {
+            Some(certificate) if certificate.block_hash == genesis.hash => self
+                .verify_certificate(certificate, sig_engine)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    fn check_valid_proposal(&self, block: &ProposalBlock, sig_engine: SignerEngine) -> bool {
+        if let Ok(validation_data) = block.get_validation_data() {
+            sig_engine
+                .verify_batch(&validation_data.signatures, &validation_data.payload_hash)
+                .is_ok()
         } else {
             false
         }
     }
 
+    /// Rejects blocks whose header timestamp precedes the timestamp of the
+    /// last confirmed block by more than `BLOCK_TIMESTAMP_TOLERANCE_SECS`.
+    fn check_timestamp_after_parent(&self, header: &BlockHeader) -> GraphResult<()> {
+        if let Some(parent) = &self.last_confirmed_block_header {
+            if header.timestamp + BLOCK_TIMESTAMP_TOLERANCE_SECS < parent.timestamp {
+                return Err(GraphError::Other(format!(
+                    "block timestamp {} precedes parent block timestamp {}",
+                    header.timestamp, parent.timestamp
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     //TODO: Refactor to return ConvergenceBlockStatus Enum as Pending
     // or Confirmed variant
-    fn check_valid_convergence(&mut self, block: &ConvergenceBlock) -> bool {
-        if let Some(_certificate) = &block.certificate {
-            //TODO: Remove this as it is redundant...
-            //match self.verify_certificate(certificate) {
-            //Ok(true) => return true,
-            //Ok(false) => return false,
-            //Err(_) => return false,
-            //}
-            return true;
+    fn check_valid_convergence(&self, block: &ConvergenceBlock, sig_engine: &SignerEngine) -> bool {
+        match &block.certificate {
+            Some(certificate) if certificate.block_hash == block.hash => self
+                .verify_certificate(certificate, sig_engine)
+                .unwrap_or(false),
+            _ => false,
         }
-        false
     }
 
+    /// Records the partial signature of `node_id` over `block_hash` and
+    /// returns the signatures collected so far once they reach the harvester
+    /// threshold.
+    ///
+    /// Receiving the same signature twice is harmless, but a node that
+    /// already signed the block can't add a different signature, so that
+    /// each harvester counts once towards the threshold. Signatures arriving
+    /// after the block was certified and appended to the DAG are rejected,
+    /// so they don't start a new collection.
     pub fn add_signer_to_block(
         &mut self,
         block_hash: String,
@@ -334,11 +562,29 @@ impl DagModule {
         node_id: NodeId,
         sig_engine: &SignerEngine,
     ) -> Result<HashSet<(NodeId, Signature)>> {
+        if self.read()?.get_vertex(block_hash.clone()).is_some() {
+            return Err(NodeError::Other(format!(
+                "block {block_hash} is already certified"
+            )));
+        }
+
         match self
             .partial_certificate_signatures
             .entry(block_hash.clone())
         {
             indexmap::map::Entry::Occupied(mut entry) => {
+                let conflicting = entry
+                    .get()
+                    .iter()
+                    .any(|(signer, signature)| signer == &node_id && signature != &sig);
+
+                if conflicting {
+                    return Err(NodeError::ConflictingSignature {
+                        node_id,
+                        block_hash,
+                    });
+                }
+
                 entry.get_mut().insert((node_id, sig));
             }
             indexmap::map::Entry::Vacant(entry) => {
@@ -347,9 +593,16 @@ impl DagModule {
                 entry.insert(set);
             }
         }
+        self.signature_collection_started
+            .entry(block_hash.clone())
+            .or_insert_with(Instant::now);
         self.check_certificate_threshold_reached(&block_hash, sig_engine)
     }
 
+    /// Returns the partial signatures collected for `block_hash` once they
+    /// reach the harvester threshold. If the threshold is still not reached
+    /// after the signature collection deadline, a `CertificateTimeout` is
+    /// returned so the caller can escalate.
     pub fn check_certificate_threshold_reached(
         &self,
         block_hash: &String,
@@ -361,9 +614,35 @@ impl DagModule {
             }
         }
 
+        if let Some(started) = self.signature_collection_started.get(block_hash) {
+            if started.elapsed() > self.signature_collection_deadline {
+                return Err(NodeError::CertificateTimeout(block_hash.clone()));
+            }
+        }
+
         Err(NodeError::Other("threshold not reached".to_string()))
     }
 
+    /// Independently checks a fully-formed certificate, e.g. one attached to
+    /// a block received from the network: it must carry signatures over the
+    /// block hash from at least a threshold of distinct harvester quorum
+    /// members, all of which have to be valid.
+    pub fn verify_certificate(
+        &self,
+        cert: &Certificate,
+        sig_engine: &SignerEngine,
+    ) -> Result<bool> {
+        if sig_engine.quorum_members().get_harvester_data().is_none() {
+            return Err(NodeError::Other(
+                "harvester quorum members are unknown".to_string(),
+            ));
+        }
+
+        Ok(sig_engine
+            .verify_harvester_quorum_signatures(&cert.signatures, &cert.block_hash)
+            .is_ok())
+    }
+
     fn _verify_certificate_signature(
         &self,
         signature: &mut Vec<(NodeId, Signature)>,
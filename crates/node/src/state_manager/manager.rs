@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    path::Path,
     sync::{Arc, RwLock},
 };
 
@@ -13,16 +14,22 @@ use bulldag::{
 use ethereum_types::U256;
 use events::Event;
 use mempool::{LeftRightMempool, MempoolReadHandleFactory};
-use primitives::{Address, NodeId, Round};
+use primitives::{Address, NodeId, Round, Signature};
+use ritelinked::LinkedHashMap;
 use signer::engine::{QuorumMembers, SignerEngine};
-use storage::vrrbdb::{types::*, ApplyBlockResult};
+use storage::vrrbdb::{types::*, ApplyBlockResult, VrrbDbCheckpoint, VrrbDbConfig, VrrbDbSnapshot};
 use storage::{
     storage_utils::StorageError,
     vrrbdb::{Claims, VrrbDb, VrrbDbReadHandle},
 };
 use telemetry::info;
 use theater::{ActorId, ActorState};
-use vrrb_core::{account::Account, claim::Claim};
+use vrrb_config::MempoolPressureConfig;
+use vrrb_core::{
+    account::Account,
+    claim::Claim,
+    staking::{Stake, StakeUpdate},
+};
 use vrrb_core::{
     account::UpdateArgs,
     transactions::{Transaction, TransactionDigest, TransactionKind},
@@ -36,6 +43,23 @@ use super::{
     DagModule, GraphResult,
 };
 
+/// Number of recent blocks `StateManager::rollback_to` can revert to.
+pub const MAX_ROLLBACK_DEPTH: usize = 64;
+
+/// What became of a transaction when the block including it was applied by
+/// `StateManager::update_state`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    /// The transaction's updates were written to state.
+    Applied,
+    /// The transaction was already confirmed, or appeared earlier in the
+    /// same round, so it was left out.
+    SkippedDuplicate,
+    /// The transaction couldn't be applied against current state and was left
+    /// out, for the given reason.
+    Failed(String),
+}
+
 /// Provides a convenient configuration struct for building a
 /// StateManager
 #[derive(Debug, Clone)]
@@ -53,6 +77,9 @@ pub struct StateManager {
     pub(crate) dag: DagModule,
     pub(crate) database: VrrbDb,
     pub(crate) mempool: LeftRightMempool,
+    mempool_pressure: MempoolPressureConfig,
+    mempool_under_pressure: bool,
+    checkpoints: LinkedHashMap<BlockHash, VrrbDbCheckpoint>,
 }
 
 impl StateManager {
@@ -65,25 +92,36 @@ impl StateManager {
             _status: ActorState::Stopped,
             dag: dag_module,
             mempool: config.mempool,
+            mempool_pressure: MempoolPressureConfig::default(),
+            mempool_under_pressure: false,
+            checkpoints: LinkedHashMap::new(),
         }
     }
 
+    /// Appends a genesis block to the DAG and applies it to state once it
+    /// is confirmed. Returns `None` while the block awaits its certificate.
     pub fn append_genesis(
         &mut self,
         genesis_block: &GenesisBlock,
-    ) -> GraphResult<ApplyBlockResult> {
-        self.dag.append_genesis(genesis_block)?;
-        self.apply_block(Block::Genesis {
-            block: genesis_block.to_owned(),
-        })
-        .map_err(|err| GraphError::Other(format!("{err:?}")))
+        sig_engine: &SignerEngine,
+    ) -> GraphResult<Option<ApplyBlockResult>> {
+        match self.dag.append_genesis(genesis_block, sig_engine)? {
+            Some(genesis) => self.apply_genesis_block(genesis).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn apply_genesis_block(&mut self, genesis: GenesisBlock) -> GraphResult<ApplyBlockResult> {
+        self.apply_block(Block::Genesis { block: genesis })
+            .map_err(|err| GraphError::Other(format!("{err:?}")))
     }
 
     pub fn append_convergence(
         &mut self,
         convergence: &ConvergenceBlock,
+        sig_engine: &SignerEngine,
     ) -> GraphResult<ApplyBlockResult> {
-        let opt = self.dag.append_convergence(convergence)?;
+        let opt = self.dag.append_convergence(convergence, sig_engine)?;
         if let Some(cblock) = opt {
             let ref_blocks = self.dag.get_convergence_reference_blocks(convergence);
             let proposals: Vec<ProposalBlock> = ref_blocks
@@ -112,30 +150,120 @@ impl StateManager {
             .database
             .apply_convergence_block(convergence, proposals)
             .map_err(|err| GraphError::Other(err.to_string()))?;
+
+        self.record_checkpoint(convergence.hash.clone());
+
         Ok(res)
     }
 
+    /// Remembers the current versions of the StateStore, TransactionStore
+    /// and ClaimStore as the state right after `block_hash` was applied, so
+    /// that it can later be restored by `rollback_to`. Only the
+    /// `MAX_ROLLBACK_DEPTH` most recent blocks are kept.
+    pub(crate) fn record_checkpoint(&mut self, block_hash: BlockHash) {
+        let checkpoint = self.database.checkpoint();
+        self.checkpoints.remove(&block_hash);
+        self.checkpoints.insert(block_hash, checkpoint);
+
+        while self.checkpoints.len() > MAX_ROLLBACK_DEPTH {
+            self.checkpoints.pop_front();
+        }
+    }
+
+    /// Reverts the StateStore, TransactionStore and ClaimStore to the state
+    /// they were in right after the block `block_hash` was applied, e.g. when
+    /// the blocks applied since then were part of an abandoned fork.
+    ///
+    /// Either all three stores are reverted or none of them is. The
+    /// checkpoints of the blocks applied after `block_hash` are discarded.
+    pub fn rollback_to(&mut self, block_hash: &BlockHash) -> Result<()> {
+        let checkpoint = *self.checkpoints.get(block_hash).ok_or_else(|| {
+            NodeError::Other(format!("no checkpoint recorded for block {block_hash}"))
+        })?;
+
+        self.database.rollback_to(&checkpoint)?;
+
+        while let Some((hash, _)) = self.checkpoints.back() {
+            if hash == block_hash {
+                break;
+            }
+            self.checkpoints.pop_back();
+        }
+
+        Ok(())
+    }
+
     pub fn append_certificate_to_convergence_block(
         &mut self,
         certificate: &Certificate,
+        sig_engine: &SignerEngine,
     ) -> GraphResult<Option<ConvergenceBlock>> {
         self.dag
-            .append_certificate_to_convergence_block(certificate)
+            .append_certificate_to_convergence_block(certificate, sig_engine)
     }
 
     pub fn append_certificate_to_genesis_block(
         &mut self,
         block_hash: &str,
         certificate: &Certificate,
+        sig_engine: &SignerEngine,
     ) -> GraphResult<Option<GenesisBlock>> {
-        self.dag
-            .append_certificate_to_genesis_block(block_hash, certificate)
+        let opt =
+            self.dag
+                .append_certificate_to_genesis_block(block_hash, certificate, sig_engine)?;
+        if let Some(genesis) = &opt {
+            self.apply_genesis_block(genesis.clone())?;
+        }
+
+        Ok(opt)
     }
 
     pub fn export_state(&self) {
         self.database.export_state();
     }
 
+    /// Writes a snapshot of the StateStore, TransactionStore and ClaimStore,
+    /// along with their root hashes, to `path`.
+    pub fn export_snapshot(&self, path: &Path) -> Result<()> {
+        let snapshot = self.database.snapshot()?;
+        let bytes = bincode::serialize(&snapshot)
+            .map_err(|err| NodeError::Other(format!("failed to serialize snapshot: {err}")))?;
+
+        std::fs::write(path, bytes)?;
+
+        Ok(())
+    }
+
+    /// Loads a snapshot written by `export_snapshot` into a new database at
+    /// the location described by `db_config` and switches over to it once
+    /// its root hashes check out and its state root matches
+    /// `expected_state_root_hash`. On failure the current database is left
+    /// untouched.
+    ///
+    /// Read handles obtained before the import keep pointing to the previous
+    /// database.
+    pub fn import_snapshot(
+        &mut self,
+        path: &Path,
+        db_config: VrrbDbConfig,
+        expected_state_root_hash: &str,
+    ) -> Result<()> {
+        let bytes = std::fs::read(path)?;
+        let snapshot: VrrbDbSnapshot = bincode::deserialize(&bytes)
+            .map_err(|err| NodeError::Other(format!("failed to deserialize snapshot: {err}")))?;
+
+        if snapshot.state_root_hash != expected_state_root_hash {
+            return Err(NodeError::Other(format!(
+                "snapshot state root hash {} does not match expected {expected_state_root_hash}",
+                snapshot.state_root_hash
+            )));
+        }
+
+        self.database = VrrbDb::from_snapshot(db_config, snapshot)?;
+
+        Ok(())
+    }
+
     /// Produces the read handle for the VrrbDb instance in this
     /// struct. VrrbDbReadHandle provides a ReadHandleFactory for
     /// each of the StateStore, TransactionStore and ClaimStore.
@@ -194,29 +322,136 @@ impl StateManager {
     /// Given the hash of a `ConvergenceBlock` this method
     /// updates the StateStore, ClaimStore and TransactionStore
     /// for all new claims and transactions (excluding
-    /// ClaimStaking transactions currently).
-    pub fn update_state(&mut self, block_hash: BlockHash) -> Result<()> {
-        if let Some(mut round_blocks) = self.get_proposal_blocks(block_hash) {
-            let update_list = self.get_update_list(&mut round_blocks);
-            let update_args = get_update_args(update_list);
-            let consolidated_update_args = consolidate_update_args(update_args);
-            consolidated_update_args.into_iter().for_each(|(_, args)| {
-                if let Err(err) = self.database.update_account(args) {
-                    telemetry::error!("error updating account: {err}");
-                }
-            });
+    /// ClaimStaking transactions currently), and reports what became of each
+    /// transaction the block included. Transactions that were already
+    /// confirmed, or that can't be applied against current state, are left
+    /// out while the rest of the block is applied.
+    ///
+    /// The block is applied atomically: if any account update fails, the
+    /// StateStore is rolled back and nothing is written.
+    pub fn update_state(
+        &mut self,
+        block_hash: BlockHash,
+    ) -> Result<Vec<(TransactionDigest, ApplyOutcome)>> {
+        let mut round_blocks = self
+            .get_proposal_blocks(block_hash)
+            .ok_or_else(|| NodeError::Other("Convergence block not found in DAG".to_string()))?;
+        let (consolidated_update_args, outcomes) = self.round_state_diff(&mut round_blocks)?;
+
+        let checkpoint = self.database.checkpoint();
+        for (_, args) in consolidated_update_args {
+            if let Err(err) = self.database.update_account(args) {
+                self.database.rollback_to(&checkpoint)?;
+                return Err(err.into());
+            }
+        }
 
-            let proposals = round_blocks.proposals.clone();
+        let proposals = round_blocks.proposals.clone();
 
-            self.update_txn_trie(&proposals);
-            self.update_claim_store(&proposals);
+        self.update_txn_trie(&proposals);
+        self.update_claim_store(&proposals);
 
-            return Ok(());
+        Ok(outcomes)
+    }
+
+    /// Drops the transactions of the round that were already confirmed,
+    /// appear more than once, or can't be applied against current state,
+    /// returning the outcome of every transaction of the round.
+    fn retain_applicable_txns(
+        &self,
+        round_blocks: &mut RoundBlocks,
+    ) -> Vec<(TransactionDigest, ApplyOutcome)> {
+        let reader = self.database.read_handle();
+        let mut seen = HashSet::new();
+        let mut spent: HashMap<Address, u128> = HashMap::new();
+        let mut outcomes = Vec::new();
+
+        for proposal in round_blocks.proposals.iter_mut() {
+            proposal.txns.retain(|digest, txn| {
+                let confirmed = reader.get_transaction(digest).is_ok();
+                let outcome = if confirmed || !seen.insert(digest.clone()) {
+                    ApplyOutcome::SkippedDuplicate
+                } else {
+                    match Self::check_txn(&reader, &mut spent, txn) {
+                        Ok(()) => ApplyOutcome::Applied,
+                        Err(reason) => ApplyOutcome::Failed(reason),
+                    }
+                };
+
+                let applied = outcome == ApplyOutcome::Applied;
+                outcomes.push((digest.clone(), outcome));
+                applied
+            });
         }
 
-        Err(NodeError::Other(
-            "Convergene block not found in DAG".to_string(),
-        ))
+        outcomes
+    }
+
+    /// Checks that `txn` can be applied on top of current state and of the
+    /// transactions of the round accepted so far, whose spending is tracked
+    /// in `spent`.
+    fn check_txn(
+        reader: &VrrbDbReadHandle,
+        spent: &mut HashMap<Address, u128>,
+        txn: &TransactionKind,
+    ) -> std::result::Result<(), String> {
+        let sender = txn.sender_address();
+        let sender_account = reader
+            .get_account_by_address(&sender)
+            .map_err(|_| format!("sender {sender} not found"))?;
+
+        let receiver = txn.receiver_address();
+        reader
+            .get_account_by_address(&receiver)
+            .map_err(|_| format!("receiver {receiver} not found"))?;
+
+        let spent_by_sender = spent.entry(sender.clone()).or_default();
+        let balance = sender_account
+            .credits()
+            .saturating_sub(sender_account.debits())
+            .saturating_sub(*spent_by_sender);
+
+        if txn.amount() > balance {
+            return Err(format!("sender {sender} has insufficient balance"));
+        }
+
+        *spent_by_sender += txn.amount();
+
+        Ok(())
+    }
+
+    /// Given the hash of a `ConvergenceBlock` this method returns the
+    /// consolidated account updates that `update_state` would write to the
+    /// StateStore for that block, without applying them.
+    pub fn compute_state_diff(
+        &self,
+        block_hash: BlockHash,
+    ) -> Result<HashMap<Address, UpdateArgs>> {
+        let mut round_blocks = self
+            .get_proposal_blocks(block_hash)
+            .ok_or_else(|| NodeError::Other("Convergence block not found in DAG".to_string()))?;
+
+        self.round_state_diff(&mut round_blocks)
+            .map(|(diff, _)| diff)
+    }
+
+    /// Consolidates the state updates of the transactions included by the
+    /// round's `ConvergenceBlock` that can be applied into a single
+    /// `UpdateArgs` per address, along with the outcome of every transaction.
+    fn round_state_diff(
+        &self,
+        round_blocks: &mut RoundBlocks,
+    ) -> Result<(
+        HashMap<Address, UpdateArgs>,
+        Vec<(TransactionDigest, ApplyOutcome)>,
+    )> {
+        Self::retain_converged_txns(round_blocks);
+        let outcomes = self.retain_applicable_txns(round_blocks);
+
+        let update_list = self.get_update_list(round_blocks);
+        let update_args = get_update_args(update_list);
+
+        Ok((consolidate_update_args(update_args)?, outcomes))
     }
 
     /// Provided a reference to an array of `ProposalBlock`s
@@ -263,22 +498,10 @@ impl StateManager {
 
     /// Provides a method to convert a `RoundBlocks` wrapper struct into
     /// a HashSet of unique `StateUpdate`s
-    fn get_update_list(&self, round_blocks: &mut RoundBlocks) -> HashSet<StateUpdate> {
-        let convergence = round_blocks.convergence.clone();
-        let filtered_proposals: Vec<ProposalBlock> = round_blocks
-            .proposals
-            .iter_mut()
-            .map(|block| {
-                if let Some(digests) = convergence.txns.get(&block.hash) {
-                    block.txns.retain(|digest, _| digests.contains(digest))
-                }
-                block.clone()
-            })
-            .collect();
-
+    fn get_update_list(&self, round_blocks: &RoundBlocks) -> HashSet<StateUpdate> {
         let mut updates: HashSet<StateUpdate> = HashSet::new();
 
-        filtered_proposals.iter().for_each(|block| {
+        round_blocks.proposals.iter().for_each(|block| {
             let subset = HashSet::from_block(block.clone());
             updates.extend(subset);
         });
@@ -286,6 +509,17 @@ impl StateManager {
         updates
     }
 
+    /// Drops the transactions of the round's `ProposalBlock`s that the
+    /// `ConvergenceBlock` didn't include.
+    fn retain_converged_txns(round_blocks: &mut RoundBlocks) {
+        let convergence = &round_blocks.convergence;
+        round_blocks.proposals.iter_mut().for_each(|block| {
+            if let Some(digests) = convergence.txns.get(&block.hash) {
+                block.txns.retain(|digest, _| digests.contains(digest))
+            }
+        });
+    }
+
     /// Inserts an account into the `VrrbDb` `StateStore`. This method Should
     /// only be used for *new* accounts
     pub fn insert_account(&mut self, key: Address, account: Account) -> Result<()> {
@@ -366,7 +600,7 @@ impl StateManager {
     ) -> Result<Event> {
         match block {
             Block::Genesis { ref mut block } => {
-                if let Err(e) = self.dag.append_genesis(block) {
+                if let Err(e) = self.dag.append_genesis(block, &sig_engine) {
                     let err_note = format!("Encountered GraphError: {e:?}");
                     return Err(NodeError::Other(err_note));
                 };
@@ -378,7 +612,7 @@ impl StateManager {
                 }
             }
             Block::Convergence { ref mut block } => {
-                if let Err(e) = self.dag.append_convergence(block) {
+                if let Err(e) = self.dag.append_convergence(block, &sig_engine) {
                     let err_note = format!("Encountered GraphError: {e:?}");
                     return Err(NodeError::Other(err_note));
                 }
@@ -433,6 +667,39 @@ impl StateManager {
         self.mempool.len()
     }
 
+    pub fn set_mempool_pressure_config(&mut self, config: MempoolPressureConfig) {
+        self.mempool_pressure = config;
+    }
+
+    pub fn is_mempool_under_pressure(&self) -> bool {
+        self.mempool_under_pressure
+    }
+
+    /// Compares the mempool occupancy against the configured watermarks,
+    /// returning the event to broadcast when the mempool just grew past the
+    /// high watermark or just shrank below the low one. `cutoff` is the last
+    /// transaction inserted into the mempool.
+    pub fn check_mempool_pressure(&mut self, cutoff: &TransactionDigest) -> Option<Event> {
+        let occupancy = self.mempool_len();
+
+        if !self.mempool_under_pressure && occupancy > self.mempool_pressure.high_watermark {
+            self.mempool_under_pressure = true;
+
+            return Some(Event::MempoolPressureReached {
+                occupancy,
+                cutoff: cutoff.clone(),
+            });
+        }
+
+        if self.mempool_under_pressure && occupancy < self.mempool_pressure.low_watermark {
+            self.mempool_under_pressure = false;
+
+            return Some(Event::MempoolPressureRelieved { occupancy });
+        }
+
+        None
+    }
+
     pub async fn handle_transaction_validated(&mut self, txn: TransactionKind) -> Result<()> {
         self.mempool
             .remove(&txn.id())
@@ -496,6 +763,99 @@ impl StateManager {
         Ok(())
     }
 
+    /// Applies a certified stake deposit: the staked amount is debited from
+    /// the staker's account, the stake's digest is recorded among the
+    /// account's stake digests and the stake is added to the staker's claim
+    /// in the ClaimStore.
+    ///
+    /// The stake must be signed with the key of the account it debits, and
+    /// its certificate must be backed by `farmer_signatures`, signatures of
+    /// the stake's payload by a Farmer quorum known to `sig_engine`.
+    ///
+    /// Fails without touching state if the stake isn't authorized, if the
+    /// staker can't cover the stake, or if the stake was already applied.
+    pub fn stake_claim(
+        &mut self,
+        stake: Stake,
+        farmer_signatures: &[(NodeId, Signature)],
+        sig_engine: &SignerEngine,
+    ) -> Result<()> {
+        let StakeUpdate::Add(amount) = stake.get_amount() else {
+            return Err(NodeError::Other(
+                "only stake deposits can be applied to a claim".to_string(),
+            ));
+        };
+
+        let staker = stake.get_sender();
+        let digest = stake.digest();
+
+        stake
+            .verify()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if Address::new(stake.get_pubkey()) != staker {
+            return Err(NodeError::UnauthorizedStake(digest));
+        }
+
+        let payload = stake.get_payload();
+        let certified = stake
+            .get_certificate()
+            .is_some_and(|(_, payload_hash)| payload_hash == payload)
+            && sig_engine
+                .verify_farmer_quorum_signatures(farmer_signatures, &payload)
+                .is_ok();
+        if !certified {
+            return Err(NodeError::UncertifiedStake(digest));
+        }
+
+        let account = self.get_account(&staker)?;
+
+        if account.digests().get_stake().contains(&digest) {
+            return Err(NodeError::StakeAlreadyApplied(digest));
+        }
+
+        if account.credits().saturating_sub(account.debits()) < amount {
+            return Err(NodeError::InsufficientBalance(staker));
+        }
+
+        let mut claim = self
+            .get_claims_by_account_address(&stake.get_receiver())?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                NodeError::Other(format!("no claim found for {}", stake.get_receiver()))
+            })?;
+
+        claim
+            .update_stake(stake)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        // NOTE: the debit is reverted if the claim can't be written, so that
+        // a stake is either applied in full or not at all
+        let checkpoint = self.database.checkpoint();
+
+        self.update_account(
+            StateUpdate {
+                address: staker,
+                token: None,
+                amount,
+                nonce: None,
+                storage: None,
+                package_address: None,
+                digest,
+                update_account: UpdateAccount::Claim,
+            }
+            .into(),
+        )?;
+
+        if let Err(err) = self.database.insert_claim(claim) {
+            self.database.rollback_to(&checkpoint)?;
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
     pub fn dag(&self) -> Arc<RwLock<BullDag<Block, String>>> {
         self.dag.dag().clone()
     }
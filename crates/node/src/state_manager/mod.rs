@@ -23,14 +23,17 @@ mod tests {
     use signer::engine::SignerEngine;
 
     use storage::storage_utils::remove_vrrb_data_dir;
-    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+    use storage::vrrbdb::{
+        StateQueryHandle, VrrbDb, VrrbDbConfig, DEFAULT_STATE_QUERY_CONCURRENCY,
+    };
 
     use vrrb_core::transactions::TransactionKind;
     use vrrb_core::{account::Account, claim::Claim, keypair::KeyPair};
 
     use super::*;
     use crate::test_utils::{
-        create_keypair, produce_accounts, produce_convergence_block, produce_genesis_block,
+        create_keypair, create_sig_engine, create_sig_engine_for, create_sig_engines,
+        produce_accounts, produce_convergence_block, produce_genesis_block,
         produce_proposal_blocks,
     };
 
@@ -79,10 +82,7 @@ mod tests {
         let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
 
         let keypair = KeyPair::random();
-        let sig_engine = SignerEngine::new(
-            *keypair.get_miner_public_key(),
-            *keypair.get_miner_secret_key(),
-        );
+        let sig_engine = create_sig_engine_for(&keypair);
         let pk = *keypair.get_miner_public_key();
         let addr = create_address(&pk);
         let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
@@ -148,4 +148,1008 @@ mod tests {
             assert_eq!(digests.get_stake().len(), 0);
         }
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn state_diff_matches_post_apply_state() {
+        let db_config = VrrbDbConfig::default().with_path(std::env::temp_dir().join("db"));
+        let db = VrrbDb::new(db_config);
+        let mempool = LeftRightMempool::default();
+
+        let accounts: Vec<(Address, Option<Account>)> = produce_accounts(5);
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+
+        let keypair = KeyPair::random();
+        let sig_engine = create_sig_engine_for(&keypair);
+        let pk = *keypair.get_miner_public_key();
+        let addr = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let signature = Claim::signature_for_valid_claim(
+            pk,
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let claim = create_claim(&pk, &addr, ip_address, signature);
+
+        let mut state_module = StateManager::new(StateManagerConfig {
+            mempool,
+            database: db,
+            claim,
+            dag: dag.clone(),
+        });
+        state_module.extend_accounts(accounts.clone()).unwrap();
+
+        let genesis = produce_genesis_block();
+        let gblock: Block = genesis.clone().into();
+        let gvtx: Vertex<Block, BlockHash> = gblock.into();
+        if let Ok(mut guard) = dag.write() {
+            guard.add_vertex(&gvtx);
+        }
+
+        let proposals = produce_proposal_blocks(genesis.hash, accounts.clone(), 5, 5, sig_engine);
+        if let Ok(mut guard) = dag.write() {
+            proposals.into_iter().for_each(|pblock| {
+                let pblock: Block = pblock.into();
+                let pvtx: Vertex<Block, BlockHash> = pblock.into();
+                guard.add_edge(&(&gvtx, &pvtx));
+            });
+        }
+
+        let block_hash = produce_convergence_block(dag).unwrap();
+
+        let diff = state_module.compute_state_diff(block_hash.clone()).unwrap();
+
+        // computing the diff must not touch the state store
+        let store = state_module.read_handle().state_store_values().unwrap();
+        for (address, _) in accounts.iter() {
+            if let Some(account) = store.get(address) {
+                assert!(account.digests().get_sent().is_empty());
+            }
+        }
+
+        state_module.update_state(block_hash).unwrap();
+        state_module.commit();
+
+        let store = state_module.read_handle().state_store_values().unwrap();
+        for (address, _) in accounts.iter() {
+            let account = store.get(address).unwrap();
+            let digests = diff.get(address).unwrap().digests.clone().unwrap();
+
+            assert_eq!(account.digests().get_sent(), digests.get_sent());
+            assert_eq!(account.digests().get_recv(), digests.get_recv());
+        }
+    }
+
+    fn state_manager_at(path: std::path::PathBuf) -> StateManager {
+        let db = VrrbDb::new(VrrbDbConfig::default().with_path(path));
+        let (sk, pk) = create_keypair();
+        let addr = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let signature =
+            Claim::signature_for_valid_claim(pk, ip_address, sk.secret_bytes().to_vec()).unwrap();
+        let claim = create_claim(&pk, &addr, ip_address, signature);
+
+        StateManager::new(StateManagerConfig {
+            mempool: LeftRightMempool::default(),
+            database: db,
+            dag: Arc::new(RwLock::new(BullDag::new())),
+            claim,
+        })
+    }
+
+    #[test]
+    #[serial]
+    fn state_snapshot_round_trips_between_databases() {
+        let temp_dir = env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let snapshot_path = env::temp_dir().join(format!("{}.snapshot", uuid::Uuid::new_v4()));
+
+        let mut source = state_manager_at(temp_dir.join("source"));
+        let accounts = produce_accounts(5);
+        source.extend_accounts(accounts.clone()).unwrap();
+        source.commit();
+
+        source.export_snapshot(&snapshot_path).unwrap();
+        let state_root_hash = source.state_root_hash().unwrap();
+
+        let mut target = state_manager_at(temp_dir.join("target"));
+        target
+            .import_snapshot(
+                &snapshot_path,
+                VrrbDbConfig::default().with_path(temp_dir.join("imported")),
+                &state_root_hash,
+            )
+            .unwrap();
+
+        assert_eq!(target.state_root_hash().unwrap(), state_root_hash);
+        for (address, account) in accounts {
+            assert_eq!(target.get_account(&address).unwrap(), account.unwrap());
+        }
+
+        std::fs::remove_file(snapshot_path).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn rollback_restores_state_of_earlier_block() {
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+
+        let accounts = produce_accounts(3);
+        state_module.extend_accounts(accounts.clone()).unwrap();
+        state_module.commit();
+        state_module.record_checkpoint("block-1".to_string());
+
+        let (address, account) = accounts[0].clone();
+        let mut credited = account.clone().unwrap();
+        credited.set_credits(credited.credits() + 500);
+        let (new_address, new_account) = produce_accounts(1).remove(0);
+        state_module
+            .extend_accounts(vec![
+                (address.clone(), Some(credited.clone())),
+                (new_address.clone(), new_account),
+            ])
+            .unwrap();
+        state_module.commit();
+        state_module.record_checkpoint("block-2".to_string());
+
+        assert_eq!(state_module.get_account(&address).unwrap(), credited);
+
+        state_module.rollback_to(&"block-1".to_string()).unwrap();
+
+        for (address, account) in accounts {
+            assert_eq!(
+                state_module.get_account(&address).unwrap(),
+                account.unwrap()
+            );
+        }
+        assert!(state_module.get_account(&new_address).is_err());
+        assert!(state_module.rollback_to(&"block-2".to_string()).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn staking_a_claim_debits_the_staker_and_records_the_stake() {
+        use primitives::{NodeId, QuorumKind};
+        use vrrb_core::staking::{Stake, StakeUpdate};
+
+        use crate::NodeError;
+
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+
+        let (sk, pk) = create_keypair();
+        let address = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let signature =
+            Claim::signature_for_valid_claim(pk, ip_address, sk.secret_bytes().to_vec()).unwrap();
+        let claim = create_claim(&pk, &address, ip_address, signature);
+        state_module.insert_claims(vec![claim]).unwrap();
+
+        let mut account = Account::new(address.clone());
+        account.set_credits(15_000);
+        state_module
+            .extend_accounts(vec![(address.clone(), Some(account))])
+            .unwrap();
+        state_module.commit();
+
+        let mut farmers = create_sig_engines("farmer", 3);
+        let mut sig_engine = farmers[0].1.clone();
+        sig_engine.set_quorum_members(vec![(
+            QuorumKind::Farmer,
+            farmers
+                .iter()
+                .map(|(node_id, engine)| (node_id.clone(), engine.public_key()))
+                .collect(),
+        )]);
+
+        let mut certify = |stake: &mut Stake| {
+            stake.certify((vec![0u8; 96], stake.get_payload())).unwrap();
+            farmers
+                .iter_mut()
+                .map(|(node_id, engine)| {
+                    (node_id.clone(), engine.sign(stake.get_payload()).unwrap())
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut stake = |amount| {
+            let mut stake =
+                Stake::new(StakeUpdate::Add(amount), sk, pk, address.clone(), None).unwrap();
+            let farmer_signatures = certify(&mut stake);
+            (stake, farmer_signatures)
+        };
+
+        let (deposit, farmer_signatures) = stake(10_000);
+        state_module
+            .stake_claim(deposit.clone(), &farmer_signatures, &sig_engine)
+            .unwrap();
+
+        let account = state_module.get_account(&address).unwrap();
+        assert_eq!(account.debits(), 10_000);
+        assert!(account.digests().get_stake().contains(&deposit.digest()));
+
+        let claims = state_module
+            .get_claims_by_account_address(&address)
+            .unwrap();
+        assert_eq!(claims[0].get_stake(), 10_000);
+
+        assert!(matches!(
+            state_module.stake_claim(deposit, &farmer_signatures, &sig_engine),
+            Err(NodeError::StakeAlreadyApplied(_))
+        ));
+        let (overdraft, farmer_signatures) = stake(10_001);
+        assert!(matches!(
+            state_module.stake_claim(overdraft, &farmer_signatures, &sig_engine),
+            Err(NodeError::InsufficientBalance(staker)) if staker == address
+        ));
+
+        let (uncertified, mut farmer_signatures) = stake(1_000);
+        farmer_signatures.truncate(1);
+        assert!(matches!(
+            state_module.stake_claim(uncertified, &farmer_signatures, &sig_engine),
+            Err(NodeError::UncertifiedStake(_))
+        ));
+
+        // signed by another key, naming the staker as the sender
+        let (thief_sk, thief_pk) = create_keypair();
+        let mut theft = Stake::new(
+            StakeUpdate::Add(1_000),
+            thief_sk,
+            thief_pk,
+            address.clone(),
+            None,
+        )
+        .unwrap();
+        let farmer_signatures = certify(&mut theft);
+        assert!(matches!(
+            state_module.stake_claim(theft, &farmer_signatures, &sig_engine),
+            Err(NodeError::UnauthorizedStake(_))
+        ));
+
+        let claims = state_module
+            .get_claims_by_account_address(&address)
+            .unwrap();
+        assert_eq!(claims[0].get_stake(), 10_000);
+        assert_eq!(state_module.get_account(&address).unwrap().debits(), 10_000);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn update_state_reports_the_outcome_of_each_txn() {
+        use block::ProposalBlock;
+        use ritelinked::LinkedHashMap;
+
+        use crate::test_utils::create_txn_from_accounts;
+
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+
+        let funded_account = || {
+            let (_, pk) = create_keypair();
+            let address = create_address(&pk);
+            let mut account = Account::new(address.clone());
+            account.set_credits(15_000);
+            (address, account)
+        };
+        let (sender, sender_account) = funded_account();
+        let (resender, resender_account) = funded_account();
+        let (receiver, receiver_account) = funded_account();
+        let (proposer, proposer_account) = funded_account();
+        state_module
+            .extend_accounts(vec![
+                (sender.clone(), Some(sender_account.clone())),
+                (resender.clone(), Some(resender_account.clone())),
+                (receiver.clone(), Some(receiver_account)),
+                (proposer.clone(), Some(proposer_account)),
+            ])
+            .unwrap();
+
+        let fresh = create_txn_from_accounts(
+            (sender.clone(), Some(sender_account)),
+            receiver.clone(),
+            vec![],
+        );
+        let confirmed = create_txn_from_accounts(
+            (resender.clone(), Some(resender_account)),
+            receiver.clone(),
+            vec![],
+        );
+        state_module.confirm_txn(confirmed.clone()).await.unwrap();
+        state_module.commit();
+
+        let (sk, pk) = create_keypair();
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let signature =
+            Claim::signature_for_valid_claim(pk, ip_address, sk.secret_bytes().to_vec()).unwrap();
+        let from = create_claim(&pk, &proposer, ip_address, signature);
+        let sig_engine = create_sig_engine();
+
+        let dag = state_module.dag();
+        let genesis = produce_genesis_block();
+        let txns: LinkedHashMap<_, _> = [fresh.clone(), confirmed.clone()]
+            .into_iter()
+            .map(|txn| (txn.id(), txn))
+            .collect();
+        let proposal = ProposalBlock::build(
+            genesis.hash.clone(),
+            0,
+            0,
+            txns,
+            LinkedHashMap::new(),
+            from,
+            sig_engine,
+        );
+        if let Ok(mut guard) = dag.write() {
+            let gvtx: Vertex<Block, BlockHash> = Block::from(genesis).into();
+            let pvtx: Vertex<Block, BlockHash> = Block::from(proposal).into();
+            guard.add_vertex(&gvtx);
+            guard.add_edge(&(&gvtx, &pvtx));
+        }
+        let block_hash = produce_convergence_block(dag).unwrap();
+
+        let mut outcomes = state_module.update_state(block_hash).unwrap();
+        outcomes.sort_by_key(|(digest, _)| *digest != fresh.id());
+        state_module.commit();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                (fresh.id(), ApplyOutcome::Applied),
+                (confirmed.id(), ApplyOutcome::SkippedDuplicate),
+            ]
+        );
+        assert_eq!(state_module.get_account(&sender).unwrap().debits(), 10_000);
+        assert_eq!(state_module.get_account(&resender).unwrap().debits(), 0);
+        assert_eq!(
+            state_module.get_account(&receiver).unwrap().credits(),
+            25_000
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[serial]
+    async fn state_queries_are_answered_while_blocks_are_applied() {
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+
+        let (address, account) = produce_accounts(1).remove(0);
+        let initial = account.clone().unwrap().credits();
+        state_module
+            .extend_accounts(vec![(address.clone(), account)])
+            .unwrap();
+        state_module.commit();
+
+        let queries =
+            StateQueryHandle::new(state_module.read_handle(), DEFAULT_STATE_QUERY_CONCURRENCY);
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let queries = queries.clone();
+                let address = address.clone();
+                tokio::spawn(async move {
+                    let mut balances = Vec::new();
+                    for _ in 0..50 {
+                        balances.push(queries.balance(address.clone()).await.unwrap());
+                    }
+                    balances
+                })
+            })
+            .collect();
+
+        // Simulates applying blocks that each credit the account.
+        for block in 1..=10 {
+            let mut account = state_module.get_account(&address).unwrap();
+            account.set_credits(initial + block * 100);
+            state_module
+                .extend_accounts(vec![(address.clone(), Some(account))])
+                .unwrap();
+            state_module.commit();
+            tokio::task::yield_now().await;
+        }
+
+        for reader in readers {
+            let balances = reader.await.unwrap();
+
+            // Readers only ever observe published versions of state, in order.
+            assert!(balances
+                .iter()
+                .all(|balance| (balance - initial) % 100 == 0 && *balance <= initial + 1000));
+            assert!(balances.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+
+        assert_eq!(
+            queries.balance(address.clone()).await.unwrap(),
+            initial + 1000
+        );
+        assert_eq!(
+            queries.account(address).await.unwrap().credits(),
+            initial + 1000
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn failed_snapshot_import_leaves_database_untouched() {
+        let temp_dir = env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let snapshot_path = env::temp_dir().join(format!("{}.snapshot", uuid::Uuid::new_v4()));
+
+        let mut source = state_manager_at(temp_dir.join("source"));
+        source.extend_accounts(produce_accounts(5)).unwrap();
+        source.commit();
+        source.export_snapshot(&snapshot_path).unwrap();
+
+        let mut target = state_manager_at(temp_dir.join("target"));
+        let target_accounts = produce_accounts(2);
+        target.extend_accounts(target_accounts.clone()).unwrap();
+        target.commit();
+        let target_root_hash = target.state_root_hash().unwrap();
+
+        let result = target.import_snapshot(
+            &snapshot_path,
+            VrrbDbConfig::default().with_path(temp_dir.join("imported")),
+            &target_root_hash,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(target.state_root_hash().unwrap(), target_root_hash);
+        for (address, _) in target_accounts {
+            assert!(target.get_account(&address).is_ok());
+        }
+
+        std::fs::remove_file(snapshot_path).unwrap();
+    }
+
+    fn harvester_quorum(harvesters: usize) -> (Vec<(String, SignerEngine)>, SignerEngine) {
+        use primitives::QuorumKind;
+
+        let signers = create_sig_engines("harvester", harvesters);
+
+        let mut sig_engine = create_sig_engine();
+        sig_engine.set_quorum_members(vec![(
+            QuorumKind::Harvester,
+            signers
+                .iter()
+                .map(|(id, engine)| (id.clone(), engine.public_key()))
+                .collect(),
+        )]);
+
+        (signers, sig_engine)
+    }
+
+    fn certify(signers: &mut [(String, SignerEngine)], block_hash: &str) -> block::Certificate {
+        let signatures = signers
+            .iter_mut()
+            .map(|(id, engine)| (id.clone(), engine.sign(block_hash).unwrap()))
+            .collect();
+
+        block::Certificate {
+            signatures,
+            inauguration: None,
+            root_hash: String::from("root"),
+            block_hash: block_hash.to_string(),
+        }
+    }
+
+    fn harvester_certificate(
+        block_hash: &str,
+        harvesters: usize,
+    ) -> (block::Certificate, SignerEngine) {
+        let (mut signers, sig_engine) = harvester_quorum(harvesters);
+
+        (certify(&mut signers, block_hash), sig_engine)
+    }
+
+    fn certified_genesis_block(signers: &mut [(String, SignerEngine)]) -> block::GenesisBlock {
+        let mut genesis = produce_genesis_block();
+        genesis.certificate = Some(certify(signers, &genesis.hash));
+        genesis
+    }
+
+    #[test]
+    #[serial]
+    fn dag_verifies_well_formed_certificate() {
+        let state_module = state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let (certificate, sig_engine) = harvester_certificate("block-1", 4);
+
+        assert!(state_module
+            .dag
+            .verify_certificate(&certificate, &sig_engine)
+            .unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn dag_rejects_certificate_with_forged_signature() {
+        let state_module = state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let (mut certificate, sig_engine) = harvester_certificate("block-1", 4);
+
+        let mut forger = create_sig_engine();
+        certificate.signatures[0].1 = forger.sign("block-1").unwrap();
+
+        assert!(!state_module
+            .dag
+            .verify_certificate(&certificate, &sig_engine)
+            .unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn dag_rejects_certificate_with_repeated_signer() {
+        let state_module = state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let (mut certificate, sig_engine) = harvester_certificate("block-1", 4);
+
+        let repeated = certificate.signatures[0].clone();
+        certificate.signatures.truncate(1);
+        certificate.signatures.extend(vec![repeated; 3]);
+
+        assert!(!state_module
+            .dag
+            .verify_certificate(&certificate, &sig_engine)
+            .unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn dag_appends_genesis_only_with_a_valid_certificate() {
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let genesis = produce_genesis_block();
+        let (certificate, sig_engine) = harvester_certificate(&genesis.hash, 4);
+
+        assert!(state_module
+            .dag
+            .append_genesis(&genesis, &sig_engine)
+            .unwrap()
+            .is_none());
+        assert!(state_module
+            .dag
+            .read()
+            .unwrap()
+            .get_vertex(genesis.hash.clone())
+            .is_none());
+
+        let mut forger = create_sig_engine();
+        let mut forged = genesis.clone();
+        let mut forged_certificate = certificate.clone();
+        forged_certificate.signatures[0].1 = forger.sign(&genesis.hash).unwrap();
+        forged.certificate = Some(forged_certificate);
+
+        assert!(!state_module.dag.check_valid_genesis(&forged, &sig_engine));
+        assert!(state_module
+            .dag
+            .append_genesis(&forged, &sig_engine)
+            .is_err());
+        assert!(state_module
+            .dag
+            .read()
+            .unwrap()
+            .get_vertex(genesis.hash.clone())
+            .is_none());
+
+        assert!(state_module
+            .dag
+            .append_certificate_to_genesis_block(&genesis.hash, &certificate, &sig_engine)
+            .unwrap()
+            .is_some());
+        assert!(state_module
+            .dag
+            .read()
+            .unwrap()
+            .get_vertex(genesis.hash.clone())
+            .is_some());
+        assert_eq!(
+            state_module.dag.last_confirmed_block_header(),
+            Some(genesis.header.clone())
+        );
+        assert!(state_module
+            .dag
+            .append_certificate_to_genesis_block(&genesis.hash, &certificate, &sig_engine)
+            .is_err());
+    }
+
+    fn convergence_block_at(
+        parent: &block::GenesisBlock,
+        timestamp: i64,
+        certificate: Option<block::Certificate>,
+    ) -> block::ConvergenceBlock {
+        let mut header = parent.header.clone();
+        header.timestamp = timestamp;
+        header.block_height += 1;
+
+        block::ConvergenceBlock {
+            header,
+            txns: Default::default(),
+            claims: Default::default(),
+            hash: String::from("convergence"),
+            certificate,
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn dag_rejects_backdated_convergence_block() {
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let (mut signers, sig_engine) = harvester_quorum(4);
+        let genesis = certified_genesis_block(&mut signers);
+        state_module
+            .dag
+            .append_genesis(&genesis, &sig_engine)
+            .unwrap();
+
+        let backdated = convergence_block_at(&genesis, genesis.header.timestamp - 60, None);
+
+        assert!(state_module
+            .dag
+            .append_convergence(&backdated, &sig_engine)
+            .is_err());
+        assert!(state_module
+            .dag
+            .get_pending_convergence_block_mut(&backdated.hash)
+            .is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn dag_accepts_convergence_block_after_parent() {
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let (mut signers, sig_engine) = harvester_quorum(4);
+        let genesis = certified_genesis_block(&mut signers);
+        state_module
+            .dag
+            .append_genesis(&genesis, &sig_engine)
+            .unwrap();
+
+        let timestamp = genesis.header.timestamp + 1;
+        let certificate = certify(&mut signers, "convergence");
+        let pending = convergence_block_at(&genesis, timestamp, None);
+        let certified = convergence_block_at(&genesis, timestamp, Some(certificate));
+
+        assert!(state_module
+            .dag
+            .append_convergence(&pending, &sig_engine)
+            .unwrap()
+            .is_none());
+        assert!(state_module
+            .dag
+            .append_convergence(&certified, &sig_engine)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            state_module
+                .dag
+                .last_confirmed_block_header()
+                .unwrap()
+                .timestamp,
+            timestamp
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn dag_rejects_convergence_block_without_a_valid_certificate() {
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let (mut signers, sig_engine) = harvester_quorum(4);
+        let genesis = certified_genesis_block(&mut signers);
+        state_module
+            .dag
+            .append_genesis(&genesis, &sig_engine)
+            .unwrap();
+
+        let timestamp = genesis.header.timestamp + 1;
+        let certificate = certify(&mut signers, "convergence");
+
+        let mut unsigned = certificate.clone();
+        unsigned.signatures.clear();
+
+        let mut forger = create_sig_engine();
+        let mut forged = certificate.clone();
+        forged.signatures[0].1 = forger.sign("convergence").unwrap();
+
+        let mut misdirected = certificate;
+        misdirected.block_hash = String::from("another-block");
+
+        for certificate in [unsigned, forged, misdirected] {
+            let block = convergence_block_at(&genesis, timestamp, Some(certificate));
+            assert!(state_module
+                .dag
+                .append_convergence(&block, &sig_engine)
+                .is_err());
+        }
+
+        assert_eq!(
+            state_module.dag.last_confirmed_block_header(),
+            Some(genesis.header.clone())
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn dag_connects_orphaned_proposal_once_parent_arrives() {
+        use bulldag::graph::GraphError;
+        use primitives::{NodeId, QuorumKind};
+
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        let mut genesis = produce_genesis_block();
+
+        let mut sig_engine = create_sig_engine();
+        sig_engine.set_quorum_members(vec![(
+            QuorumKind::Harvester,
+            vec![(NodeId::default(), sig_engine.public_key())],
+        )]);
+
+        let accounts = produce_accounts(2);
+        let proposal =
+            produce_proposal_blocks(genesis.hash.clone(), accounts, 1, 1, sig_engine.clone())
+                .pop()
+                .unwrap();
+
+        assert!(matches!(
+            state_module
+                .dag
+                .append_proposal(&proposal, sig_engine.clone()),
+            Err(GraphError::NonExistentSource)
+        ));
+        assert_eq!(state_module.dag.orphan_count(), 1);
+
+        genesis.certificate = Some(certify(
+            &mut [(NodeId::default(), sig_engine.clone())],
+            &genesis.hash,
+        ));
+        state_module
+            .dag
+            .append_genesis(&genesis, &sig_engine)
+            .unwrap();
+
+        assert_eq!(state_module.dag.orphan_count(), 0);
+        let guard = state_module.dag.read().unwrap();
+        assert!(guard.get_vertex(genesis.hash.clone()).is_some());
+        assert!(guard.get_vertex(proposal.hash.clone()).is_some());
+    }
+
+    #[test]
+    #[serial]
+    fn dag_reports_certificate_timeout_after_signature_deadline() {
+        use std::time::Duration;
+
+        use crate::NodeError;
+
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        state_module
+            .dag
+            .set_signature_collection_deadline(Duration::from_millis(50));
+
+        // block-1 stays short of the threshold past the deadline
+        let (stalled, sig_engine) = harvester_certificate("block-1", 4);
+        let threshold = sig_engine.quorum_members().get_harvester_threshold();
+        for (node_id, sig) in stalled.signatures.iter().take(threshold - 1) {
+            let result = state_module.dag.add_signer_to_block(
+                stalled.block_hash.clone(),
+                *sig,
+                node_id.clone(),
+                &sig_engine,
+            );
+            assert!(matches!(result, Err(NodeError::Other(_))));
+        }
+
+        std::thread::sleep(Duration::from_millis(100));
+
+        assert!(matches!(
+            state_module
+                .dag
+                .check_certificate_threshold_reached(&stalled.block_hash, &sig_engine),
+            Err(NodeError::CertificateTimeout(block_hash)) if block_hash == "block-1"
+        ));
+
+        // block-2 reaches the threshold within the deadline
+        state_module
+            .dag
+            .set_signature_collection_deadline(Duration::from_secs(30));
+        let (certified, sig_engine) = harvester_certificate("block-2", 4);
+        let threshold = sig_engine.quorum_members().get_harvester_threshold();
+        let mut result = Err(NodeError::Other(String::new()));
+        for (node_id, sig) in certified.signatures.iter().take(threshold) {
+            result = state_module.dag.add_signer_to_block(
+                certified.block_hash.clone(),
+                *sig,
+                node_id.clone(),
+                &sig_engine,
+            );
+        }
+
+        assert_eq!(result.unwrap().len(), threshold);
+    }
+
+    #[test]
+    #[serial]
+    fn dag_sweeps_timed_out_signature_collections() {
+        use std::time::Duration;
+
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+        state_module
+            .dag
+            .set_signature_collection_deadline(Duration::from_millis(50));
+
+        let (stalled, sig_engine) = harvester_certificate("block-1", 4);
+        let (node_id, sig) = stalled.signatures[0].clone();
+        let _ = state_module.dag.add_signer_to_block(
+            stalled.block_hash.clone(),
+            sig,
+            node_id,
+            &sig_engine,
+        );
+        assert!(state_module
+            .dag
+            .timed_out_signature_collections(&sig_engine)
+            .is_empty());
+
+        std::thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(
+            state_module
+                .dag
+                .timed_out_signature_collections(&sig_engine),
+            vec![stalled.block_hash.clone()]
+        );
+
+        // Restarting the collection window re-arms the deadline.
+        state_module
+            .dag
+            .restart_signature_collection(&stalled.block_hash);
+        assert_eq!(
+            state_module
+                .dag
+                .signature_collection_restarts(&stalled.block_hash),
+            1
+        );
+        assert!(state_module
+            .dag
+            .timed_out_signature_collections(&sig_engine)
+            .is_empty());
+
+        std::thread::sleep(Duration::from_millis(100));
+        state_module
+            .dag
+            .prune_signature_collection(&stalled.block_hash);
+
+        assert!(state_module
+            .dag
+            .timed_out_signature_collections(&sig_engine)
+            .is_empty());
+        assert_eq!(
+            state_module
+                .dag
+                .signature_collection_restarts(&stalled.block_hash),
+            0
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn harvester_signs_a_block_only_once() {
+        use crate::NodeError;
+
+        let mut state_module =
+            state_manager_at(env::temp_dir().join(uuid::Uuid::new_v4().to_string()));
+
+        let (certificate, sig_engine) = harvester_certificate("block-1", 4);
+        let (node_id, sig) = certificate.signatures[0].clone();
+        let (_, other_sig) = certificate.signatures[1].clone();
+
+        for _ in 0..2 {
+            let _ = state_module.dag.add_signer_to_block(
+                certificate.block_hash.clone(),
+                sig,
+                node_id.clone(),
+                &sig_engine,
+            );
+        }
+
+        assert!(matches!(
+            state_module.dag.add_signer_to_block(
+                certificate.block_hash.clone(),
+                other_sig,
+                node_id.clone(),
+                &sig_engine,
+            ),
+            Err(NodeError::ConflictingSignature { node_id: signer, .. }) if signer == node_id
+        ));
+
+        // Only the first signature of the node counts towards the threshold.
+        let threshold = sig_engine.quorum_members().get_harvester_threshold();
+        let mut result = Err(NodeError::Other(String::new()));
+        for (node_id, sig) in certificate.signatures.iter().skip(1).take(threshold - 1) {
+            result = state_module.dag.add_signer_to_block(
+                certificate.block_hash.clone(),
+                *sig,
+                node_id.clone(),
+                &sig_engine,
+            );
+        }
+
+        let signatures = result.unwrap();
+        assert_eq!(signatures.len(), threshold);
+        assert!(signatures.contains(&(node_id, sig)));
+    }
+
+    #[test]
+    fn consolidated_storage_and_code_follow_highest_nonce() {
+        use std::collections::HashSet;
+
+        use vrrb_core::account::UpdateArgs;
+
+        use super::utils::consolidate_update_args;
+
+        let (_, public_key) = create_keypair();
+        let address = Address::new(public_key);
+
+        let update = |nonce: u128, storage: Option<&str>, code: Option<&str>| UpdateArgs {
+            address: address.clone(),
+            nonce: Some(nonce),
+            credits: Some(10),
+            debits: None,
+            storage: storage.map(|value| Some(value.to_string())),
+            package_address: code.map(|value| Some(value.to_string())),
+            digests: None,
+        };
+
+        let updates = vec![
+            update(3, Some("storage-3"), Some("code-3")),
+            update(1, Some("storage-1"), Some("code-1")),
+            update(5, Some("storage-5"), Some("code-5")),
+            update(6, None, None),
+            update(2, Some("storage-2"), Some("code-2")),
+        ];
+
+        let forward: HashSet<UpdateArgs> = updates.iter().cloned().collect();
+        let backward: HashSet<UpdateArgs> = updates.iter().rev().cloned().collect();
+
+        let forward = consolidate_update_args(forward).unwrap();
+        let backward = consolidate_update_args(backward).unwrap();
+        assert_eq!(forward, backward);
+
+        let consolidated = forward.get(&address).unwrap();
+        assert_eq!(consolidated.nonce, Some(6));
+        assert_eq!(consolidated.credits, Some(50));
+        assert_eq!(consolidated.storage, Some(Some("storage-5".to_string())));
+        assert_eq!(
+            consolidated.package_address,
+            Some(Some("code-5".to_string()))
+        );
+    }
+
+    #[test]
+    fn consolidating_overflowing_credits_fails() {
+        use std::collections::HashSet;
+
+        use vrrb_core::account::UpdateArgs;
+
+        use super::utils::consolidate_update_args;
+        use crate::NodeError;
+
+        let (_, public_key) = create_keypair();
+        let address = Address::new(public_key);
+
+        let update = |nonce: u128| UpdateArgs {
+            address: address.clone(),
+            nonce: Some(nonce),
+            credits: Some(u128::MAX - 1),
+            debits: None,
+            storage: None,
+            package_address: None,
+            digests: None,
+        };
+
+        let updates: HashSet<UpdateArgs> = [update(1), update(2)].into_iter().collect();
+
+        assert!(matches!(
+            consolidate_update_args(updates),
+            Err(NodeError::BalanceOverflow(overflowing)) if overflowing == address
+        ));
+    }
 }
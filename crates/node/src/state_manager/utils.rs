@@ -4,6 +4,8 @@ use primitives::Address;
 use storage::vrrbdb::types::*;
 use vrrb_core::account::UpdateArgs;
 
+use crate::{NodeError, Result};
+
 /// Converts a HashSet of `StateUpdate`s into a HashSet of `UpdateArgs`s
 /// structs.
 pub(super) fn get_update_args(updates: HashSet<StateUpdate>) -> HashSet<UpdateArgs> {
@@ -13,40 +15,55 @@ pub(super) fn get_update_args(updates: HashSet<StateUpdate>) -> HashSet<UpdateAr
 /// Iterates through all `UpdateArgs` structs in a HashSet and consolidates
 /// them into a single `UpdateArgs` struct for each address which has
 /// activity in a given round.
+///
+/// Fails if the credits or debits of an address overflow once added up,
+/// which can only happen for a crafted block, instead of wrapping them.
 pub(super) fn consolidate_update_args(
     updates: HashSet<UpdateArgs>,
-) -> HashMap<Address, UpdateArgs> {
+) -> Result<HashMap<Address, UpdateArgs>> {
     let mut consolidated_updates: HashMap<Address, UpdateArgs> = HashMap::new();
 
+    // Storage and code are taken from the highest nonce update that sets
+    // them, so updates are folded in nonce order rather than in the
+    // HashSet's iteration order, which differs between nodes.
+    let mut updates: Vec<UpdateArgs> = updates.into_iter().collect();
+    updates.sort_by(|a, b| {
+        (a.nonce, &a.storage, &a.package_address).cmp(&(b.nonce, &b.storage, &b.package_address))
+    });
+
     for update in updates.into_iter() {
-        let address = update.address.clone();
-
-        consolidated_updates
-            .entry(address)
-            .and_modify(|existing_update| {
-                existing_update.nonce = existing_update.nonce.max(update.nonce);
-                existing_update.credits = match (existing_update.credits, update.credits) {
-                    (Some(a), Some(b)) => Some(a + b),
-                    (a, None) => a,
-                    (_, b) => b,
-                };
-                existing_update.debits = match (existing_update.debits, update.debits) {
-                    (Some(a), Some(b)) => Some(a + b),
-                    (a, None) => a,
-                    (_, b) => b,
-                };
-                existing_update.storage = update.storage.clone(); // TODO: Update this to use the most recent value
-                existing_update.package_address = update.package_address.clone(); // TODO: Update this to use the most recent value
-                if let Some(digests) = update.digests.clone() {
-                    if let Some(ref mut existing_digests) = existing_update.digests {
-                        existing_digests.extend_all(digests);
-                    } else {
-                        existing_update.digests = Some(digests);
-                    }
-                }
-            })
-            .or_insert(update);
+        let Some(existing_update) = consolidated_updates.get_mut(&update.address) else {
+            consolidated_updates.insert(update.address.clone(), update);
+            continue;
+        };
+
+        let overflow = || NodeError::BalanceOverflow(update.address.clone());
+
+        existing_update.nonce = existing_update.nonce.max(update.nonce);
+        existing_update.credits = match (existing_update.credits, update.credits) {
+            (Some(a), Some(b)) => Some(a.checked_add(b).ok_or_else(overflow)?),
+            (a, None) => a,
+            (_, b) => b,
+        };
+        existing_update.debits = match (existing_update.debits, update.debits) {
+            (Some(a), Some(b)) => Some(a.checked_add(b).ok_or_else(overflow)?),
+            (a, None) => a,
+            (_, b) => b,
+        };
+        if update.storage.is_some() {
+            existing_update.storage = update.storage.clone();
+        }
+        if update.package_address.is_some() {
+            existing_update.package_address = update.package_address.clone();
+        }
+        if let Some(digests) = update.digests.clone() {
+            if let Some(ref mut existing_digests) = existing_update.digests {
+                existing_digests.extend_all(digests);
+            } else {
+                existing_update.digests = Some(digests);
+            }
+        }
     }
 
-    consolidated_updates
+    Ok(consolidated_updates)
 }
@@ -212,6 +212,27 @@ pub fn create_keypair() -> (SecretKey, PublicKey) {
     kp.miner_kp
 }
 
+/// Creates a `SignerEngine` signing with the miner keys of `keypair`.
+pub fn create_sig_engine_for(keypair: &KeyPair) -> SignerEngine {
+    SignerEngine::new(
+        *keypair.get_miner_public_key(),
+        *keypair.get_miner_secret_key(),
+    )
+}
+
+/// Creates a `SignerEngine` backed by a random keypair.
+pub fn create_sig_engine() -> SignerEngine {
+    create_sig_engine_for(&KeyPair::random())
+}
+
+/// Creates `n` signer engines backed by random keypairs, identified as
+/// `{prefix}-{idx}`.
+pub fn create_sig_engines(prefix: &str, n: usize) -> Vec<(NodeId, SignerEngine)> {
+    (0..n)
+        .map(|idx| (format!("{prefix}-{idx}"), create_sig_engine()))
+        .collect()
+}
+
 pub fn create_txn_from_accounts(
     sender: (Address, Option<Account>),
     receiver: Address,
@@ -636,9 +657,9 @@ pub async fn setup_network(
     for current_node in nodes.iter_mut() {
         for node in other_nodes_copy.iter() {
             let node_pubkey = node.config_ref().keypair.miner_public_key_owned();
-            node_0.create_account(node_pubkey).unwrap();
-            current_node.create_account(node_0_pubkey).unwrap();
-            current_node.create_account(node_pubkey).unwrap();
+            node_0.get_or_create_account(node_pubkey).unwrap();
+            current_node.get_or_create_account(node_0_pubkey).unwrap();
+            current_node.get_or_create_account(node_pubkey).unwrap();
         }
     }
 
@@ -711,6 +732,7 @@ pub fn dummy_convergence_block() -> ConvergenceBlock {
             block_height: 21_600,
             timestamp: Default::default(),
             txn_hash: Default::default(),
+            txn_root: None,
             miner_claim: produce_random_claim(22),
             claim_list_hash: Default::default(),
             block_reward: Default::default(),
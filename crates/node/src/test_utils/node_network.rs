@@ -87,6 +87,7 @@ pub async fn create_test_network_from_config(n: u16, base_config: Option<NodeCon
     let bootstrap_config = BootstrapConfig {
         additional_genesis_receivers,
         bootstrap_quorum_config: bootstrap_quorum_config.clone(),
+        quorum_assignment_seed: 0,
     };
 
     let mut config = create_mock_full_node_config();
@@ -17,10 +17,32 @@ use std::{
     sync::Arc,
 };
 use tokio_util::sync::CancellationToken;
-use vrrb_config::{BootstrapPeerData, BootstrapQuorumConfig, BootstrapQuorumMember};
+use vrrb_config::{BootstrapPeerData, BootstrapQuorumConfig, BootstrapQuorumMember, NodeConfig};
 use vrrb_core::keypair::Keypair;
 
 use super::create_mock_full_node_config;
+
+/// Creates a standalone NodeRuntime from `config`, exporting its metrics on
+/// the Prometheus address and with the certificates `config` points to.
+pub async fn create_node_runtime(config: &NodeConfig, events_tx: EventPublisher) -> NodeRuntime {
+    let factory = Arc::new(
+        PrometheusFactory::new(
+            config.prometheus_bind_addr.clone(),
+            0,
+            false,
+            HashMap::new(),
+            config.prometheus_cert_path.clone(),
+            config.prometheus_private_key_path.clone(),
+            CancellationToken::new(),
+        )
+        .unwrap(),
+    );
+
+    NodeRuntime::new(config, events_tx, factory, HashMap::new())
+        .await
+        .unwrap()
+}
+
 /// Creates n NodeRuntimes to simulate networks
 pub async fn create_node_runtime_network(
     n: usize,
@@ -68,6 +90,7 @@ pub async fn create_node_runtime_network(
     let bootstrap_node_config = vrrb_config::BootstrapConfig {
         additional_genesis_receivers: None,
         bootstrap_quorum_config,
+        quorum_assignment_seed: 0,
     };
 
     let mut config = create_mock_full_node_config();
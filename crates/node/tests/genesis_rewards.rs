@@ -1,11 +1,12 @@
 //! Genesis block should contain a list of rewards to pre configured addresses. These rewards should allocate a pre configurable number of tokens.
-use block::{Block, Certificate, GenesisReceiver};
+use block::{Block, Certificate};
 use events::DEFAULT_BUFFER;
 use node::{
     node_runtime::NodeRuntime, test_utils::create_quorum_assigned_node_runtime_network, NodeError,
 };
 use primitives::{Address, NodeType, QuorumKind, Signature};
 use storage::vrrbdb::ApplyBlockResult;
+use vrrb_config::GenesisAllocation;
 
 /// Genesis blocks created by elected Miner nodes should contain at least one reward
 #[tokio::test]
@@ -21,8 +22,11 @@ async fn genesis_block_contains_rewards() {
         .iter()
         .map(|node| Address::new(node.config.keypair.miner_public_key_owned()))
         .collect::<Vec<Address>>();
-    let receivers = assign_genesis_receivers(receiver_addresses);
-    let genesis_rewards = genesis_miner.distribute_genesis_reward(receivers).unwrap();
+    allocate_genesis_to(
+        &receiver_addresses,
+        nodes.iter_mut().chain([&mut genesis_miner]),
+    );
+    let genesis_rewards = genesis_miner.distribute_genesis_reward().unwrap();
     let genesis_block = genesis_miner
         .mine_genesis_block(genesis_rewards.clone())
         .unwrap();
@@ -43,8 +47,11 @@ async fn genesis_block_rewards_are_valid() {
         .iter()
         .map(|node| Address::new(node.config.keypair.miner_public_key_owned()))
         .collect::<Vec<Address>>();
-    let receivers = assign_genesis_receivers(receiver_addresses.clone());
-    let genesis_rewards = genesis_miner.distribute_genesis_reward(receivers).unwrap();
+    allocate_genesis_to(
+        &receiver_addresses,
+        nodes.iter_mut().chain([&mut genesis_miner]),
+    );
+    let genesis_rewards = genesis_miner.distribute_genesis_reward().unwrap();
     let genesis_block = genesis_miner
         .mine_genesis_block(genesis_rewards.clone())
         .unwrap();
@@ -67,8 +74,11 @@ async fn genesis_block_can_be_certified() {
         .iter()
         .map(|node| Address::new(node.config.keypair.miner_public_key_owned()))
         .collect::<Vec<Address>>();
-    let receivers = assign_genesis_receivers(receiver_addresses.clone());
-    let genesis_rewards = genesis_miner.distribute_genesis_reward(receivers).unwrap();
+    allocate_genesis_to(
+        &receiver_addresses,
+        nodes.iter_mut().chain([&mut genesis_miner]),
+    );
+    let genesis_rewards = genesis_miner.distribute_genesis_reward().unwrap();
     let genesis_block = genesis_miner
         .mine_genesis_block(genesis_rewards.clone())
         .unwrap();
@@ -85,10 +95,12 @@ async fn genesis_block_can_be_certified() {
         })
         .collect();
     let mut chosen_harvester = harvesters.pop().unwrap();
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     assert!(chosen_harvester
         .state_driver
-        .append_genesis(&genesis_block)
-        .is_ok());
+        .append_genesis(&genesis_block, &sig_engine)
+        .unwrap()
+        .is_none());
     let mut sigs: Vec<Signature> = Vec::new();
     for node in harvesters.iter_mut() {
         sigs.push(
@@ -98,7 +110,12 @@ async fn genesis_block_can_be_certified() {
             .await
             .unwrap(),
         );
-        assert!(node.state_driver.append_genesis(&genesis_block).is_ok());
+        let sig_engine = node.consensus_driver.sig_engine();
+        assert!(node
+            .state_driver
+            .append_genesis(&genesis_block, &sig_engine)
+            .unwrap()
+            .is_none());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     for (sig, harvester) in sigs.into_iter().zip(harvesters.iter()) {
@@ -124,8 +141,11 @@ async fn all_nodes_append_certified_genesis_block_to_dag() {
         .iter()
         .map(|node| Address::new(node.config.keypair.miner_public_key_owned()))
         .collect::<Vec<Address>>();
-    let receivers = assign_genesis_receivers(receiver_addresses.clone());
-    let genesis_rewards = genesis_miner.distribute_genesis_reward(receivers).unwrap();
+    allocate_genesis_to(
+        &receiver_addresses,
+        nodes.iter_mut().chain([&mut genesis_miner]),
+    );
+    let genesis_rewards = genesis_miner.distribute_genesis_reward().unwrap();
     let genesis_block = genesis_miner
         .mine_genesis_block(genesis_rewards.clone())
         .unwrap();
@@ -182,10 +202,12 @@ async fn all_nodes_append_certified_genesis_block_to_dag() {
             .unwrap();
     });
     let mut chosen_harvester = harvesters.pop().unwrap();
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     assert!(chosen_harvester
         .state_driver
-        .append_genesis(&genesis_block)
-        .is_ok());
+        .append_genesis(&genesis_block, &sig_engine)
+        .unwrap()
+        .is_none());
     let mut sigs: Vec<Signature> = Vec::new();
     for node in harvesters.iter_mut() {
         sigs.push(
@@ -195,7 +217,12 @@ async fn all_nodes_append_certified_genesis_block_to_dag() {
             .await
             .unwrap(),
         );
-        assert!(node.state_driver.append_genesis(&genesis_block).is_ok());
+        let sig_engine = node.consensus_driver.sig_engine();
+        assert!(node
+            .state_driver
+            .append_genesis(&genesis_block, &sig_engine)
+            .unwrap()
+            .is_none());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     for (sig, harvester) in sigs.into_iter().zip(harvesters.iter()) {
@@ -251,11 +278,33 @@ async fn genesis_block_rewards_are_applied_to_state() {
         .iter()
         .map(|node| Address::new(node.config.keypair.miner_public_key_owned()))
         .collect::<Vec<Address>>();
-    let receivers = assign_genesis_receivers(receiver_addresses);
-    let genesis_reward_state_updates = genesis_miner.distribute_genesis_reward(receivers).unwrap();
-    let genesis_block = genesis_miner
+    allocate_genesis_to(
+        &receiver_addresses,
+        all_nodes.iter_mut().chain([&mut genesis_miner]),
+    );
+    let genesis_reward_state_updates = genesis_miner.distribute_genesis_reward().unwrap();
+    let mut genesis_block = genesis_miner
         .mine_genesis_block(genesis_reward_state_updates.clone())
         .unwrap();
+
+    // certify genesis block, nodes only apply it once certified
+    let mut signatures = Vec::new();
+    for node in all_nodes.iter_mut() {
+        if node.consensus_driver.is_harvester().is_ok() {
+            let signature = node
+                .handle_sign_genesis_block(&genesis_block)
+                .await
+                .unwrap();
+            signatures.push((node.config.id.clone(), signature));
+        }
+    }
+    genesis_block.certificate = Some(Certificate {
+        signatures,
+        inauguration: None,
+        root_hash: genesis_block.header.txn_hash.clone(),
+        block_hash: genesis_block.hash.clone(),
+    });
+
     // apply rewards
     let results: Vec<ApplyBlockResult> = all_nodes
         .iter_mut()
@@ -280,9 +329,21 @@ async fn genesis_block_rewards_are_applied_to_state() {
     });
 }
 
-fn assign_genesis_receivers(receiver_addresses: Vec<Address>) -> Vec<GenesisReceiver> {
-    receiver_addresses
+/// Nodes check genesis rewards against their own config, so every node gets
+/// the same allocations.
+fn allocate_genesis_to<'a>(
+    receiver_addresses: &[Address],
+    nodes: impl IntoIterator<Item = &'a mut NodeRuntime>,
+) {
+    let allocations: Vec<GenesisAllocation> = receiver_addresses
         .iter()
-        .map(|address| GenesisReceiver::new(address.clone()))
-        .collect()
+        .map(|address| GenesisAllocation {
+            address: address.clone(),
+            amount: 10000,
+        })
+        .collect();
+
+    for node in nodes {
+        node.config.genesis_allocations = allocations.clone();
+    }
 }
@@ -102,9 +102,10 @@ async fn harvester_nodes_form_certificate() {
         .collect();
     let convergence_block = dummy_convergence_block();
     let mut chosen_harvester = harvesters.pop().unwrap();
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     let _ = chosen_harvester
         .state_driver
-        .append_convergence(&convergence_block);
+        .append_convergence(&convergence_block, &sig_engine);
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
         // 2 of 3 harvester nodes sign a convergence block
@@ -114,9 +115,10 @@ async fn harvester_nodes_form_certificate() {
                 .await
                 .unwrap(),
         );
+        let sig_engine = harvester.consensus_driver.sig_engine();
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &sig_engine);
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -157,9 +159,10 @@ async fn certificate_formed_includes_pending_quorum() {
     let convergence_block = dummy_convergence_block();
     let mut chosen_harvester = harvesters.pop().unwrap();
 
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     let _ = chosen_harvester
         .state_driver
-        .append_convergence(&convergence_block);
+        .append_convergence(&convergence_block, &sig_engine);
 
     let mut sigs: Vec<Signature> = Vec::new();
 
@@ -171,9 +174,10 @@ async fn certificate_formed_includes_pending_quorum() {
                 .await
                 .unwrap(),
         );
+        let sig_engine = harvester.consensus_driver.sig_engine();
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &sig_engine);
     }
 
     let mut eligible_claims = produce_random_claims(21)
@@ -265,9 +269,10 @@ async fn all_nodes_append_certificate_to_convergence_block() {
             .unwrap();
     });
     let mut chosen_harvester = harvesters.pop().unwrap();
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     let _ = chosen_harvester
         .state_driver
-        .append_convergence(&convergence_block);
+        .append_convergence(&convergence_block, &sig_engine);
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
         // 2 of 3 harvester nodes sign a convergence block
@@ -277,9 +282,10 @@ async fn all_nodes_append_certificate_to_convergence_block() {
                 .await
                 .unwrap(),
         );
+        let sig_engine = harvester.consensus_driver.sig_engine();
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &sig_engine);
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -369,9 +375,10 @@ async fn all_nodes_append_certified_convergence_block_to_dag() {
             .unwrap();
     });
     let mut chosen_harvester = harvesters.pop().unwrap();
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     let _ = chosen_harvester
         .state_driver
-        .append_convergence(&convergence_block);
+        .append_convergence(&convergence_block, &sig_engine);
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
         // 2 of 3 harvester nodes sign a convergence block
@@ -381,9 +388,10 @@ async fn all_nodes_append_certified_convergence_block_to_dag() {
                 .await
                 .unwrap(),
         );
+        let sig_engine = harvester.consensus_driver.sig_engine();
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &sig_engine);
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -516,9 +524,10 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
     });
 
     let mut chosen_harvester = harvesters.pop().unwrap();
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     let _ = chosen_harvester
         .state_driver
-        .append_convergence(&convergence_block);
+        .append_convergence(&convergence_block, &sig_engine);
 
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
@@ -529,9 +538,10 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
                 .await
                 .unwrap(),
         );
+        let sig_engine = harvester.consensus_driver.sig_engine();
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &sig_engine);
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
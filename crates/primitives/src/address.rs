@@ -8,6 +8,34 @@ use crate::{ByteVec, PublicKey, SecretKey};
 
 pub type AddressBytes = [u8; 20];
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddressError {
+    #[error("malformed address {0}: expected 0x followed by 40 hex characters")]
+    Malformed(String),
+
+    #[error("invalid checksum for address {0}")]
+    InvalidChecksum(String),
+
+    #[error("invalid bech32 human-readable prefix: {0}")]
+    InvalidHrp(String),
+
+    #[error("malformed bech32 address {0}")]
+    InvalidBech32(String),
+
+    #[error("expected a bech32 address prefixed with {expected}, found {found}")]
+    UnexpectedHrp { expected: String, found: String },
+}
+
+/// Characters of the bech32 alphabet, indexed by the 5 bit value they encode.
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Maximum length of a bech32 string as defined by BIP-173.
+const BECH32_MAX_LEN: usize = 90;
+
+/// Length of the data part of a bech32 address: 20 bytes regrouped into 32
+/// five bit characters, followed by a 6 character checksum.
+const BECH32_ADDRESS_DATA_LEN: usize = 32 + 6;
+
 /// Represents the lower 20 bytes
 /// of a secp256k1 public key, hashed with sha256::digest
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -22,6 +50,110 @@ impl Address {
         self.0
     }
 
+    /// Encodes the address as mixed-case hex, where the case of every letter
+    /// is a checksum of the address as described in EIP-55.
+    pub fn to_checksummed_string(&self) -> String {
+        let encoded = hex::encode(self.0);
+        let hash = Keccak256::digest(encoded.as_bytes());
+
+        let checksummed: String = encoded
+            .chars()
+            .enumerate()
+            .map(|(idx, c)| {
+                let nibble = (hash[idx / 2] >> (4 * (1 - idx % 2))) & 0x0f;
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+
+        format!("0x{checksummed}")
+    }
+
+    /// Parses a hex address, validating its EIP-55 checksum when it's in
+    /// mixed case. All lowercase or all uppercase addresses carry no checksum
+    /// and are accepted as is.
+    pub fn from_checksummed_str(s: &str) -> Result<Self, AddressError> {
+        let digits = s
+            .strip_prefix("0x")
+            .filter(|digits| digits.len() == 40)
+            .ok_or_else(|| AddressError::Malformed(s.to_string()))?;
+
+        let mut address = [0u8; 20];
+        hex::decode_to_slice(digits, &mut address)
+            .map_err(|_| AddressError::Malformed(s.to_string()))?;
+        let address = Address(address);
+
+        let is_lowercase = !digits.chars().any(|c| c.is_ascii_uppercase());
+        let is_uppercase = !digits.chars().any(|c| c.is_ascii_lowercase());
+        if !is_lowercase && !is_uppercase && address.to_checksummed_string() != s {
+            return Err(AddressError::InvalidChecksum(s.to_string()));
+        }
+
+        Ok(address)
+    }
+
+    /// Encodes the address as bech32 (BIP-173) with the human-readable prefix
+    /// `hrp`, e.g. `vrrb1...`. The prefix must be lowercase printable ASCII
+    /// and short enough for the whole address to fit in `BECH32_MAX_LEN`.
+    pub fn to_bech32(&self, hrp: &str) -> Result<String, AddressError> {
+        validate_hrp(hrp)?;
+        if hrp.len() + 1 + BECH32_ADDRESS_DATA_LEN > BECH32_MAX_LEN {
+            return Err(AddressError::InvalidHrp(hrp.to_string()));
+        }
+
+        let mut data = convert_bits(&self.0, 8, 5, true).unwrap_or_default();
+        data.extend(bech32_checksum(hrp, &data));
+
+        let encoded: String = data
+            .iter()
+            .map(|value| BECH32_CHARSET[*value as usize] as char)
+            .collect();
+
+        Ok(format!("{hrp}1{encoded}"))
+    }
+
+    /// Decodes a bech32 address, rejecting it unless its human-readable
+    /// prefix is `expected_hrp`, so that addresses of another network aren't
+    /// mistaken for local ones.
+    pub fn from_bech32(s: &str, expected_hrp: &str) -> Result<Self, AddressError> {
+        let malformed = || AddressError::InvalidBech32(s.to_string());
+
+        let has_lowercase = s.chars().any(|c| c.is_ascii_lowercase());
+        let has_uppercase = s.chars().any(|c| c.is_ascii_uppercase());
+        if s.len() > BECH32_MAX_LEN || (has_lowercase && has_uppercase) {
+            return Err(malformed());
+        }
+
+        let lowercase = s.to_ascii_lowercase();
+        let (hrp, encoded) = lowercase.rsplit_once('1').ok_or_else(malformed)?;
+        validate_hrp(hrp).map_err(|_| malformed())?;
+
+        if hrp != expected_hrp {
+            return Err(AddressError::UnexpectedHrp {
+                expected: expected_hrp.to_string(),
+                found: hrp.to_string(),
+            });
+        }
+
+        let data = encoded
+            .bytes()
+            .map(|c| BECH32_CHARSET.iter().position(|&b| b == c).map(|v| v as u8))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(malformed)?;
+
+        if data.len() < 6 || bech32_polymod(&[hrp_expand(hrp), data.clone()].concat()) != 1 {
+            return Err(malformed());
+        }
+
+        let bytes = convert_bits(&data[..data.len() - 6], 5, 8, false).ok_or_else(malformed)?;
+        let address: AddressBytes = bytes.try_into().map_err(|_| malformed())?;
+
+        Ok(Address(address))
+    }
+
     #[deprecated]
     pub fn public_key_bytes(&self) -> ByteVec {
         // TODO: revisit later
@@ -102,6 +234,81 @@ impl FromStr for Address {
     }
 }
 
+fn validate_hrp(hrp: &str) -> Result<(), AddressError> {
+    let is_valid = !hrp.is_empty()
+        && hrp.len() <= 83
+        && hrp
+            .bytes()
+            .all(|c| (33..=126).contains(&c) && !c.is_ascii_uppercase());
+
+    if !is_valid {
+        return Err(AddressError::InvalidHrp(hrp.to_string()));
+    }
+    Ok(())
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    values.iter().fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = ((chk & 0x1ffffff) << 5) ^ *value as u32;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| (top >> idx) & 1 == 1)
+            .fold(chk, |chk, (_, generator)| chk ^ generator)
+    })
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let high = hrp.bytes().map(|c| c >> 5);
+    let low = hrp.bytes().map(|c| c & 0x1f);
+
+    high.chain(std::iter::once(0)).chain(low).collect()
+}
+
+fn bech32_checksum(hrp: &str, data: &[u8]) -> Vec<u8> {
+    let values = [hrp_expand(hrp), data.to_vec(), vec![0; 6]].concat();
+    let polymod = bech32_polymod(&values) ^ 1;
+
+    (0..6)
+        .map(|idx| ((polymod >> (5 * (5 - idx))) & 0x1f) as u8)
+        .collect()
+}
+
+/// Regroups `data` from `from` bit values into `to` bit values. Without
+/// padding, leftover bits must be zero and fewer than `from`.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to) - 1;
+    let max_acc: u32 = (1 << (from + to - 1)) - 1;
+    let mut converted = Vec::new();
+
+    for value in data {
+        if (*value as u32) >> from != 0 {
+            return None;
+        }
+        acc = ((acc << from) | *value as u32) & max_acc;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            converted.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            converted.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_value) != 0 {
+        return None;
+    }
+
+    Some(converted)
+}
+
 pub type AccountKeypair = (secp256k1::SecretKey, secp256k1::PublicKey);
 
 pub fn generate_account_keypair() -> AccountKeypair {
@@ -117,3 +324,103 @@ pub fn generate_mock_account_keypair() -> AccountKeypair {
     let public_key = PublicKey::from_secret_key(&secp, &secret_key);
     (secret_key, public_key)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn checksummed_address_round_trips() {
+        let address = Address::from_checksummed_str(CHECKSUMMED).unwrap();
+
+        assert_eq!(address.to_checksummed_string(), CHECKSUMMED);
+        assert_eq!(address.to_string(), CHECKSUMMED.to_lowercase());
+    }
+
+    #[test]
+    fn wrong_checksum_is_rejected() {
+        let mistyped = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+
+        assert_eq!(
+            Address::from_checksummed_str(mistyped),
+            Err(AddressError::InvalidChecksum(mistyped.to_string()))
+        );
+    }
+
+    #[test]
+    fn lowercase_address_is_accepted() {
+        let address = Address::from_checksummed_str(&CHECKSUMMED.to_lowercase()).unwrap();
+
+        assert_eq!(address.to_checksummed_string(), CHECKSUMMED);
+    }
+
+    #[test]
+    fn bech32_address_round_trips() {
+        let address = Address::from_checksummed_str(CHECKSUMMED).unwrap();
+        let encoded = address.to_bech32("vrrb").unwrap();
+
+        assert_eq!(encoded, "vrrb1t2htvpfl862vnwdqnuekd9p4ulh3h6hd2j0qxz");
+        assert_eq!(Address::from_bech32(&encoded, "vrrb").unwrap(), address);
+        assert_eq!(
+            Address::from_bech32(&encoded.to_uppercase(), "vrrb").unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn bech32_address_with_wrong_prefix_is_rejected() {
+        let address = Address::from_checksummed_str(CHECKSUMMED).unwrap();
+        let encoded = address.to_bech32("tvrrb").unwrap();
+
+        assert_eq!(
+            Address::from_bech32(&encoded, "vrrb"),
+            Err(AddressError::UnexpectedHrp {
+                expected: "vrrb".to_string(),
+                found: "tvrrb".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn corrupted_bech32_address_is_rejected() {
+        let corrupted = "vrrb1t2htvpfl862vnwdqnuekd9p4ulh3h6hd2j0qxq";
+
+        assert_eq!(
+            Address::from_bech32(corrupted, "vrrb"),
+            Err(AddressError::InvalidBech32(corrupted.to_string()))
+        );
+        assert!(Address([0; 20]).to_bech32("VRRB").is_err());
+    }
+
+    #[test]
+    fn bech32_prefix_too_long_for_an_address_is_rejected() {
+        let address = Address([7; 20]);
+        let longest_hrp = "a".repeat(BECH32_MAX_LEN - 1 - BECH32_ADDRESS_DATA_LEN);
+        let too_long_hrp = format!("{longest_hrp}a");
+
+        let encoded = address.to_bech32(&longest_hrp).unwrap();
+        assert_eq!(encoded.len(), BECH32_MAX_LEN);
+        assert_eq!(Address::from_bech32(&encoded, &longest_hrp).unwrap(), address);
+
+        assert_eq!(
+            address.to_bech32(&too_long_hrp),
+            Err(AddressError::InvalidHrp(too_long_hrp.clone()))
+        );
+    }
+
+    #[test]
+    fn malformed_addresses_are_rejected() {
+        for malformed in [
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aAeb6053F",
+            "0xzz",
+        ] {
+            assert_eq!(
+                Address::from_checksummed_str(malformed),
+                Err(AddressError::Malformed(malformed.to_string()))
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, str::FromStr};
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -80,3 +80,111 @@ impl From<usize> for NodeType {
         }
     }
 }
+
+/// Maps string node identities (`NodeId`) to the compact indices (`NodeIdx`)
+/// used by DKG/hbbft routing and back.
+///
+/// Indices are assigned by sorting the node ids, so every node that registers
+/// the same set of ids ends up with the exact same mapping.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeRegistry {
+    indices: BTreeMap<NodeId, NodeIdx>,
+    node_ids: Vec<NodeId>,
+}
+
+impl NodeRegistry {
+    pub fn new<I>(node_ids: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = NodeId>,
+    {
+        let mut node_ids = node_ids.into_iter().collect::<Vec<NodeId>>();
+        node_ids.sort();
+        node_ids.dedup();
+
+        if node_ids.len() > NodeIdx::MAX as usize + 1 {
+            return Err(Error::Other(format!(
+                "cannot register {} nodes, at most {} node indices are available",
+                node_ids.len(),
+                NodeIdx::MAX as usize + 1
+            )));
+        }
+
+        let indices = node_ids
+            .iter()
+            .enumerate()
+            .map(|(idx, node_id)| (node_id.clone(), idx as NodeIdx))
+            .collect();
+
+        Ok(Self { indices, node_ids })
+    }
+
+    pub fn node_idx(&self, node_id: &NodeId) -> Option<NodeIdx> {
+        self.indices.get(node_id).copied()
+    }
+
+    pub fn node_id(&self, node_idx: NodeIdx) -> Option<&NodeId> {
+        self.node_ids.get(node_idx as usize)
+    }
+
+    pub fn contains(&self, node_id: &NodeId) -> bool {
+        self.indices.contains_key(node_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.node_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.node_ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_registry_round_trips_node_ids() {
+        let node_ids = vec![
+            String::from("node-3"),
+            String::from("node-1"),
+            String::from("node-2"),
+            String::from("node-0"),
+        ];
+
+        let registry = NodeRegistry::new(node_ids.clone()).unwrap();
+
+        assert_eq!(registry.len(), 4);
+
+        for node_id in node_ids.iter() {
+            let node_idx = registry.node_idx(node_id).unwrap();
+            assert_eq!(registry.node_id(node_idx), Some(node_id));
+        }
+
+        assert_eq!(registry.node_idx(&String::from("node-4")), None);
+        assert_eq!(registry.node_id(4), None);
+    }
+
+    #[test]
+    fn node_registry_assigns_indices_deterministically() {
+        let registry = NodeRegistry::new(vec![
+            String::from("node-b"),
+            String::from("node-c"),
+            String::from("node-a"),
+        ])
+        .unwrap();
+
+        let other_registry = NodeRegistry::new(vec![
+            String::from("node-c"),
+            String::from("node-a"),
+            String::from("node-b"),
+            String::from("node-a"),
+        ])
+        .unwrap();
+
+        assert_eq!(registry, other_registry);
+        assert_eq!(registry.node_idx(&String::from("node-a")), Some(0));
+        assert_eq!(registry.node_idx(&String::from("node-b")), Some(1));
+        assert_eq!(registry.node_idx(&String::from("node-c")), Some(2));
+    }
+}
@@ -0,0 +1,91 @@
+use std::{collections::HashMap, hash::Hash};
+
+use patriecia::Version;
+use storage_utils::Result;
+
+use crate::VrrbDb;
+
+/// Versions of the state, transaction and claim tries at the time it was
+/// taken. A `VrrbDb` can be rolled back to the contents it had then, e.g. to
+/// undo the blocks of an abandoned fork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrrbDbCheckpoint {
+    pub state_version: Version,
+    pub transactions_version: Version,
+    pub claims_version: Version,
+}
+
+impl VrrbDb {
+    /// Captures the versions of the committed contents of all stores.
+    pub fn checkpoint(&self) -> VrrbDbCheckpoint {
+        VrrbDbCheckpoint {
+            state_version: self.state_store_factory().handle().version(),
+            transactions_version: self.transaction_store_factory().handle().version(),
+            claims_version: self.claim_store_factory().handle().version(),
+        }
+    }
+
+    /// Reverts all stores to the contents they had when `checkpoint` was
+    /// taken, writing the reverted contents as new versions of the tries.
+    ///
+    /// All stores are read before any of them is written, so if reading any
+    /// of them fails the database is left untouched.
+    pub fn rollback_to(&mut self, checkpoint: &VrrbDbCheckpoint) -> Result<()> {
+        let state = self.state_store_factory().handle();
+        let accounts = changes_since(
+            state.entries()?,
+            state.entries_at(checkpoint.state_version)?,
+        );
+
+        let transactions = self.transaction_store_factory().handle();
+        let transactions = changes_since(
+            transactions.entries()?,
+            transactions.entries_at(checkpoint.transactions_version)?,
+        );
+
+        let by_hash = |claims: HashMap<_, vrrb_core::claim::Claim>| -> HashMap<_, _> {
+            claims
+                .into_values()
+                .map(|claim| (claim.hash, claim))
+                .collect()
+        };
+        let claims = self.claim_store_factory().handle();
+        let claims = changes_since(
+            by_hash(claims.entries()?),
+            by_hash(claims.entries_at(checkpoint.claims_version)?),
+        );
+
+        self.extend_accounts(accounts);
+        self.extend_transaction_entries(transactions);
+        self.extend_claims(claims);
+
+        self.commit_state();
+        self.commit_transactions();
+        self.commit_claims();
+
+        Ok(())
+    }
+}
+
+/// Lists the writes turning `current` back into `target`: entries missing
+/// from `target` are removed and entries that differ are restored.
+fn changes_since<K, V>(current: HashMap<K, V>, target: HashMap<K, V>) -> Vec<(K, Option<V>)>
+where
+    K: Eq + Hash + Clone,
+    V: PartialEq,
+{
+    let mut changes: Vec<(K, Option<V>)> = current
+        .keys()
+        .filter(|key| !target.contains_key(key))
+        .map(|key| (key.clone(), None))
+        .collect();
+
+    changes.extend(
+        target
+            .into_iter()
+            .filter(|(key, value)| current.get(key) != Some(value))
+            .map(|(key, value)| (key, Some(value))),
+    );
+
+    changes
+}
@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 
-use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
+use ethereum_types::U256;
+use integral_db::{JellyfishMerkleTreeWrapper, Proof, ReadHandleFactory};
 use patriecia::{JellyfishMerkleTree, Version};
 use primitives::NodeId;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::claim::Claim;
 
-use crate::RocksDbAdapter;
+use crate::{ClaimProof, RocksDbAdapter};
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandle {
@@ -43,10 +44,15 @@ impl ClaimStoreReadHandle {
     }
 
     pub fn entries(&self) -> Result<HashMap<NodeId, Claim>> {
+        self.entries_at(self.inner.version())
+    }
+
+    /// Returns the claims stored in the trie as of `version`.
+    pub fn entries_at(&self, version: Version) -> Result<HashMap<NodeId, Claim>> {
         // TODO: revisit and refactor into inner wrapper
         Ok(self
             .inner
-            .iter(self.inner.version())
+            .iter(version)
             .map_err(|err| {
                 StorageError::Other(format!("unable to create iterator from trie: {}", err))
             })?
@@ -62,6 +68,29 @@ impl ClaimStoreReadHandle {
             .collect())
     }
 
+    /// Returns the claim stored under `key` along with a proof of its
+    /// membership in the latest version of the claim trie.
+    pub fn get_proof(&self, key: &U256) -> Result<ClaimProof> {
+        let (claim, proof): (Option<Claim>, Proof) = self
+            .inner
+            .get_with_proof(key, self.inner.version())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let claim =
+            claim.ok_or_else(|| StorageError::Other(format!("claim {key} not found in trie")))?;
+
+        Ok(ClaimProof {
+            key: *key,
+            claim,
+            proof,
+        })
+    }
+
+    /// Latest version of the trie visible to this handle.
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Returns a number of initialized claims in the database
     pub fn len(&self) -> usize {
         self.inner.len()
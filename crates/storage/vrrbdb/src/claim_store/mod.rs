@@ -1,8 +1,8 @@
 use std::{path::Path, sync::Arc};
 
 use ethereum_types::U256;
-use integral_db::LeftRightTrie;
-use patriecia::RootHash;
+use integral_db::{LeftRightTrie, Proof};
+use patriecia::{KeyHash, RootHash};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::claim::Claim;
@@ -15,6 +15,30 @@ pub use claim_store_rh::*;
 pub type Claims = Vec<Claim>;
 pub type FailedClaimUpdates = Vec<(U256, Claims, Result<()>)>;
 
+/// Proof that a claim is stored in the claim trie, which lets light clients
+/// check a claim against the trie's root hash without holding the store.
+#[derive(Debug, Clone)]
+pub struct ClaimProof {
+    pub key: U256,
+    pub claim: Claim,
+    pub proof: Proof,
+}
+
+impl ClaimProof {
+    /// Verifies that `claim` is stored under `key` in the claim trie whose
+    /// root hash is `root_hash`.
+    pub fn verify(&self, root_hash: RootHash) -> Result<()> {
+        let key = bincode::serialize(&self.key)
+            .map_err(|err| StorageError::Other(format!("unable to serialize claim key: {err}")))?;
+        let claim = bincode::serialize(&self.claim)
+            .map_err(|err| StorageError::Other(format!("unable to serialize claim: {err}")))?;
+
+        self.proof
+            .verify(root_hash, KeyHash::with::<Sha256>(key), Some(claim))
+            .map_err(|err| StorageError::Other(format!("invalid claim proof: {err}")))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ClaimStore {
     trie: LeftRightTrie<'static, U256, Claim, RocksDbAdapter, Sha256>,
@@ -281,6 +305,12 @@ impl ClaimStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
+    /// Builds a proof that the claim stored under `key` is part of the
+    /// committed claim trie, to be verified against `root_hash`.
+    pub fn get_proof(&self, key: &U256) -> Result<ClaimProof> {
+        self.read_handle().get_proof(key)
+    }
+
     pub fn extend(&mut self, claims: Vec<(U256, Option<Claim>)>) {
         self.trie.extend(claims)
     }
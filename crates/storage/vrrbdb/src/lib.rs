@@ -1,6 +1,9 @@
+mod checkpoint;
 mod claim_store;
 pub mod result;
 mod rocksdb_adapter;
+mod snapshot;
+mod state_query;
 mod state_store;
 pub mod test_utils;
 mod transaction_store;
@@ -9,8 +12,11 @@ mod vrrbdb;
 mod vrrbdb_read_handle;
 mod vrrbdb_serialized_values;
 
+pub use checkpoint::*;
 pub use claim_store::*;
 pub use rocksdb_adapter::*;
+pub use snapshot::*;
+pub use state_query::*;
 pub use state_store::*;
 pub use transaction_store::*;
 pub use types::*;
@@ -0,0 +1,95 @@
+use primitives::Address;
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+use vrrb_core::{account::Account, claim::Claim, transactions::TransactionKind};
+
+use crate::{VrrbDb, VrrbDbConfig};
+
+/// A full copy of the contents of a `VrrbDb` along with the root hashes of
+/// its tries at the time it was taken. Used to bootstrap new nodes without
+/// having them replay the whole DAG.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VrrbDbSnapshot {
+    pub state_root_hash: String,
+    pub transactions_root_hash: String,
+    pub claims_root_hash: String,
+    pub accounts: Vec<(Address, Account)>,
+    pub transactions: Vec<TransactionKind>,
+    pub claims: Vec<Claim>,
+}
+
+impl VrrbDb {
+    /// Captures the committed contents of all stores.
+    pub fn snapshot(&self) -> Result<VrrbDbSnapshot> {
+        let read_handle = self.read_handle();
+
+        Ok(VrrbDbSnapshot {
+            state_root_hash: hex::encode(self.state_root_hash()?.0),
+            transactions_root_hash: hex::encode(self.transactions_root_hash()?.0),
+            claims_root_hash: hex::encode(self.claims_root_hash()?.0),
+            accounts: read_handle.state_store_values()?.into_iter().collect(),
+            transactions: read_handle
+                .transaction_store_values()?
+                .into_values()
+                .collect(),
+            claims: read_handle.claim_store_values()?.into_values().collect(),
+        })
+    }
+
+    /// Builds a new `VrrbDb` at the location described by `config` out of a
+    /// snapshot, failing if the resulting root hashes don't match the ones
+    /// recorded in the snapshot. `config` should point to an empty location,
+    /// otherwise the snapshot is merged with whatever is already stored there
+    /// and verification fails.
+    pub fn from_snapshot(config: VrrbDbConfig, snapshot: VrrbDbSnapshot) -> Result<VrrbDb> {
+        let mut db = VrrbDb::new(config);
+
+        db.extend_accounts(
+            snapshot
+                .accounts
+                .into_iter()
+                .map(|(address, account)| (address, Some(account)))
+                .collect(),
+        );
+        db.extend_transactions(snapshot.transactions);
+        db.extend_claims(
+            snapshot
+                .claims
+                .into_iter()
+                .map(|claim| (claim.hash, Some(claim)))
+                .collect(),
+        );
+
+        db.commit_state();
+        db.commit_transactions();
+        db.commit_claims();
+
+        verify_root_hash(
+            "state",
+            &snapshot.state_root_hash,
+            &hex::encode(db.state_root_hash()?.0),
+        )?;
+        verify_root_hash(
+            "transactions",
+            &snapshot.transactions_root_hash,
+            &hex::encode(db.transactions_root_hash()?.0),
+        )?;
+        verify_root_hash(
+            "claims",
+            &snapshot.claims_root_hash,
+            &hex::encode(db.claims_root_hash()?.0),
+        )?;
+
+        Ok(db)
+    }
+}
+
+fn verify_root_hash(store: &str, expected: &str, actual: &str) -> Result<()> {
+    if expected != actual {
+        return Err(StorageError::Other(format!(
+            "{store} root hash mismatch: expected {expected}, got {actual}"
+        )));
+    }
+
+    Ok(())
+}
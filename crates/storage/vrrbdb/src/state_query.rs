@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use ethereum_types::U256;
+use primitives::Address;
+use storage_utils::StorageError;
+use tokio::sync::Semaphore;
+use vrrb_core::account::Account;
+
+use crate::{result::Result, ClaimProof, VrrbDbReadHandle};
+
+/// Number of state queries that may read from the database at the same time.
+/// Further queries wait for one of them to finish.
+pub const DEFAULT_STATE_QUERY_CONCURRENCY: usize = 16;
+
+/// Cheap to clone handle answering read-only queries about state on behalf
+/// of RPC and other readers, so heavy read traffic neither competes with
+/// `StateManager` for the database while blocks are being applied nor stalls
+/// the async runtime.
+///
+/// Queries only go through a `VrrbDbReadHandle`, whose reads never wait on
+/// the writer. Every query sees the state as of the last published commit:
+/// writes made while a block is being applied only become visible once they
+/// are published, all at once. Two consecutive queries may observe different
+/// versions of state if a commit lands between them.
+///
+/// Reads hit RocksDB, so each one runs on the blocking thread pool. At most
+/// `max_concurrent_reads` run at once, so a burst of queries can't take over
+/// the pool.
+#[derive(Debug, Clone)]
+pub struct StateQueryHandle {
+    read_handle: VrrbDbReadHandle,
+    permits: Arc<Semaphore>,
+}
+
+impl StateQueryHandle {
+    pub fn new(read_handle: VrrbDbReadHandle, max_concurrent_reads: usize) -> Self {
+        Self {
+            read_handle,
+            permits: Arc::new(Semaphore::new(max_concurrent_reads.max(1))),
+        }
+    }
+
+    /// Returns the balance of `address`, its credits minus its debits.
+    pub async fn balance(&self, address: Address) -> Result<u128> {
+        self.query(move |read_handle| {
+            let account = read_handle.get_account_by_address(&address)?;
+            Ok(account.credits().saturating_sub(account.debits()))
+        })
+        .await
+    }
+
+    pub async fn account(&self, address: Address) -> Result<Account> {
+        self.query(move |read_handle| read_handle.get_account_by_address(&address))
+            .await
+    }
+
+    /// Returns the claim stored under `key` along with a proof of its
+    /// membership in the claim trie.
+    pub async fn claim_proof(&self, key: U256) -> Result<ClaimProof> {
+        self.query(move |read_handle| read_handle.claim_proof(&key))
+            .await
+    }
+
+    async fn query<T, F>(&self, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&VrrbDbReadHandle) -> Result<T> + Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| StorageError::Other("state query handle is closed".to_string()))?;
+
+        let read_handle = self.read_handle.clone();
+
+        tokio::task::spawn_blocking(move || read(&read_handle))
+            .await
+            .map_err(|err| StorageError::Other(format!("state query failed to complete: {err}")))?
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
-use patriecia::JellyfishMerkleTree;
+use patriecia::{JellyfishMerkleTree, Version};
 use primitives::Address;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
@@ -44,11 +44,16 @@ impl StateStoreReadHandle {
     }
 
     pub fn entries(&self) -> Result<HashMap<Address, Account>> {
+        self.entries_at(self.inner.version())
+    }
+
+    /// Returns the accounts stored in the trie as of `version`.
+    pub fn entries_at(&self, version: Version) -> Result<HashMap<Address, Account>> {
         // TODO: revisit and refactor into inner wrapper
 
         Ok(self
             .inner
-            .iter(self.inner.version())
+            .iter(version)
             .map_err(|err| {
                 StorageError::Other(format!("unable to create iterator from trie: {}", err))
             })?
@@ -62,6 +67,11 @@ impl StateStoreReadHandle {
             .collect())
     }
 
+    /// Latest version of the trie visible to this handle.
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Returns a number of initialized accounts in the database
     pub fn len(&self) -> usize {
         self.inner.len()
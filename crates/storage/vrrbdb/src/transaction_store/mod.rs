@@ -70,6 +70,12 @@ impl TransactionStore {
         self.trie.extend(transactions)
     }
 
+    /// Writes `entries` to the trie as is, removing the transactions whose
+    /// entry is `None`.
+    pub fn extend_entries(&mut self, entries: Vec<(TransactionDigest, Option<TransactionKind>)>) {
+        self.trie.extend(entries)
+    }
+
     pub fn root_hash(&self) -> Result<RootHash> {
         self.trie
             .root_latest()
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
-use patriecia::{JellyfishMerkleTree, Version};
+use patriecia::{JellyfishMerkleTree, KeyHash, Version};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
@@ -40,10 +40,18 @@ impl TransactionStoreReadHandle {
     }
 
     pub fn entries(&self) -> Result<HashMap<TransactionDigest, TransactionKind>> {
+        self.entries_at(self.inner.version())
+    }
+
+    /// Returns the transactions stored in the trie as of `version`.
+    pub fn entries_at(
+        &self,
+        version: Version,
+    ) -> Result<HashMap<TransactionDigest, TransactionKind>> {
         // TODO: revisit and refactor into inner wrapper
         Ok(self
             .inner
-            .iter(self.inner.version())
+            .iter(version)
             .map_err(|err| {
                 StorageError::Other(format!("unable to create iterator from trie: {}", err))
             })?
@@ -58,6 +66,68 @@ impl TransactionStoreReadHandle {
             .collect())
     }
 
+    /// Returns up to `limit` transactions following `cursor`, along with the
+    /// cursor of the next page, or `None` once the last page is reached.
+    ///
+    /// Transactions are ordered by the hash of their digest, the order the
+    /// trie iterates them in, so pages are stable across calls for as long as
+    /// the trie isn't modified.
+    pub fn get_page(
+        &self,
+        cursor: Option<TransactionDigest>,
+        limit: usize,
+    ) -> Result<(Vec<TransactionKind>, Option<TransactionDigest>)> {
+        let cursor = cursor
+            .map(|digest| {
+                bincode::serialize(&digest)
+                    .map(KeyHash::with::<Sha256>)
+                    .map_err(|err| StorageError::Other(format!("invalid cursor: {err}")))
+            })
+            .transpose()?;
+
+        let iter = self.inner.iter(self.inner.version()).map_err(|err| {
+            StorageError::Other(format!("unable to create iterator from trie: {}", err))
+        })?;
+
+        // The trie iterator always starts at the smallest key hash, so entries
+        // up to the cursor are skipped rather than sought past. Errors are
+        // returned instead of skipped, or a page could silently come back
+        // short and end the pagination early.
+        let mut page = Vec::new();
+        for item in iter {
+            let (key_hash, txn) = item.map_err(|err| {
+                StorageError::Other(format!("unable to read transaction from trie: {}", err))
+            })?;
+
+            if cursor.is_some_and(|cursor| key_hash <= cursor) {
+                continue;
+            }
+
+            page.push(
+                bincode::deserialize::<TransactionKind>(&txn)
+                    .map_err(|err| StorageError::Other(err.to_string()))?,
+            );
+
+            if page.len() > limit {
+                break;
+            }
+        }
+
+        if page.len() <= limit {
+            return Ok((page, None));
+        }
+
+        page.truncate(limit);
+        let next_cursor = page.last().map(|txn| txn.id());
+
+        Ok((page, next_cursor))
+    }
+
+    /// Latest version of the trie visible to this handle.
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Returns a number of transactions in the ledger
     pub fn len(&self) -> usize {
         self.inner.len()
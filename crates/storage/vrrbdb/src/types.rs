@@ -16,8 +16,8 @@ pub struct RoundBlocks {
 
 /// Provides variants to parse to ensure state module handles updates
 /// properly, whether it be an Account receiving tokens, and
-/// account sending tokens, a new claim, claim staking (TODO),
-/// fees or rewards (TODO).
+/// account sending tokens, a new claim, claim staking, which debits the
+/// staked amount from the staker, fees or rewards (TODO).
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum UpdateAccount {
     Sender,
@@ -120,7 +120,7 @@ impl From<StateUpdate> for UpdateArgs {
                     address: item.address,
                     nonce: item.nonce,
                     credits: None,
-                    debits: None,
+                    debits: Some(item.amount),
                     storage: None,
                     package_address: None,
                     digests: Some(digest.clone()),
@@ -221,27 +221,48 @@ impl FromTxn for IntoUpdates {
 
 /// Converts a Transaction into a HashSet of `StateUpdate`s
 /// for fee distribution among the validators of a given tx
+///
+/// The validator fee share is split evenly between the validators that voted
+/// for the transaction. The remainder of the division goes to the validator
+/// with the lowest address, so the shares always add up to the whole fee
+/// share regardless of the order validators are stored in.
 impl FromTxn for HashSet<StateUpdate> {
     fn from_txn(txn: TransactionKind) -> HashSet<StateUpdate> {
         let mut set = HashSet::new();
         let fees = txn.validator_fee_share();
-        if let Some(mut validator_set) = txn.validators() {
-            validator_set.retain(|_, vote| *vote);
-            let validator_share = fees / (validator_set.len() as u128);
-            validator_set.iter().for_each(|(k, _v)| {
-                let address = Address::from_str(k);
-                if let Ok(addr) = address {
-                    set.insert(StateUpdate {
-                        address: addr,
-                        token: None,
-                        amount: validator_share,
-                        nonce: None,
-                        storage: None,
-                        package_address: None,
-                        digest: TransactionDigest::default(),
-                        update_account: UpdateAccount::Fee,
-                    });
-                }
+        if let Some(validator_set) = txn.validators() {
+            let mut validators: Vec<Address> = validator_set
+                .iter()
+                .filter(|(_, vote)| **vote)
+                .filter_map(|(k, _)| Address::from_str(k).ok())
+                .collect();
+
+            if validators.is_empty() {
+                return set;
+            }
+
+            validators.sort();
+
+            let validator_share = fees / (validators.len() as u128);
+            let remainder = fees % (validators.len() as u128);
+
+            validators.into_iter().enumerate().for_each(|(i, addr)| {
+                let amount = if i == 0 {
+                    validator_share + remainder
+                } else {
+                    validator_share
+                };
+
+                set.insert(StateUpdate {
+                    address: addr,
+                    token: None,
+                    amount,
+                    nonce: None,
+                    storage: None,
+                    package_address: None,
+                    digest: TransactionDigest::default(),
+                    update_account: UpdateAccount::Fee,
+                });
             });
         }
 
@@ -6,14 +6,14 @@ use patriecia::RootHash;
 use primitives::Address;
 
 use storage_utils::{Result, StorageError};
-use vrrb_core::transactions::{Transaction, TransactionKind, Transfer};
+use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, Transfer};
 use vrrb_core::{
     account::{Account, UpdateArgs},
     claim::Claim,
 };
 
 use crate::{
-    ClaimStore, ClaimStoreReadHandleFactory, FromTxn, IntoUpdates, StateStore,
+    ClaimProof, ClaimStore, ClaimStoreReadHandleFactory, FromTxn, IntoUpdates, StateStore,
     StateStoreReadHandleFactory, StateUpdate, TransactionStore, TransactionStoreReadHandleFactory,
     VrrbDbReadHandle,
 };
@@ -146,6 +146,12 @@ impl VrrbDb {
         self.claim_store.root_hash()
     }
 
+    /// Builds a proof that the claim stored under `key` is part of the claim
+    /// trie, to be verified against `claims_root_hash`.
+    pub fn claim_proof(&self, key: &U256) -> Result<ClaimProof> {
+        self.claim_store.get_proof(key)
+    }
+
     /// Produces a reader factory that can be used to generate read handles into
     /// the state trie.
     pub fn state_store_factory(&self) -> StateStoreReadHandleFactory {
@@ -205,6 +211,15 @@ impl VrrbDb {
         self.transaction_store.extend(transactions);
     }
 
+    /// Writes transactions to the current transaction tree as is, removing
+    /// the ones whose entry is `None`.
+    pub fn extend_transaction_entries(
+        &mut self,
+        entries: Vec<(TransactionDigest, Option<TransactionKind>)>,
+    ) {
+        self.transaction_store.extend_entries(entries);
+    }
+
     /// Inserts a confirmed claim to the current claim tree.
     pub fn insert_claim_unchecked(&mut self, claim: Claim) -> Result<()> {
         self.claim_store.insert(claim)
@@ -356,10 +371,16 @@ impl VrrbDb {
             Block::Convergence { block: _ } => {
                 todo!()
             }
-            _ => {
-                telemetry::info!("unsupported block type: {:?}", block);
-                Err(StorageError::Other("unsupported block type".to_string()))
-            }
+            // Proposals only affect state once the convergence block of their
+            // round is applied, so the current roots are reported unchanged.
+            // The transaction trie stays empty until then, as for genesis.
+            Block::Proposal { block: _ } => Ok(ApplyBlockResult {
+                state_root_hash: self.state_store.root_hash()?,
+                transactions_root_hash: self
+                    .transaction_store
+                    .root_hash()
+                    .unwrap_or(RootHash(Default::default())),
+            }),
         }
     }
 }
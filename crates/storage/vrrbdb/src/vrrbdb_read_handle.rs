@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use ethereum_types::U256;
 use primitives::{Address, NodeId};
 use storage_utils::StorageError;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
@@ -7,7 +8,8 @@ use vrrb_core::{account::Account, claim::Claim};
 
 use crate::result::Result;
 use crate::{
-    ClaimStoreReadHandleFactory, StateStoreReadHandleFactory, TransactionStoreReadHandleFactory,
+    ClaimProof, ClaimStoreReadHandleFactory, StateStoreReadHandleFactory,
+    TransactionStoreReadHandleFactory,
 };
 
 #[derive(Debug, Clone)]
@@ -42,6 +44,13 @@ impl VrrbDbReadHandle {
         self.transaction_store_handle_factory.handle().entries()
     }
 
+    /// Returns the transaction stored under `digest` in the latest version of
+    /// the transaction trie.
+    pub fn get_transaction(&self, digest: &TransactionDigest) -> Result<TransactionKind> {
+        let handle = self.transaction_store_handle_factory.handle();
+        handle.get(digest, handle.version())
+    }
+
     // TODO: rewrite these to get start at the first key available and the latest version
     /// Returns a copy of all values stored within the state trie
     pub fn claim_store_values(&self) -> Result<HashMap<NodeId, Claim>> {
@@ -56,4 +65,10 @@ impl VrrbDbReadHandle {
                 StorageError::Other(format!("Failed to get account by address: {:?}", err))
             })
     }
+
+    /// Returns the claim stored under `key` along with a proof of its
+    /// membership in the latest published version of the claim trie.
+    pub fn claim_proof(&self, key: &U256) -> Result<ClaimProof> {
+        self.claim_store_handle_factory.handle().get_proof(key)
+    }
 }
@@ -37,3 +37,41 @@ fn claims_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn claim_proofs_can_be_verified_against_the_claims_root_hash() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default());
+
+    let claim = _generate_random_claim();
+    let other = _generate_random_claim();
+
+    db.insert_claim(claim.clone()).unwrap();
+    db.insert_claim(other).unwrap();
+
+    let root_hash = db.claims_root_hash().unwrap();
+    let proof = db.claim_proof(&claim.hash).unwrap();
+
+    assert_eq!(proof.claim, claim);
+    proof.verify(root_hash).unwrap();
+}
+
+#[test]
+#[serial]
+fn tampered_claim_proofs_are_rejected() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default());
+
+    let claim = _generate_random_claim();
+    db.insert_claim(claim.clone()).unwrap();
+
+    let root_hash = db.claims_root_hash().unwrap();
+    let proof = db.claim_proof(&claim.hash).unwrap();
+
+    let mut tampered = proof.clone();
+    tampered.claim.signature = String::from("forged");
+    assert!(tampered.verify(root_hash).is_err());
+
+    let mut misplaced = proof;
+    misplaced.key = _generate_random_claim().hash;
+    assert!(misplaced.verify(root_hash).is_err());
+}
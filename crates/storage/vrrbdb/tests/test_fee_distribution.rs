@@ -0,0 +1,38 @@
+use std::collections::{HashMap, HashSet};
+
+use vrrb_core::transactions::{Transaction, TransactionKind};
+use vrrbdb::{FromTxn, StateUpdate};
+mod common;
+
+use common::{_generate_random_address, _generate_random_valid_transaction};
+
+#[test]
+fn validator_fee_shares_add_up_to_the_fee() {
+    let mut validators: Vec<_> = (0..3).map(|_| _generate_random_address().1).collect();
+    validators.sort();
+
+    let mut votes: HashMap<String, bool> = validators
+        .iter()
+        .map(|address| (address.to_string(), true))
+        .collect();
+    votes.insert(_generate_random_address().1.to_string(), false);
+
+    let TransactionKind::Transfer(mut transfer) = _generate_random_valid_transaction();
+    transfer.validators = Some(votes);
+    let txn = TransactionKind::Transfer(transfer);
+
+    let fees = txn.validator_fee_share();
+    assert_ne!(fees % 3, 0);
+
+    let updates = HashSet::<StateUpdate>::from_txn(txn);
+    let shares: HashMap<_, _> = updates
+        .iter()
+        .map(|update| (update.address.clone(), update.amount))
+        .collect();
+
+    assert_eq!(shares.len(), 3);
+    assert_eq!(shares.values().sum::<u128>(), fees);
+    assert_eq!(shares[&validators[0]], fees / 3 + fees % 3);
+    assert_eq!(shares[&validators[1]], fees / 3);
+    assert_eq!(shares[&validators[2]], fees / 3);
+}
@@ -1,6 +1,7 @@
-use std::env;
+use std::{collections::HashSet, env};
 
 use serial_test::serial;
+use vrrb_core::transactions::{Transaction, TransactionDigest};
 use vrrbdb::{VrrbDb, VrrbDbConfig};
 mod common;
 
@@ -40,3 +41,42 @@ fn transactions_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn transactions_can_be_paged_through() {
+    let temp_dir_path = env::temp_dir();
+    let mut db = VrrbDb::new(
+        VrrbDbConfig::default().with_path(temp_dir_path.join(_generate_random_string())),
+    );
+
+    let txns: Vec<_> = (0..7)
+        .map(|_| _generate_random_valid_transaction())
+        .collect();
+    db.extend_transactions_unchecked(txns.clone());
+
+    let handle = db.transaction_store_factory().handle();
+    let mut cursor: Option<TransactionDigest> = None;
+    let mut page_sizes = vec![];
+    let mut paged = vec![];
+
+    loop {
+        let (page, next_cursor) = handle.get_page(cursor.clone(), 3).unwrap();
+        assert_eq!(handle.get_page(cursor, 3).unwrap().0, page);
+
+        page_sizes.push(page.len());
+        paged.extend(page.into_iter().map(|txn| txn.id()));
+
+        if next_cursor.is_none() {
+            break;
+        }
+        cursor = next_cursor;
+    }
+
+    let unique: HashSet<TransactionDigest> = paged.iter().cloned().collect();
+    let inserted: HashSet<TransactionDigest> = txns.iter().map(|txn| txn.id()).collect();
+
+    assert_eq!(page_sizes, vec![3, 3, 1]);
+    assert_eq!(unique.len(), paged.len());
+    assert_eq!(unique, inserted);
+}
@@ -1,60 +1,173 @@
+use std::{fmt, str::FromStr};
+
 use primitives::{get_pretty_print_logs, Environment};
 use thiserror::Error;
+use tracing::Subscriber;
 use tracing_subscriber::{
+    filter::LevelFilter,
     fmt::MakeWriter,
+    layer::SubscriberExt,
     util::{SubscriberInitExt, TryInitError},
+    Layer,
 };
 
+use crate::sampling::SpanSampler;
+
 #[derive(Debug, Error)]
 pub enum TelemetryError {
     #[error("failed to initialize: {0}")]
     Init(#[from] TryInitError),
+
+    #[error("unknown log format: {0}, expected pretty or json")]
+    UnknownLogFormat(String),
 }
 
 type Result<T> = std::result::Result<T, TelemetryError>;
 
-// TODO: figure out the proper generic sig to export a telemetry builder instead
+/// The format log events are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Multi-line, human readable output.
+    Pretty,
+    /// One JSON object per event, for log aggregators.
+    Json,
+}
+
+impl LogFormat {
+    /// Reads the format from the environment, defaulting to JSON unless
+    /// pretty printed logs were requested.
+    pub fn from_env() -> Self {
+        if get_pretty_print_logs() {
+            LogFormat::Pretty
+        } else {
+            LogFormat::Json
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = TelemetryError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(TelemetryError::UnknownLogFormat(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Pretty => write!(f, "pretty"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct TelemetrySubscriber {}
 
 impl TelemetrySubscriber {
+    pub fn builder() -> TelemetrySubscriberBuilder {
+        TelemetrySubscriberBuilder::default()
+    }
+
+    /// Initializes the global subscriber with the log format read from the
+    /// environment.
     pub fn init<W>(out: W) -> Result<()>
+    where
+        W: for<'s> MakeWriter<'s> + 'static + Sync + Send,
+    {
+        Self::builder().init(out)
+    }
+
+    /// Initializes the global subscriber, writing events to `out` in `format`.
+    pub fn init_with_format<W>(out: W, format: LogFormat) -> Result<()>
+    where
+        W: for<'s> MakeWriter<'s> + 'static + Sync + Send,
+    {
+        Self::builder().format(format).init(out)
+    }
+}
+
+#[derive(Debug)]
+pub struct TelemetrySubscriberBuilder {
+    format: LogFormat,
+    sampler: SpanSampler,
+}
+
+impl Default for TelemetrySubscriberBuilder {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::from_env(),
+            sampler: SpanSampler::new(),
+        }
+    }
+}
+
+impl TelemetrySubscriberBuilder {
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Records only `ratio` of the spans named `target` or recorded under the
+    /// `target` module, e.g. `sample_spans("handle", 0.01)` keeps 1 in 100
+    /// `handle` spans. Warnings and errors are always recorded.
+    pub fn sample_spans(mut self, target: impl Into<String>, ratio: f64) -> Self {
+        self.sampler = self.sampler.sample(target, ratio);
+        self
+    }
+
+    /// Initializes the global subscriber, writing events to `out`.
+    pub fn init<W>(self, out: W) -> Result<()>
+    where
+        W: for<'s> MakeWriter<'s> + 'static + Sync + Send,
+    {
+        self.build(out).try_init()?;
+
+        _set_panic_hook();
+
+        Ok(())
+    }
+
+    /// Builds a subscriber writing events to `out` without installing it.
+    ///
+    /// The JSON format emits one object per event, holding the timestamp,
+    /// level, target, the event's fields and the spans it was recorded in.
+    pub fn build<W>(self, out: W) -> impl Subscriber + Send + Sync
     where
         W: for<'s> MakeWriter<'s> + 'static + Sync + Send,
     {
         let environ = primitives::get_vrrb_environment();
         let is_local_env = matches!(environ, Environment::Local);
 
-        let pretty_print_logs = get_pretty_print_logs();
-
-        if pretty_print_logs {
-            let sub = tracing_subscriber::fmt()
+        let fmt_layer = match self.format {
+            LogFormat::Pretty => tracing_subscriber::fmt::layer()
                 .with_writer(out)
                 .with_file(is_local_env)
                 .with_line_number(is_local_env)
                 .with_target(is_local_env)
                 .compact()
                 .pretty()
-                .finish();
-
-            sub.try_init()?;
-        } else {
-            let sub = tracing_subscriber::fmt()
+                .boxed(),
+            LogFormat::Json => tracing_subscriber::fmt::layer()
                 .with_writer(out)
                 .with_file(is_local_env)
                 .with_line_number(is_local_env)
+                .with_target(true)
                 .json()
-                .with_current_span(false)
+                .with_current_span(true)
                 .flatten_event(true)
-                .with_span_list(false)
-                .finish();
+                .with_span_list(true)
+                .boxed(),
+        };
 
-            sub.try_init()?;
-        }
-
-        _set_panic_hook();
-
-        Ok(())
+        tracing_subscriber::registry()
+            .with(LevelFilter::INFO)
+            .with(fmt_layer.with_filter(self.sampler))
     }
 }
 
@@ -71,6 +184,11 @@ fn _set_panic_hook() {
 #[cfg(test)]
 mod tests {
 
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
     use tracing_subscriber::fmt::TestWriter;
 
     use super::*;
@@ -83,4 +201,92 @@ mod tests {
 
         tracing::info!("hello world 2");
     }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn json_format_emits_one_object_per_event() {
+        let buffer = Buffer::default();
+        let sub = TelemetrySubscriber::builder()
+            .format(LogFormat::Json)
+            .build(buffer.clone());
+
+        tracing::subscriber::with_default(sub, || {
+            let span = tracing::info_span!("block_mining", round = 7);
+            let _guard = span.enter();
+            tracing::warn!(txns = 3, "mined block");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1);
+
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert!(event["timestamp"].is_string());
+        assert_eq!(event["level"], "WARN");
+        assert_eq!(event["target"], module_path!());
+        assert_eq!(event["message"], "mined block");
+        assert_eq!(event["txns"], 3);
+        assert_eq!(event["span"]["name"], "block_mining");
+        assert_eq!(event["span"]["round"], 7);
+        assert_eq!(event["spans"][0]["name"], "block_mining");
+    }
+
+    #[test]
+    fn sampled_out_spans_are_dropped_while_errors_survive() {
+        let buffer = Buffer::default();
+        let sub = TelemetrySubscriber::builder()
+            .format(LogFormat::Json)
+            .sample_spans("handle", 0.0)
+            .build(buffer.clone());
+
+        tracing::subscriber::with_default(sub, || {
+            tracing::info_span!("handle").in_scope(|| tracing::info!("handled event"));
+            tracing::info_span!("mine").in_scope(|| tracing::info!("mined block"));
+            tracing::error_span!("handle").in_scope(|| tracing::error!("failed to handle"));
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0]["message"], "handled event");
+        assert!(events[0].get("span").is_none());
+
+        assert_eq!(events[1]["message"], "mined block");
+        assert_eq!(events[1]["span"]["name"], "mine");
+
+        assert_eq!(events[2]["message"], "failed to handle");
+        assert_eq!(events[2]["level"], "ERROR");
+        assert_eq!(events[2]["span"]["name"], "handle");
+    }
+
+    #[test]
+    fn log_format_parses_from_str() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("Pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
 }
@@ -4,6 +4,7 @@
 pub mod custom_subscriber;
 mod metrics;
 pub mod request_stats;
+pub mod sampling;
 #[cfg(test)]
 mod tests;
 pub use metrics::*;
@@ -29,6 +29,8 @@ struct StopWatch {
     start_ms: Option<u128>,
     /// End time for this specific stat.
     stop_ms: Option<u128>,
+    /// Whether the measured part of the request failed.
+    failed: bool,
 }
 
 impl StopWatch {
@@ -37,6 +39,7 @@ impl StopWatch {
         Ok(StopWatch {
             start_ms: Some(Self::now()?),
             stop_ms: None,
+            failed: false,
         })
     }
 
@@ -69,6 +72,59 @@ impl StopWatch {
         self.stop_ms = Some(Self::now()?);
         Ok(())
     }
+
+    fn is_running(&self) -> bool {
+        self.stop_ms.is_none()
+    }
+}
+
+/// Aggregated stats of every measurement sharing a label.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointStats {
+    pub label: String,
+    /// No of completed measurements, failed ones included.
+    pub count: usize,
+    /// No of completed measurements that failed.
+    pub errors: usize,
+    /// No of measurements started but not stopped yet.
+    pub in_flight: usize,
+    pub p50_ms: u128,
+    pub p95_ms: u128,
+    pub p99_ms: u128,
+}
+
+impl EndpointStats {
+    /// The fraction of completed measurements that failed.
+    pub fn error_rate(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.count as f64
+    }
+}
+
+/// A point in time view of the stats recorded by a [RequestStats] instance.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestStatsReport {
+    pub name: String,
+    pub instance: String,
+    /// Stats per label, in the order the labels were first started.
+    pub endpoints: Vec<EndpointStats>,
+}
+
+impl RequestStatsReport {
+    pub fn endpoint(&self, label: &str) -> Option<&EndpointStats> {
+        self.endpoints.iter().find(|stats| stats.label == label)
+    }
+}
+
+/// Nearest-rank percentile of sorted durations.
+fn percentile(sorted: &[u128], pct: usize) -> u128 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (pct * sorted.len() + 99) / 100;
+    sorted[rank.saturating_sub(1)]
 }
 
 #[derive(Debug)]
@@ -111,13 +167,80 @@ impl RequestStats {
 
     /// Stops measuring a started stat within this [RequestStats] instance. Only likely to fail in
     /// cases where we provide a named stat where the named stat doesn't already exist. The [name]
-    /// parameter should refer to a stat created by [self.start()].
+    /// parameter should refer to a stat created by [self.start()]. When the same stat was started
+    /// several times, the latest one still running is stopped.
     pub fn stop(&mut self, name: String) -> Result<()> {
-        if let Some(index) = self.labels.iter().position(|v| v == name.as_str()) {
+        if let Some(index) = self.running(&name) {
             self.values[index].stop()?;
         }
         Ok(())
     }
+
+    /// Stops measuring a started stat like [self.stop()], recording that it failed.
+    pub fn fail(&mut self, name: String) -> Result<()> {
+        if let Some(index) = self.running(&name) {
+            self.values[index].stop()?;
+            self.values[index].failed = true;
+        }
+        Ok(())
+    }
+
+    /// Aggregates the stats recorded so far by label, with the count, error rate and latency
+    /// percentiles of the completed measurements of each.
+    pub fn snapshot(&self) -> RequestStatsReport {
+        let mut endpoints: Vec<(EndpointStats, Vec<u128>)> = Vec::new();
+
+        for (label, stopwatch) in self.labels.iter().zip(self.values.iter()) {
+            let position = endpoints
+                .iter()
+                .position(|(stats, _)| &stats.label == label);
+            let (stats, durations) = match position {
+                Some(position) => &mut endpoints[position],
+                None => {
+                    let stats = EndpointStats {
+                        label: label.clone(),
+                        ..Default::default()
+                    };
+                    endpoints.push((stats, Vec::new()));
+                    endpoints.last_mut().unwrap()
+                }
+            };
+
+            match stopwatch.duration() {
+                Ok(duration) => {
+                    stats.count += 1;
+                    stats.errors += stopwatch.failed as usize;
+                    durations.push(duration);
+                }
+                Err(_) if stopwatch.is_running() => stats.in_flight += 1,
+                Err(_) => {}
+            }
+        }
+
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(mut stats, mut durations)| {
+                durations.sort_unstable();
+                stats.p50_ms = percentile(&durations, 50);
+                stats.p95_ms = percentile(&durations, 95);
+                stats.p99_ms = percentile(&durations, 99);
+                stats
+            })
+            .collect();
+
+        RequestStatsReport {
+            name: self.name.clone(),
+            instance: self.instance.clone(),
+            endpoints,
+        }
+    }
+
+    fn running(&self, name: &str) -> Option<usize> {
+        self.labels
+            .iter()
+            .zip(self.values.iter())
+            .rposition(|(label, stopwatch)| label == name && stopwatch.is_running())
+    }
 }
 
 impl Drop for RequestStats {
@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tracing::{Level, Metadata};
+use tracing_subscriber::layer::{Context, Filter};
+
+/// Keeps a fraction of the spans matching a target, e.g. 1 in 100 `handle`
+/// spans, to bound the volume of telemetry produced under load.
+#[derive(Debug)]
+struct SamplingRule {
+    target: String,
+    ratio: f64,
+    seen: AtomicU64,
+}
+
+impl SamplingRule {
+    /// A rule matches spans named `target` as well as every span recorded in
+    /// a module under `target`.
+    fn matches(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.name() == self.target || metadata.target().starts_with(&self.target)
+    }
+
+    /// Spreads the kept spans evenly: the n-th matching span is kept whenever
+    /// it brings the number of kept spans up to `n * ratio`.
+    fn sample(&self) -> bool {
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+
+        ((seen + 1.0) * self.ratio).floor() > (seen * self.ratio).floor()
+    }
+}
+
+/// A per-layer filter sampling spans by target.
+///
+/// Spans at `WARN` level or above are always recorded, as are spans that
+/// match no rule. Events are never sampled out, but events recorded within a
+/// dropped span carry no context of it.
+#[derive(Debug, Default)]
+pub struct SpanSampler {
+    rules: Vec<SamplingRule>,
+}
+
+impl SpanSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records only `ratio` of the spans matching `target`, where `ratio` is
+    /// clamped between 0.0, dropping every span, and 1.0, keeping them all.
+    ///
+    /// When several rules match a span, the first one added applies.
+    pub fn sample(mut self, target: impl Into<String>, ratio: f64) -> Self {
+        self.rules.push(SamplingRule {
+            target: target.into(),
+            ratio: ratio.clamp(0.0, 1.0),
+            seen: AtomicU64::new(0),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl<S> Filter<S> for SpanSampler {
+    fn enabled(&self, metadata: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        if !metadata.is_span() || *metadata.level() <= Level::WARN {
+            return true;
+        }
+
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(metadata))
+            .map_or(true, SamplingRule::sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept(ratio: f64, spans: usize) -> usize {
+        let sampler = SpanSampler::new().sample("handle", ratio);
+        let rule = &sampler.rules[0];
+
+        (0..spans).filter(|_| rule.sample()).count()
+    }
+
+    #[test]
+    fn ratio_bounds_the_number_of_kept_spans() {
+        assert_eq!(kept(0.0, 100), 0);
+        assert_eq!(kept(0.25, 100), 25);
+        assert_eq!(kept(1.0, 100), 100);
+        assert_eq!(kept(7.0, 100), 100);
+    }
+}
@@ -14,3 +14,40 @@ fn req_stats_ok() {
     sleep(Duration::from_secs(3));
     stats.stop("3sec".to_string()).expect("Failed to stop");
 }
+
+#[test]
+fn req_stats_snapshot_aggregates_by_label() {
+    let mut stats = RequestStats::new("Test".to_string(), "snapshot".to_string())
+        .expect("Failed to create new stats object");
+    for _ in 0..9 {
+        stats.start("fast".to_string()).expect("Failed to start");
+        sleep(Duration::from_millis(5));
+        stats.stop("fast".to_string()).expect("Failed to stop");
+    }
+    stats.start("fast".to_string()).expect("Failed to start");
+    sleep(Duration::from_millis(60));
+    stats.fail("fast".to_string()).expect("Failed to stop");
+    stats.start("slow".to_string()).expect("Failed to start");
+
+    let report = stats.snapshot();
+    assert_eq!(report.name, "Test");
+    assert_eq!(report.endpoints.len(), 3);
+
+    let total = report.endpoint("total").unwrap();
+    assert_eq!(total.count, 0);
+    assert_eq!(total.in_flight, 1);
+
+    let fast = report.endpoint("fast").unwrap();
+    assert_eq!(fast.count, 10);
+    assert_eq!(fast.errors, 1);
+    assert_eq!(fast.in_flight, 0);
+    assert!((fast.error_rate() - 0.1).abs() < f64::EPSILON);
+    assert!((5..60).contains(&fast.p50_ms));
+    assert!(fast.p95_ms >= 60);
+    assert!(fast.p99_ms >= 60);
+
+    let slow = report.endpoint("slow").unwrap();
+    assert_eq!(slow.count, 0);
+    assert_eq!(slow.in_flight, 1);
+    assert_eq!(slow.p99_ms, 0);
+}
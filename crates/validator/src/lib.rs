@@ -1,6 +1,7 @@
 // pub mod mempool_processor;
 pub mod claim_validator;
 pub mod result;
+pub mod signature_cache;
 pub mod txn_validator;
 pub mod validator_core;
 pub mod validator_core_manager;
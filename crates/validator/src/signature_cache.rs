@@ -0,0 +1,206 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use sha2::{Digest, Sha256};
+use vrrb_core::{
+    cache::Cache,
+    transactions::{Transaction, TransactionKind},
+};
+
+use crate::txn_validator::Result;
+
+/// Number of signature verifications a node remembers by default.
+pub const DEFAULT_SIGNATURE_CACHE_CAPACITY: usize = 10_000;
+
+/// Time in milliseconds after which a remembered verification is dropped.
+pub const DEFAULT_SIGNATURE_CACHE_TTL: u64 = 10 * 60 * 1000;
+
+/// Hash of the signed payload, the sender's public key and the signature.
+/// Together they determine the outcome of a verification, unlike the
+/// transaction id, which is taken as is from the wire.
+type SignatureKey = ([u8; 32], [u8; 33], [u8; 64]);
+
+/// Remembers the outcome of transaction signature verifications, so that a
+/// transaction seen again while it is validated, voted on and included in a
+/// block has its signature checked only once.
+///
+/// The cache is bounded, least recently used entries are evicted first, and
+/// clones share the same entries, so it can be handed to every validator core
+/// of a node.
+#[derive(Debug, Clone)]
+pub struct SignatureCache {
+    entries: Arc<Mutex<Cache<SignatureKey, Result<()>>>>,
+    verifications: Arc<AtomicUsize>,
+}
+
+impl Default for SignatureCache {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_SIGNATURE_CACHE_CAPACITY,
+            DEFAULT_SIGNATURE_CACHE_TTL,
+        )
+    }
+}
+
+impl SignatureCache {
+    pub fn new(capacity: usize, ttl: u64) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Cache::new(capacity, ttl))),
+            verifications: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Returns the remembered outcome of verifying the signature of `txn`,
+    /// calling `verify` and remembering its outcome if there's none.
+    pub fn verify_with(
+        &self,
+        txn: &TransactionKind,
+        verify: impl FnOnce(&TransactionKind) -> Result<()>,
+    ) -> Result<()> {
+        let key = Self::key(txn);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(outcome) = entries.get(&key) {
+                return outcome.clone();
+            }
+        }
+
+        self.verifications.fetch_add(1, Ordering::Relaxed);
+        let outcome = verify(txn);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(key, outcome.clone());
+        }
+
+        outcome
+    }
+
+    fn key(txn: &TransactionKind) -> SignatureKey {
+        let mut payload_hash = [0u8; 32];
+        payload_hash.copy_from_slice(&Sha256::digest(txn.build_payload().as_bytes()));
+
+        (
+            payload_hash,
+            txn.sender_public_key().serialize(),
+            txn.signature().serialize_compact(),
+        )
+    }
+
+    /// Number of signatures that were verified, as opposed to being served
+    /// from the cache.
+    pub fn verifications(&self) -> usize {
+        self.verifications.load(Ordering::Relaxed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use primitives::Address;
+    use secp256k1::Message;
+    use vrrb_core::{
+        keypair::KeyPair,
+        transactions::{NewTransferArgs, Transfer},
+    };
+
+    use super::*;
+    use crate::txn_validator::TxnValidatorError;
+
+    fn signed_txn(nonce: u128) -> TransactionKind {
+        let sender_kp = KeyPair::random();
+        let recv_kp = KeyPair::random();
+        let message = Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb");
+
+        let mut txn = TransactionKind::Transfer(Transfer::new(NewTransferArgs {
+            timestamp: 1,
+            sender_address: Address::new(*sender_kp.get_miner_public_key()),
+            sender_public_key: *sender_kp.get_miner_public_key(),
+            receiver_address: Address::new(*recv_kp.get_miner_public_key()),
+            token: None,
+            amount: 0,
+            signature: sender_kp.get_miner_secret_key().sign_ecdsa(message),
+            validators: Some(HashMap::new()),
+            nonce,
+        }));
+        txn.sign(sender_kp.get_miner_secret_key());
+
+        txn
+    }
+
+    #[test]
+    fn verification_outcomes_are_memoized() {
+        let cache = SignatureCache::default();
+        let valid = signed_txn(1);
+        let invalid = signed_txn(2);
+        let reject = |_: &TransactionKind| Err(TxnValidatorError::TxnSignatureIncorrect("".into()));
+
+        for _ in 0..3 {
+            assert!(cache.verify_with(&valid, |_| Ok(())).is_ok());
+            assert!(cache.verify_with(&invalid, reject).is_err());
+        }
+
+        assert_eq!(cache.verifications(), 2);
+        assert_eq!(cache.len(), 2);
+
+        // the remembered outcome wins over a verifier that would disagree
+        assert!(cache.verify_with(&valid, reject).is_ok());
+    }
+
+    #[test]
+    fn tampered_txns_do_not_share_a_verified_outcome() {
+        let cache = SignatureCache::default();
+        let valid = signed_txn(1);
+        let reject = |_: &TransactionKind| Err(TxnValidatorError::TxnSignatureIncorrect("".into()));
+
+        assert!(cache.verify_with(&valid, |_| Ok(())).is_ok());
+
+        // same id and signature, different amount
+        let TransactionKind::Transfer(transfer) = &valid;
+        let mut tampered = transfer.clone();
+        tampered.amount = 1_000;
+        let tampered = TransactionKind::Transfer(tampered);
+        assert_eq!(tampered.id(), valid.id());
+
+        assert!(cache.verify_with(&tampered, reject).is_err());
+        assert_eq!(cache.verifications(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_entries_are_evicted_when_full() {
+        let cache = SignatureCache::new(2, DEFAULT_SIGNATURE_CACHE_TTL);
+        let txns: Vec<_> = (0..3).map(signed_txn).collect();
+
+        for txn in txns.iter() {
+            cache.verify_with(txn, |_| Ok(())).unwrap();
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.verifications(), 3);
+
+        cache.verify_with(&txns[2], |_| Ok(())).unwrap();
+        assert_eq!(cache.verifications(), 3);
+
+        cache.verify_with(&txns[0], |_| Ok(())).unwrap();
+        assert_eq!(cache.verifications(), 4);
+    }
+}
@@ -2,7 +2,12 @@ use std::result::Result as StdResult;
 
 use sha2::{Digest, Sha256};
 use storage::vrrbdb::StateStoreReadHandleFactory;
-use vrrb_core::transactions::{Transaction, TransactionKind};
+use vrrb_core::{
+    account::Account,
+    transactions::{Transaction, TransactionKind},
+};
+
+use crate::signature_cache::SignatureCache;
 
 pub type Result<T> = StdResult<T, TxnValidatorError>;
 
@@ -62,12 +67,22 @@ pub enum TxnValidatorError {
 
 #[derive(Debug, Clone, Default)]
 // TODO: make validator configurable
-pub struct TxnValidator;
+pub struct TxnValidator {
+    signature_cache: Option<SignatureCache>,
+}
 
 impl TxnValidator {
     /// Creates a new Txn validator
     pub fn new() -> TxnValidator {
-        TxnValidator
+        TxnValidator::default()
+    }
+
+    /// Creates a Txn validator that looks up signature verifications in
+    /// `signature_cache` before verifying them
+    pub fn with_signature_cache(signature_cache: SignatureCache) -> TxnValidator {
+        TxnValidator {
+            signature_cache: Some(signature_cache),
+        }
     }
 
     /// An entire Txn validator
@@ -80,6 +95,15 @@ impl TxnValidator {
         self.validate_structure(state_reader, txn)
     }
 
+    /// Validates `txn` against its `sender` account, fetched beforehand by
+    /// callers validating several transactions of the same sender.
+    pub fn validate_with_sender(&self, sender: &Account, txn: &TransactionKind) -> Result<()> {
+        self.validate_sender_balance(sender, txn)
+            .and_then(|_| self.validate_public_key(txn))
+            .and_then(|_| self.validate_signature(txn))
+            .and_then(|_| self.validate_timestamp(txn))
+    }
+
     /// An entire Txn structure validator
     pub fn validate_structure(
         &self,
@@ -96,6 +120,13 @@ impl TxnValidator {
 
     /// Txn signature validator.
     pub fn validate_signature(&self, txn: &TransactionKind) -> Result<()> {
+        match &self.signature_cache {
+            Some(cache) => cache.verify_with(txn, Self::verify_signature),
+            None => Self::verify_signature(txn),
+        }
+    }
+
+    fn verify_signature(txn: &TransactionKind) -> Result<()> {
         let mut hasher = Sha256::new();
         hasher.update(txn.build_payload().as_bytes());
         let result = hasher.finalize().to_vec();
@@ -172,7 +203,13 @@ impl TxnValidator {
             .handle()
             .get(&address)
             .map_err(|_| TxnValidatorError::SenderAddressIncorrect)?;
-        if (account.credits() - account.debits())
+
+        self.validate_sender_balance(&account, txn)
+    }
+
+    /// Checks that the balance of `sender` covers the amount of `txn`.
+    pub fn validate_sender_balance(&self, sender: &Account, txn: &TransactionKind) -> Result<()> {
+        if (sender.credits() - sender.debits())
             .checked_sub(txn.amount())
             .is_none()
         {
@@ -1,12 +1,19 @@
-use std::{collections::HashSet, sync::mpsc::RecvError};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::mpsc::RecvError,
+};
 
 use mempool::MempoolReadHandleFactory;
+use primitives::Address;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use storage::vrrbdb::StateStoreReadHandleFactory;
-use vrrb_core::transactions::TransactionKind;
-use vrrb_core::{claim::Claim, transactions::TransactionDigest};
+use vrrb_core::transactions::{Transaction, TransactionKind};
+use vrrb_core::{account::Account, claim::Claim, transactions::TransactionDigest};
 
-use crate::{claim_validator::ClaimValidator, txn_validator::TxnValidator};
+use crate::{
+    claim_validator::ClaimValidator,
+    txn_validator::{TxnValidator, TxnValidatorError},
+};
 
 /// Enum containing all messages related to controling the Core thread's
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -84,6 +91,46 @@ impl Core {
         Err(crate::txn_validator::TxnValidatorError::NotFound)
     }
 
+    /// Validates the mempool transactions behind `digests` in one pass,
+    /// reading the account of every sender from state only once. Digests
+    /// missing from the mempool are reported as `NotFound`.
+    pub fn process_transaction_batch(
+        &self,
+        digests: &[TransactionDigest],
+        mempool_reader: MempoolReadHandleFactory,
+        state_reader: StateStoreReadHandleFactory,
+    ) -> Vec<(
+        TransactionDigest,
+        crate::txn_validator::Result<TransactionKind>,
+    )> {
+        let state = state_reader.handle();
+        let mut senders: HashMap<Address, Option<Account>> = HashMap::new();
+
+        digests
+            .iter()
+            .map(|digest| {
+                let Some(record) = mempool_reader.get(digest) else {
+                    return (digest.clone(), Err(TxnValidatorError::NotFound));
+                };
+
+                let txn = record.txn;
+                let sender = senders
+                    .entry(txn.sender_address())
+                    .or_insert_with_key(|address| state.get(address).ok());
+
+                let result = match sender {
+                    Some(sender) => self
+                        .txn_validator
+                        .validate_with_sender(sender, &txn)
+                        .map(|_| txn),
+                    None => Err(TxnValidatorError::SenderAddressIncorrect),
+                };
+
+                (digest.clone(), result)
+            })
+            .collect()
+    }
+
     pub fn process_transactions(
         &self,
         batch: Vec<TransactionKind>,
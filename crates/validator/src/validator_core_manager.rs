@@ -9,6 +9,7 @@ use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 use crate::{
     claim_validator::ClaimValidator,
     result::{Result, ValidatorError},
+    signature_cache::SignatureCache,
     txn_validator::TxnValidator,
     validator_core::{Core, CoreId},
 };
@@ -23,6 +24,7 @@ pub struct ValidatorCoreManager {
     mempool_reader: MempoolReadHandleFactory,
     state_reader: StateStoreReadHandleFactory,
     claim_reader: ClaimStoreReadHandleFactory,
+    signature_cache: SignatureCache,
 }
 
 impl Clone for ValidatorCoreManager {
@@ -34,12 +36,14 @@ impl Clone for ValidatorCoreManager {
         let mempool_reader = self.mempool_reader.clone();
         let state_reader = self.state_reader.clone();
         let claim_reader = self.claim_reader.clone();
+        let signature_cache = self.signature_cache.clone();
 
         Self {
             core_pool,
             mempool_reader,
             state_reader,
             claim_reader,
+            signature_cache,
         }
     }
 }
@@ -63,6 +67,7 @@ impl ValidatorCoreManager {
             mempool_reader,
             state_reader,
             claim_reader,
+            signature_cache: SignatureCache::default(),
         })
     }
 
@@ -75,13 +80,32 @@ impl ValidatorCoreManager {
         self.core_pool.install(|| {
             let valcore = Core::new(
                 self.core_pool.current_thread_index().unwrap_or(0) as CoreId,
-                TxnValidator::new(),
+                TxnValidator::with_signature_cache(self.signature_cache.clone()),
                 ClaimValidator,
             );
             valcore.process_transaction_kind(transaction, mempool_reader, state_reader)
         })
     }
 
+    pub fn validate_transaction_batch(
+        &mut self,
+        transactions: &[TransactionDigest],
+        mempool_reader: MempoolReadHandleFactory,
+        state_reader: StateStoreReadHandleFactory,
+    ) -> Vec<(
+        TransactionDigest,
+        crate::txn_validator::Result<TransactionKind>,
+    )> {
+        self.core_pool.install(|| {
+            let valcore = Core::new(
+                self.core_pool.current_thread_index().unwrap_or(0) as CoreId,
+                TxnValidator::with_signature_cache(self.signature_cache.clone()),
+                ClaimValidator,
+            );
+            valcore.process_transaction_batch(transactions, mempool_reader, state_reader)
+        })
+    }
+
     pub fn validate(
         &mut self,
         batch: Vec<TransactionKind>,
@@ -92,13 +116,18 @@ impl ValidatorCoreManager {
         self.core_pool.install(|| {
             let valcore = Core::new(
                 self.core_pool.current_thread_index().unwrap_or(0) as CoreId,
-                TxnValidator::new(),
+                TxnValidator::with_signature_cache(self.signature_cache.clone()),
                 ClaimValidator,
             );
             valcore.process_transactions(batch, mempool_reader, state_reader)
         })
     }
 
+    /// Signature verifications shared by every core of this manager.
+    pub fn signature_cache(&self) -> &SignatureCache {
+        &self.signature_cache
+    }
+
     pub fn validate_claims(
         &mut self,
         claims: Vec<Claim>,
@@ -106,7 +135,7 @@ impl ValidatorCoreManager {
         self.core_pool.install(|| {
             let valcore = Core::new(
                 self.core_pool.current_thread_index().unwrap_or(0) as CoreId,
-                TxnValidator::new(),
+                TxnValidator::with_signature_cache(self.signature_cache.clone()),
                 ClaimValidator,
             );
             valcore.process_claims(claims)
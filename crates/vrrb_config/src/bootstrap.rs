@@ -12,6 +12,10 @@ pub struct BootstrapConfig {
     pub additional_genesis_receivers: Option<Vec<Address>>,
     /// Optional Genesis Quorum configuration used to bootstrap a new quorum
     pub bootstrap_quorum_config: BootstrapQuorumConfig,
+    /// Seed used to assign the bootstrap quorum members to quorums. Bootstrap
+    /// nodes sharing a seed assign a given set of peers identically
+    #[serde(default)]
+    pub quorum_assignment_seed: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1,6 +1,9 @@
 mod bootstrap;
 pub mod bootstrap_quorum;
+mod mempool;
+mod mempool_pressure;
 mod node_config;
+mod proposal_fairness;
 pub mod quorum;
 pub mod result;
 pub mod test_utils;
@@ -8,7 +11,10 @@ pub mod threshold_config;
 
 pub use bootstrap::*;
 pub use bootstrap_quorum::*;
+pub use mempool::*;
+pub use mempool_pressure::*;
 pub use node_config::*;
+pub use proposal_fairness::*;
 pub use quorum::*;
 pub use result::*;
 pub use test_utils::*;
@@ -73,4 +79,51 @@ mod tests {
         let valid_config = valid_threshold_config();
         valid_config.validate().unwrap();
     }
+
+    #[test]
+    fn threshold_config_rejects_thresholds_without_fault_tolerance() {
+        for (upper_bound, threshold) in [(4, 4), (4, 2), (6, 2), (9, 3)] {
+            let config = ThresholdConfig {
+                upper_bound,
+                threshold,
+            };
+
+            assert!(config.validate().is_err());
+        }
+
+        ThresholdConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn mempool_config_rejects_zero_max_capacity() {
+        let config = MempoolConfig {
+            max_capacity: Some(0),
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_err());
+        MempoolConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn threshold_config_is_derived_from_quorum_size() {
+        let expected = [(4, 1), (5, 1), (6, 1), (7, 2), (10, 3), (13, 4), (100, 33)];
+
+        for (quorum_size, threshold) in expected {
+            let config = ThresholdConfig::for_quorum_size(quorum_size).unwrap();
+
+            assert_eq!(config.upper_bound as usize, quorum_size);
+            assert_eq!(config.threshold, threshold);
+            config.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn threshold_config_rejects_quorum_size_below_bft_minimum() {
+        for quorum_size in 0..ThresholdConfig::BFT_MINIMUM_NODES {
+            assert!(ThresholdConfig::for_quorum_size(quorum_size).is_err());
+        }
+
+        assert!(ThresholdConfig::for_quorum_size(u16::MAX as usize + 1).is_err());
+    }
 }
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_INITIAL_MEMPOOL_CAPACITY: usize = 10000;
+
+/// Sizing of a `LeftRightMempool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolConfig {
+    /// Number of transactions the pool has room for before it reallocates.
+    pub initial_capacity: usize,
+    /// Maximum number of transactions the pool holds. Insertions that would
+    /// grow it past this are rejected with `MempoolError::Full`. Unbounded
+    /// when `None`.
+    pub max_capacity: Option<usize>,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        MempoolConfig {
+            initial_capacity: DEFAULT_INITIAL_MEMPOOL_CAPACITY,
+            max_capacity: None,
+        }
+    }
+}
+
+impl MempoolConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_capacity == Some(0) {
+            return Err(ConfigError::Other(
+                "mempool max_capacity must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
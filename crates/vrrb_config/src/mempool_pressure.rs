@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+/// Watermarks, in number of pending transactions, used to signal mempool
+/// pressure to the rest of the node.
+///
+/// Pressure is signaled once the mempool grows past `high_watermark` and
+/// relieved once it shrinks below `low_watermark`. The gap between the two
+/// keeps a mempool hovering around a single threshold from flapping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
+pub struct MempoolPressureConfig {
+    pub high_watermark: usize,
+    pub low_watermark: usize,
+}
+
+impl Default for MempoolPressureConfig {
+    fn default() -> Self {
+        MempoolPressureConfig {
+            high_watermark: 10_000,
+            low_watermark: 8_000,
+        }
+    }
+}
+
+impl MempoolPressureConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.low_watermark >= self.high_watermark {
+            return Err(ConfigError::Other(format!(
+                "mempool low_watermark must be below high_watermark, got {} and {}",
+                self.low_watermark, self.high_watermark
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -5,16 +5,31 @@ use std::{
 };
 
 use derive_builder::Builder;
-use primitives::{KademliaPeerId, NodeId, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
+use primitives::{Address, KademliaPeerId, NodeId, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use vrrb_core::keypair::Keypair;
 
 use crate::{
-    bootstrap::BootstrapConfig, BootstrapPeerData, QuorumMember, QuorumMembershipConfig,
-    ThresholdConfig,
+    bootstrap::BootstrapConfig, BootstrapPeerData, MempoolConfig, MempoolPressureConfig,
+    ProposalFairnessConfig, QuorumMember, QuorumMembershipConfig, ThresholdConfig,
 };
 
+/// Default time harvesters wait for a block to gather a threshold of partial
+/// signatures before its certificate is considered timed out.
+pub const DEFAULT_SIGNATURE_COLLECTION_DEADLINE: Duration = Duration::from_secs(30);
+
+fn default_signature_collection_deadline() -> Duration {
+    DEFAULT_SIGNATURE_COLLECTION_DEADLINE
+}
+
+/// Amount credited to an address in the genesis block
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct GenesisAllocation {
+    pub address: Address,
+    pub amount: u128,
+}
+
 #[derive(Builder, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct NodeConfig {
     /// UUID that identifies each node
@@ -72,6 +87,13 @@ pub struct NodeConfig {
     /// Address the node listens for JSON-RPC connections
     pub jsonrpc_server_address: SocketAddr,
 
+    /// Loopback address the node serves operator only JSON-RPC methods on,
+    /// like `admin_setMaintenanceMode`. The admin server is disabled when
+    /// unset
+    #[builder(default)]
+    #[serde(default)]
+    pub admin_jsonrpc_server_address: Option<SocketAddr>,
+
     // TODO: refactor env-aware options
     #[builder(default = "false")]
     pub preload_mock_state: bool,
@@ -99,6 +121,12 @@ pub struct NodeConfig {
     /// Disables all broadcasting or listening capabilities of the node
     pub disable_networking: bool,
 
+    /// Stops the node from producing blocks and signing on behalf of its
+    /// quorum while it keeps following the chain
+    #[builder(default = "false")]
+    #[serde(default)]
+    pub maintenance_mode: bool,
+
     #[builder(default = "false")]
     /// Enables block and transaction indexing via webhook calls to external
     /// services
@@ -106,6 +134,62 @@ pub struct NodeConfig {
 
     pub threshold_config: ThresholdConfig,
 
+    /// Policy used to balance fee priority and transaction age when building
+    /// proposal blocks
+    #[builder(default)]
+    #[serde(default)]
+    pub proposal_fairness: ProposalFairnessConfig,
+
+    /// Initial and maximum capacity of the node's mempool
+    #[builder(default)]
+    #[serde(default)]
+    pub mempool: MempoolConfig,
+
+    /// Mempool occupancy above which the node signals pressure, and below
+    /// which it signals relief
+    #[builder(default)]
+    #[serde(default)]
+    pub mempool_pressure: MempoolPressureConfig,
+
+    /// Total supply the genesis allocations plus the genesis reward must add
+    /// up to. Genesis allocations are not checked if unset
+    #[builder(default)]
+    #[serde(default)]
+    pub genesis_total_supply: Option<u128>,
+
+    /// Addresses credited in the genesis block. Every node derives the
+    /// genesis rewards from this list, so it must be the same network wide.
+    /// Whitelisted nodes and additional genesis receivers are credited
+    /// instead when it's empty, or the genesis miner when there are none
+    #[builder(default)]
+    #[serde(default)]
+    pub genesis_allocations: Vec<GenesisAllocation>,
+
+    /// How long harvesters wait for a block to gather a threshold of partial
+    /// signatures before reporting its certificate as timed out
+    #[builder(default = "DEFAULT_SIGNATURE_COLLECTION_DEADLINE")]
+    #[serde(default = "default_signature_collection_deadline")]
+    pub signature_collection_deadline: Duration,
+
+    /// How long publishing an event waits for room in the node's event
+    /// channel before failing. Publishing waits indefinitely when unset
+    #[builder(default)]
+    #[serde(default)]
+    pub event_send_timeout: Option<Duration>,
+
+    /// Persists consensus-critical events to a write-ahead log in `data_dir`
+    /// before the node handles them, and replays the ones left unhandled on
+    /// startup
+    #[builder(default = "false")]
+    #[serde(default)]
+    pub enable_event_wal: bool,
+
+    /// How long significant events are kept in the event audit log in
+    /// `data_dir`. The audit log is disabled when unset
+    #[builder(default)]
+    #[serde(default)]
+    pub event_audit_log_retention: Option<Duration>,
+
     pub whitelisted_nodes: Vec<QuorumMember>,
 
     /// The IP address for binding Prometheus in the Versatus Protocol.
@@ -132,6 +216,16 @@ impl NodeConfig {
         &self.data_dir
     }
 
+    /// Path of the write-ahead log used when `enable_event_wal` is set
+    pub fn event_wal_path(&self) -> PathBuf {
+        self.data_dir.join("events.wal")
+    }
+
+    /// Path of the audit log used when `event_audit_log_retention` is set
+    pub fn event_audit_log_path(&self) -> PathBuf {
+        self.data_dir.join("events.audit")
+    }
+
     /// Indicates whether the node created with this config is a bootstrap node
     pub fn is_bootstrap(&self) -> bool {
         self.node_type == NodeType::Bootstrap
@@ -158,6 +252,7 @@ impl NodeConfig {
             http_api_version: self.http_api_version.clone(),
             http_api_shutdown_timeout: self.http_api_shutdown_timeout,
             jsonrpc_server_address: self.jsonrpc_server_address,
+            admin_jsonrpc_server_address: self.admin_jsonrpc_server_address,
             preload_mock_state: self.preload_mock_state,
             bootstrap_config: self.bootstrap_config.clone(),
             keypair: self.keypair.clone(),
@@ -194,6 +289,7 @@ impl Default for NodeConfig {
             http_api_version: String::from("v.0.1.0"),
             http_api_shutdown_timeout: None,
             jsonrpc_server_address: ipv4_localhost_with_random_port,
+            admin_jsonrpc_server_address: None,
             preload_mock_state: false,
             bootstrap_config: None,
             bootstrap_peer_data: None,
@@ -201,7 +297,17 @@ impl Default for NodeConfig {
             keypair: Keypair::random(),
             enable_ui: false,
             disable_networking: false,
+            maintenance_mode: false,
             threshold_config: ThresholdConfig::default(),
+            proposal_fairness: ProposalFairnessConfig::default(),
+            mempool: MempoolConfig::default(),
+            mempool_pressure: MempoolPressureConfig::default(),
+            genesis_total_supply: None,
+            genesis_allocations: vec![],
+            signature_collection_deadline: DEFAULT_SIGNATURE_COLLECTION_DEADLINE,
+            event_send_timeout: None,
+            enable_event_wal: false,
+            event_audit_log_retention: None,
             enable_block_indexing: false,
             whitelisted_nodes: vec![],
             prometheus_bind_addr: String::from("127.0.0.1"),
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+/// Controls how harvesters pick transactions when building proposal blocks.
+///
+/// Transactions are included by fee, highest first, but a share of every
+/// block is reserved for the oldest pending transactions regardless of their
+/// fee so that low-fee senders aren't starved indefinitely.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
+pub struct ProposalFairnessConfig {
+    /// Percentage of each proposal block reserved for the oldest pending
+    /// transactions.
+    pub aged_txn_reserve_percent: u8,
+}
+
+impl Default for ProposalFairnessConfig {
+    fn default() -> Self {
+        ProposalFairnessConfig {
+            aged_txn_reserve_percent: 20,
+        }
+    }
+}
+
+impl ProposalFairnessConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.aged_txn_reserve_percent > 100 {
+            return Err(ConfigError::Other(format!(
+                "aged_txn_reserve_percent must be between 0 and 100, got {}",
+                self.aged_txn_reserve_percent
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Number of slots out of `batch_size` reserved for aged transactions,
+    /// rounded up so that any non-zero reserve guarantees at least one slot.
+    pub fn aged_slots(&self, batch_size: usize) -> usize {
+        let percent = self.aged_txn_reserve_percent.min(100) as usize;
+        (batch_size * percent).div_ceil(100)
+    }
+}
@@ -12,7 +12,7 @@ impl Default for ThresholdConfig {
     fn default() -> Self {
         ThresholdConfig {
             upper_bound: 4,
-            threshold: 2,
+            threshold: 1,
         }
     }
 }
@@ -20,6 +20,35 @@ impl Default for ThresholdConfig {
 impl ThresholdConfig {
     const MINIMUM_NODES: u16 = 2;
 
+    /// Minimum quorum size able to tolerate a single faulty node under the
+    /// hbbft BFT model (`n >= 3t + 1`).
+    pub const BFT_MINIMUM_NODES: usize = 4;
+
+    /// Derives the BFT threshold for a quorum of `quorum_size` nodes, i.e. the
+    /// maximum number of faulty nodes it can tolerate, `t = floor((n - 1) / 3)`.
+    pub fn for_quorum_size(quorum_size: usize) -> crate::Result<ThresholdConfig> {
+        if quorum_size < ThresholdConfig::BFT_MINIMUM_NODES {
+            return Err(ConfigError::Other(format!(
+                "quorum size {} is below the BFT minimum of {}",
+                quorum_size,
+                ThresholdConfig::BFT_MINIMUM_NODES
+            )));
+        }
+
+        let upper_bound = u16::try_from(quorum_size).map_err(|_| {
+            ConfigError::Other(format!("quorum size {quorum_size} exceeds {}", u16::MAX))
+        })?;
+
+        let threshold_config = ThresholdConfig {
+            upper_bound,
+            threshold: (upper_bound - 1) / 3,
+        };
+
+        threshold_config.validate()?;
+
+        Ok(threshold_config)
+    }
+
     pub fn validate(&self) -> crate::Result<()> {
         if self.upper_bound < ThresholdConfig::MINIMUM_NODES || self.upper_bound == u16::MAX {
             return Err(ConfigError::Other(format!(
@@ -35,6 +64,14 @@ impl ThresholdConfig {
                 self.upper_bound.clone()
             )));
         }
+        if (self.upper_bound as u32) < 3 * (self.threshold as u32) + 1 {
+            return Err(ConfigError::Other(format!(
+                "DKG threshold {} leaves no fault tolerance for {} nodes, at least {} are required",
+                self.threshold,
+                self.upper_bound,
+                3 * (self.threshold as u32) + 1
+            )));
+        }
         Ok(())
     }
 }
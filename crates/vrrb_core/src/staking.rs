@@ -5,6 +5,7 @@ use thiserror::Error;
 use utils::hash_data;
 
 use crate::keypair::{MinerPublicKey, MinerSecretKey};
+use crate::transactions::TransactionDigest;
 
 /// Represents a byte array that can be converted into a
 /// ThresholdSignature
@@ -202,6 +203,12 @@ impl Stake {
         hash_data!(self.pubkey, self.from, self.to, self.amount, self.timestamp).to_vec()
     }
 
+    /// Returns the digest identifying this stake, which is recorded among
+    /// the stake digests of the staker's account once the stake is applied.
+    pub fn digest(&self) -> TransactionDigest {
+        TransactionDigest::from(self.get_payload())
+    }
+
     /// Returns the instances certificate, if there is one.
     /// The certificate is a Threshold Signature that Farmer
     /// nodes use to ensure a given threshold of validators have
@@ -6,7 +6,7 @@ use primitives::{
 };
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
@@ -105,13 +105,48 @@ impl QuorumCertifiedTxn {
     ) -> QuorumCertifiedTxn {
         QuorumCertifiedTxn {
             sender_farmer_id,
-            votes,
+            votes: dedup_vote_receipts(votes),
             txn,
             signature,
             is_txn_valid,
         }
     }
 
+    pub fn votes(&self) -> &[VoteReceipt] {
+        &self.votes
+    }
+
+    /// Checks that the certificate carries at least `threshold` votes, each
+    /// from a distinct voter. Certificates that were not built through
+    /// `QuorumCertifiedTxn::new` (e.g. received over the wire) may contain
+    /// duplicate voters, in which case they are rejected outright.
+    ///
+    /// Voters are told apart by `farmer_node_id`, which no vote signature
+    /// covers, so this is only a structural check. Use
+    /// `SignerEngine::verify_certified_txn` to authenticate the votes.
+    pub fn verify_threshold(&self, threshold: usize) -> crate::Result<()> {
+        let mut voters = HashSet::new();
+
+        for vote in self.votes.iter() {
+            if !voters.insert(vote.farmer_node_id) {
+                return Err(crate::Error::Other(format!(
+                    "duplicate vote receipt from farmer node {}",
+                    vote.farmer_node_id
+                )));
+            }
+        }
+
+        if voters.len() < threshold {
+            return Err(crate::Error::Other(format!(
+                "not enough votes to reach threshold: {} < {}",
+                voters.len(),
+                threshold
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn txn(&self) -> TransactionKind {
         self.txn.clone()
     }
@@ -129,6 +164,18 @@ impl QuorumCertifiedTxn {
     }
 }
 
+/// Keeps the first valid receipt cast by each voter, so that a single
+/// farmer can never be counted more than once towards a threshold.
+fn dedup_vote_receipts(votes: Vec<VoteReceipt>) -> Vec<VoteReceipt> {
+    let mut voters = HashSet::new();
+
+    votes
+        .into_iter()
+        .filter(|vote| !vote.signature.is_empty())
+        .filter(|vote| voters.insert(vote.farmer_node_id))
+        .collect()
+}
+
 pub type RpcTransactionDigest = String;
 
 #[derive(Debug, Default, Clone, Hash, Deserialize, Serialize, Eq, PartialEq)]
@@ -222,4 +269,61 @@ mod tests {
 
         assert_eq!(txn_digest, txn_digest_recovered);
     }
+
+    fn vote_receipt(farmer_node_id: NodeIdx, signature: &[u8]) -> VoteReceipt {
+        VoteReceipt {
+            farmer_id: vec![farmer_node_id as u8],
+            farmer_node_id,
+            signature: signature.to_vec(),
+        }
+    }
+
+    #[test]
+    fn quorum_certified_txn_dedups_votes_by_voter() {
+        let votes = vec![
+            vote_receipt(1, &[1]),
+            vote_receipt(1, &[2]),
+            vote_receipt(2, &[]),
+            vote_receipt(2, &[3]),
+            vote_receipt(3, &[4]),
+        ];
+
+        let certified_txn = QuorumCertifiedTxn::new(
+            vec![1],
+            votes,
+            TransactionKind::default(),
+            vec![0],
+            true,
+        );
+
+        assert_eq!(
+            certified_txn.votes(),
+            &[
+                vote_receipt(1, &[1]),
+                vote_receipt(2, &[3]),
+                vote_receipt(3, &[4]),
+            ]
+        );
+        assert!(certified_txn.verify_threshold(3).is_ok());
+        assert!(certified_txn.verify_threshold(4).is_err());
+    }
+
+    #[test]
+    fn quorum_certified_txn_with_duplicate_voters_fails_threshold_check() {
+        // NOTE: bypasses `QuorumCertifiedTxn::new`, like a certificate
+        // deserialized from a peer would
+        let certified_txn = QuorumCertifiedTxn {
+            sender_farmer_id: vec![1],
+            votes: vec![
+                vote_receipt(1, &[1]),
+                vote_receipt(1, &[2]),
+                vote_receipt(2, &[3]),
+            ],
+            txn: TransactionKind::default(),
+            signature: vec![0],
+            is_txn_valid: true,
+        };
+
+        assert!(certified_txn.verify_threshold(2).is_err());
+    }
 }
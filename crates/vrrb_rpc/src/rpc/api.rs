@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 
 use block::block::Block;
-use block::ClaimHash;
+use block::{ChainHeadSummary, ClaimHash};
 use jsonrpsee::{proc_macros::rpc, types::ErrorObjectOwned as RpseeError};
 use primitives::{Address, NodeType, Round};
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
 use storage::vrrbdb::Claims;
 use vrrb_config::QuorumMembershipConfig;
-use vrrb_core::account::Account;
+use vrrb_core::account::{Account, AccountNonce};
 use vrrb_core::node_health_report::NodeHealthReport;
 use vrrb_core::transactions::{
     RpcTransactionDigest, Token, Transaction, TransactionKind, TxAmount, TxNonce, TxTimestamp,
@@ -60,6 +60,15 @@ impl From<TransactionKind> for RpcTransactionRecord {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcAccountNonce {
+    /// Nonce of the account in the confirmed state
+    pub nonce: AccountNonce,
+    /// Nonce to use for the next transaction sent by the account, accounting
+    /// for its transactions still pending in the mempool
+    pub next_nonce: AccountNonce,
+}
+
 #[rpc(server, client, namespace = "state")]
 #[async_trait]
 pub trait RpcApi {
@@ -96,12 +105,14 @@ pub trait RpcApi {
     #[method(name = "createAccount")]
     async fn create_account(&self, address: Address, account: Account) -> Result<(), RpseeError>;
 
-    #[method(name = "updateAccount")]
-    async fn update_account(&self, account: Account) -> Result<(), RpseeError>;
-
     #[method(name = "getAccount")]
     async fn get_account(&self, address: Address) -> Result<Account, RpseeError>;
 
+    /// Returns the current nonce of an account along with the nonce its next
+    /// transaction should carry
+    #[method(name = "getAccountNonce")]
+    async fn get_account_nonce(&self, address: Address) -> Result<RpcAccountNonce, RpseeError>;
+
     #[method(name = "faucetDrip")]
     async fn faucet_drip(&self, address: Address) -> Result<(), RpseeError>;
 
@@ -140,4 +151,27 @@ pub trait RpcApi {
 
     #[method(name = "getLastBlock")]
     async fn get_last_block(&self) -> Result<Option<Block>, RpseeError>;
+
+    /// Returns the hash, height and timestamp of the last confirmed block
+    /// along with the roots of the state, transaction and claim stores
+    #[method(name = "getChainHeadSummary")]
+    async fn get_chain_head_summary(&self) -> Result<ChainHeadSummary, RpseeError>;
+}
+
+/// Operator only methods. These are never served by the public JSON-RPC
+/// server, only by the admin server bound to a loopback address, see
+/// `JsonRpcServer::run_admin`.
+#[rpc(server, client, namespace = "admin")]
+#[async_trait]
+pub trait AdminRpcApi {
+    /// Turns maintenance mode on or off. While enabled, the node keeps
+    /// applying blocks but neither produces blocks nor signs for its quorum
+    #[method(name = "setMaintenanceMode")]
+    async fn set_maintenance_mode(&self, enabled: bool) -> Result<(), RpseeError>;
+
+    /// Requests an update of an existing account's storage, package address,
+    /// digests or nonce. Balances only change through transactions, so
+    /// requests changing an account's credits or debits are rejected
+    #[method(name = "updateAccount")]
+    async fn update_account(&self, account: Account) -> Result<(), RpseeError>;
 }
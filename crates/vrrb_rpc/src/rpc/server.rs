@@ -1,20 +1,32 @@
+use block::ChainHeadReadHandle;
 use events::{EventPublisher, DEFAULT_BUFFER};
 use jsonrpsee::server::{ServerBuilder, ServerHandle};
 use mempool::{LeftRightMempool, MempoolReadHandleFactory};
 use primitives::NodeType;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+use storage::vrrbdb::{
+    StateQueryHandle, VrrbDb, VrrbDbConfig, VrrbDbReadHandle, DEFAULT_STATE_QUERY_CONCURRENCY,
+};
 use tokio::sync::mpsc::channel;
 
-use crate::rpc::{api::RpcApiServer, server_impl::RpcServerImpl};
+use crate::rpc::{
+    api::{AdminRpcApiServer, RpcApiServer},
+    server_impl::{AdminRpcServerImpl, RpcServerImpl},
+};
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcServerConfig {
     pub address: SocketAddr,
     pub vrrbdb_read_handle: VrrbDbReadHandle,
+    /// Serves account queries off the async runtime
+    pub state_query_handle: StateQueryHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub chain_head_read_handle: ChainHeadReadHandle,
     pub node_type: NodeType,
     pub events_tx: EventPublisher,
+    /// Address of the admin server serving `AdminRpcApi`. It must be a
+    /// loopback address, and the admin server is not started when unset
+    pub admin_address: Option<SocketAddr>,
 }
 
 #[derive(Debug)]
@@ -28,7 +40,9 @@ impl JsonRpcServer {
             node_type: config.node_type,
             events_tx: config.events_tx.clone(),
             vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
+            state_query_handle: config.state_query_handle.clone(),
             mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            chain_head_read_handle: config.chain_head_read_handle.clone(),
         };
 
         let addr = server.local_addr()?;
@@ -39,6 +53,35 @@ impl JsonRpcServer {
         // You may use the `ServerHandle` to shut it down or manage it yourself.
         Ok((handle, addr))
     }
+
+    /// Starts the admin server on `config.admin_address`, if set. Admin
+    /// methods change how the node operates and are not authenticated, so
+    /// they are only served on loopback addresses, reachable by operators
+    /// of the host the node runs on.
+    pub async fn run_admin(
+        config: &JsonRpcServerConfig,
+    ) -> anyhow::Result<Option<(ServerHandle, SocketAddr)>> {
+        let Some(admin_address) = config.admin_address else {
+            return Ok(None);
+        };
+
+        if !admin_address.ip().is_loopback() {
+            anyhow::bail!(
+                "admin JSON-RPC server must bind to a loopback address, got {admin_address}"
+            );
+        }
+
+        let server = ServerBuilder::default().build(admin_address).await?;
+
+        let server_impl = AdminRpcServerImpl {
+            events_tx: config.events_tx.clone(),
+        };
+
+        let addr = server.local_addr()?;
+        let handle = server.start(server_impl.into_rpc());
+
+        Ok(Some((handle, addr)))
+    }
 }
 
 impl Default for JsonRpcServerConfig {
@@ -53,6 +96,8 @@ impl Default for JsonRpcServerConfig {
 
         let vrrbdb = VrrbDb::new(vrrbdb_config);
         let vrrbdb_read_handle = vrrbdb.read_handle();
+        let state_query_handle =
+            StateQueryHandle::new(vrrbdb_read_handle.clone(), DEFAULT_STATE_QUERY_CONCURRENCY);
 
         let mempool = LeftRightMempool::default();
         let mempool_read_handle_factory = mempool.factory();
@@ -63,9 +108,12 @@ impl Default for JsonRpcServerConfig {
         JsonRpcServerConfig {
             address,
             vrrbdb_read_handle,
+            state_query_handle,
             mempool_read_handle_factory,
+            chain_head_read_handle: ChainHeadReadHandle::new(),
             node_type,
             events_tx,
+            admin_address: None,
         }
     }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashMap, str::FromStr};
 
 use async_trait::async_trait;
 use block::block::Block;
-use block::ClaimHash;
+use block::{ChainHeadReadHandle, ChainHeadSummary, ClaimHash};
 use events::{Event, EventPublisher};
 use jsonrpsee::types::{
     error::{INTERNAL_ERROR_CODE, PARSE_ERROR_CODE},
@@ -12,26 +12,31 @@ use mempool::MempoolReadHandleFactory;
 use primitives::{Address, NodeType, Round};
 use secp256k1::{Message, SecretKey};
 use sha2::{Digest, Sha256};
-use storage::vrrbdb::{Claims, VrrbDbReadHandle};
+use storage::vrrbdb::{Claims, StateQueryHandle, VrrbDbReadHandle};
 use telemetry::{debug, error};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::node_health_report::NodeHealthReport;
 use vrrb_core::transactions::{
     RpcTransactionDigest, Transaction, TransactionDigest, TransactionKind,
 };
-use vrrb_core::{account::Account, serde_helpers::encode_to_binary};
+use vrrb_core::{
+    account::{Account, AccountNonce},
+    serde_helpers::encode_to_binary,
+};
 
 use super::{
-    api::{FullMempoolSnapshot, RpcApiServer},
+    api::{AdminRpcApiServer, FullMempoolSnapshot, RpcApiServer},
     SignOpts,
 };
-use crate::rpc::api::{FullStateSnapshot, RpcTransactionRecord};
+use crate::rpc::api::{FullStateSnapshot, RpcAccountNonce, RpcTransactionRecord};
 
 #[derive(Debug, Clone)]
 pub struct RpcServerImpl {
     pub node_type: NodeType,
     pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub state_query_handle: StateQueryHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub chain_head_read_handle: ChainHeadReadHandle,
     pub events_tx: EventPublisher,
 }
 
@@ -177,56 +182,50 @@ impl RpcApiServer for RpcServerImpl {
         Ok(())
     }
 
-    async fn update_account(&self, account: Account) -> Result<(), RpseeError> {
-        debug!("Received an updateAccount RPC request");
-
-        let account_bytes = encode_to_binary(&account)
-            .map_err(|e| RpseeError::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>))?;
+    async fn get_account(&self, address: Address) -> Result<Account, RpseeError> {
+        telemetry::info!("retrieving account {address}");
 
-        let addr = Address::from_str(account.hash())
-            .map_err(|e| RpseeError::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>))?;
+        let account = self
+            .state_query_handle
+            .account(address.clone())
+            .await
+            .map_err(|e| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("unable to find account {address}: {e}"),
+                    None::<()>,
+                )
+            })?;
 
-        let event = Event::AccountUpdateRequested((addr, account_bytes));
+        debug!("Received getAccount RPC Request: {account:?}");
 
-        self.events_tx.send(event.into()).await.map_err(|e| {
-            error!("could not update account: {e}");
-            RpseeError::owned(
-                INTERNAL_ERROR_CODE,
-                format!("could not update account: {e}"),
-                None::<()>,
-            )
-        })?;
-
-        Ok(())
+        Ok(account)
     }
 
-    async fn get_account(&self, address: Address) -> Result<Account, RpseeError> {
-        telemetry::info!("retrieving account {address}");
-
-        let values = self.vrrbdb_read_handle.state_store_values().map_err(|e| {
-            RpseeError::owned(
-                INTERNAL_ERROR_CODE,
-                format!("failed to read values: {e}"),
-                None::<()>,
-            )
-        })?;
-
-        let value = values.get(&address);
+    async fn get_account_nonce(&self, address: Address) -> Result<RpcAccountNonce, RpseeError> {
+        let account = self
+            .state_query_handle
+            .account(address.clone())
+            .await
+            .map_err(|e| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("unable to find account {address}: {e}"),
+                    None::<()>,
+                )
+            })?;
 
-        debug!("Received getAccount RPC Request: {value:?}");
+        let nonce = account.nonce();
+        let next_nonce = self
+            .mempool_read_handle_factory
+            .values()
+            .iter()
+            .filter(|txn| txn.sender_address() == address)
+            .map(|txn| txn.nonce())
+            .fold(nonce, AccountNonce::max)
+            + 1;
 
-        match value {
-            Some(account) => return Ok(account.to_owned()),
-            None => {
-                return Err({
-                    RpseeError::owned(
-                        INTERNAL_ERROR_CODE,
-                        format!("unable to find account: {value:?}"),
-                        None::<()>,
-                    )
-                })
-            }
-        }
+        Ok(RpcAccountNonce { nonce, next_nonce })
     }
 
     async fn faucet_drip(&self, _address: Address) -> Result<(), RpseeError> {
@@ -358,4 +357,57 @@ impl RpcApiServer for RpcServerImpl {
         error!("getLastBlock is not implemented");
         Ok(None)
     }
+
+    async fn get_chain_head_summary(&self) -> Result<ChainHeadSummary, RpseeError> {
+        Ok(self.chain_head_read_handle.summary())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdminRpcServerImpl {
+    pub events_tx: EventPublisher,
+}
+
+#[async_trait]
+impl AdminRpcApiServer for AdminRpcServerImpl {
+    async fn set_maintenance_mode(&self, enabled: bool) -> Result<(), RpseeError> {
+        self.events_tx
+            .send(Event::MaintenanceModeRequested(enabled).into())
+            .await
+            .map_err(|e| {
+                error!("could not set maintenance mode: {e}");
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("could not set maintenance mode: {e}"),
+                    None::<()>,
+                )
+            })?;
+
+        telemetry::info!("requested maintenance mode: {enabled}");
+
+        Ok(())
+    }
+
+    async fn update_account(&self, account: Account) -> Result<(), RpseeError> {
+        debug!("Received an updateAccount RPC request");
+
+        let account_bytes = encode_to_binary(&account)
+            .map_err(|e| RpseeError::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>))?;
+
+        let addr = Address::from_str(account.hash())
+            .map_err(|e| RpseeError::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>))?;
+
+        let event = Event::AccountUpdateRequested((addr, account_bytes));
+
+        self.events_tx.send(event.into()).await.map_err(|e| {
+            error!("could not update account: {e}");
+            RpseeError::owned(
+                INTERNAL_ERROR_CODE,
+                format!("could not update account: {e}"),
+                None::<()>,
+            )
+        })?;
+
+        Ok(())
+    }
 }
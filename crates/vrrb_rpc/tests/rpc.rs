@@ -1,13 +1,19 @@
 use std::{collections::HashMap, net::SocketAddr};
 
-use events::{EventMessage, DEFAULT_BUFFER};
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use mempool::LeftRightMempool;
 use primitives::{generate_mock_account_keypair, Address};
 use secp256k1::Message;
 use storage::storage_utils::remove_vrrb_data_dir;
+use storage::vrrbdb::{
+    FromTxn, IntoUpdates, StateQueryHandle, UpdateArgs, VrrbDb, VrrbDbConfig,
+    DEFAULT_STATE_QUERY_CONCURRENCY,
+};
 use tokio::sync::mpsc::channel;
+use vrrb_core::account::Account;
 use vrrb_core::transactions::{generate_transfer_digest_vec, Token, TransactionKind};
 use vrrb_rpc::rpc::{
-    api::{RpcApiClient, RpcTransactionRecord},
+    api::{AdminRpcApiClient, RpcApiClient, RpcTransactionRecord},
     client::create_client,
     *,
 };
@@ -96,3 +102,220 @@ async fn server_can_publish_transactions_to_be_created() {
 
     handle.stop().expect("Unable to stop server");
 }
+
+fn transfer_with_nonce(
+    secret_key: &secp256k1::SecretKey,
+    public_key: secp256k1::PublicKey,
+    receiver_address: Address,
+    nonce: u128,
+) -> TransactionKind {
+    let sender_address = Address::new(public_key);
+    let digest = generate_transfer_digest_vec(
+        0,
+        sender_address.to_string(),
+        public_key,
+        receiver_address.to_string(),
+        Token::default(),
+        10,
+        nonce,
+    );
+
+    type H = secp256k1::hashes::sha256::Hash;
+    let msg = Message::from_hashed_data::<H>(&digest);
+
+    TransactionKind::transfer_builder()
+        .timestamp(0)
+        .sender_address(sender_address)
+        .sender_public_key(public_key)
+        .receiver_address(receiver_address)
+        .amount(10)
+        .signature(secret_key.sign_ecdsa(msg))
+        .nonce(nonce)
+        .build_kind()
+        .expect("failed to build transfer transaction")
+}
+
+/// Creates a funded account for a random keypair.
+fn funded_account(vrrbdb: &mut VrrbDb) -> (secp256k1::SecretKey, secp256k1::PublicKey) {
+    let (secret_key, public_key) = generate_mock_account_keypair();
+    let address = Address::new(public_key);
+
+    vrrbdb
+        .insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+    vrrbdb
+        .update_account(UpdateArgs {
+            address,
+            nonce: None,
+            credits: Some(1_000),
+            debits: None,
+            storage: None,
+            package_address: None,
+            digests: None,
+        })
+        .unwrap();
+
+    (secret_key, public_key)
+}
+
+async fn run_server(
+    vrrbdb: &VrrbDb,
+    mempool: &LeftRightMempool,
+) -> (jsonrpsee::server::ServerHandle, SocketAddr) {
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        vrrbdb_read_handle: vrrbdb.read_handle(),
+        state_query_handle: StateQueryHandle::new(
+            vrrbdb.read_handle(),
+            DEFAULT_STATE_QUERY_CONCURRENCY,
+        ),
+        mempool_read_handle_factory: mempool.factory(),
+        ..Default::default()
+    };
+
+    JsonRpcServer::run(&json_rpc_server_config).await.unwrap()
+}
+
+fn temp_vrrbdb() -> VrrbDb {
+    let path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    VrrbDb::new(VrrbDbConfig::default().with_path(path))
+}
+
+#[tokio::test]
+async fn account_nonce_reflects_applied_transactions() {
+    let mut vrrbdb = temp_vrrbdb();
+    let mempool = LeftRightMempool::default();
+    let (secret_key, public_key) = funded_account(&mut vrrbdb);
+    let address = Address::new(public_key);
+    let (_, recv_public_key) = generate_mock_account_keypair();
+
+    let (handle, rpc_server_address) = run_server(&vrrbdb, &mempool).await;
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let before = client.get_account_nonce(address.clone()).await.unwrap();
+    assert_eq!(before.nonce, 0);
+    assert_eq!(before.next_nonce, 1);
+
+    let txn = transfer_with_nonce(&secret_key, public_key, Address::new(recv_public_key), 1);
+    let sender_update = IntoUpdates::from_txn(txn).sender_update;
+    vrrbdb.update_account(sender_update.into()).unwrap();
+
+    let after = client.get_account_nonce(address).await.unwrap();
+    assert_eq!(after.nonce, 1);
+    assert_eq!(after.next_nonce, 2);
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn pending_transactions_raise_next_account_nonce() {
+    let mut vrrbdb = temp_vrrbdb();
+    let mut mempool = LeftRightMempool::default();
+    let (secret_key, public_key) = funded_account(&mut vrrbdb);
+    let address = Address::new(public_key);
+    let (other_secret_key, other_public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+    let recv_address = Address::new(recv_public_key);
+
+    for nonce in 1..=3 {
+        mempool
+            .insert(transfer_with_nonce(
+                &secret_key,
+                public_key,
+                recv_address.clone(),
+                nonce,
+            ))
+            .unwrap();
+    }
+    mempool
+        .insert(transfer_with_nonce(
+            &other_secret_key,
+            other_public_key,
+            recv_address,
+            9,
+        ))
+        .unwrap();
+
+    let (handle, rpc_server_address) = run_server(&vrrbdb, &mempool).await;
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let nonce = client.get_account_nonce(address).await.unwrap();
+    assert_eq!(nonce.nonce, 0);
+    assert_eq!(nonce.next_nonce, 4);
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn maintenance_mode_is_only_served_by_the_admin_server() {
+    let (events_tx, mut events_rx) = channel::<EventMessage>(DEFAULT_BUFFER);
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        events_tx,
+        admin_address: Some("127.0.0.1:0".parse().unwrap()),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+    let (admin_handle, admin_server_address) = JsonRpcServer::run_admin(&json_rpc_server_config)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let client = create_client(rpc_server_address).await.unwrap();
+    assert!(client.set_maintenance_mode(true).await.is_err());
+    assert!(events_rx.try_recv().is_err());
+
+    let admin_client = create_client(admin_server_address).await.unwrap();
+    admin_client.set_maintenance_mode(true).await.unwrap();
+    let event: Event = events_rx.recv().await.unwrap().into();
+    assert_eq!(event, Event::MaintenanceModeRequested(true));
+
+    handle.stop().expect("Unable to stop server");
+    admin_handle.stop().expect("Unable to stop admin server");
+}
+
+#[tokio::test]
+async fn account_updates_are_not_served_by_the_public_server() {
+    let (events_tx, mut events_rx) = channel::<EventMessage>(DEFAULT_BUFFER);
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        events_tx,
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let (_, public_key) = generate_mock_account_keypair();
+    let mut account = Account::new(Address::new(public_key));
+    account
+        .update_field(vrrb_core::account::AccountField::Credits(1_000_000))
+        .unwrap();
+
+    assert!(client.update_account(account).await.is_err());
+    assert!(events_rx.try_recv().is_err());
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn admin_server_refuses_non_loopback_addresses() {
+    let json_rpc_server_config = JsonRpcServerConfig {
+        admin_address: Some("0.0.0.0:0".parse().unwrap()),
+        ..Default::default()
+    };
+
+    assert!(JsonRpcServer::run_admin(&json_rpc_server_config)
+        .await
+        .is_err());
+
+    let json_rpc_server_config = JsonRpcServerConfig::default();
+
+    assert!(JsonRpcServer::run_admin(&json_rpc_server_config)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -1,10 +1,15 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use telemetry::info;
 use wasm_runtime::{
     metering::{cost_function, MeteringConfig},
+    module_cache::ModuleCache,
     wasm_runtime::WasmRuntime,
 };
 use wasmer::{Cranelift, Target};
@@ -21,10 +26,27 @@ pub struct ExecuteOpts {
     /// multiple times.
     #[clap(short, long, value_parser, value_name = "KEY=VALUE")]
     pub env: Vec<String>,
+    /// A dotenv-style file of KEY=VALUE lines to pass to the running WASM
+    /// module as environment variables. Variables given with --env take
+    /// precedence over the ones in the file.
+    #[clap(long, value_parser, value_name = "FILE")]
+    pub env_file: Option<PathBuf>,
     /// The initial limit of credits that the WASM module's meter will use to track
     /// operation expenses.
     #[clap(short = 'l', long, value_parser, value_name = "UINT64")]
     pub meter_limit: u64,
+    /// The directory compiled WASM modules are cached in. Defaults to
+    /// $HOME/.cache/versatus-wasm-module-cache, or to a user specific
+    /// directory under the system's temporary directory when HOME is unset.
+    #[clap(long, value_parser, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+    /// Always compile the WASM module, bypassing the module cache.
+    #[clap(long)]
+    pub no_cache: bool,
+    /// The number of seconds the WASM module may run for before it is
+    /// aborted, regardless of its remaining meter credits.
+    #[clap(short, long, value_parser, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
     /// Remaining arguments (after '--') are passed to the WASM module command
     /// line.
     #[clap(last = true)]
@@ -57,24 +79,30 @@ pub fn run(opts: &ExecuteOpts) -> Result<()> {
         jsonfile
     );
 
-    let mut env_vars: HashMap<String, String> = HashMap::new();
-    for var in opts.env.iter() {
-        if let Some((key, value)) = var.split_once('=') {
-            env_vars.insert(key.to_string(), value.to_string());
-        }
-    }
+    let mut env_vars: HashMap<String, String> = match &opts.env_file {
+        Some(env_file) => load_env_file(env_file)?,
+        None => HashMap::new(),
+    };
+    env_vars.extend(parse_env_vars(&opts.env)?);
 
     let target = Target::default();
+    let metering_config = MeteringConfig::new(opts.meter_limit, cost_function);
+    let wasm = if opts.no_cache {
+        WasmRuntime::new::<Cranelift>(&target, &wasm_bytes, metering_config)?
+    } else {
+        let cache = opts
+            .cache_dir
+            .as_ref()
+            .map(ModuleCache::new)
+            .unwrap_or_default();
+        WasmRuntime::new_with_cache::<Cranelift>(&target, &wasm_bytes, metering_config, &cache)?
+    };
     // Execute the WASM module.
-    let mut wasm = WasmRuntime::new::<Cranelift>(
-        &target,
-        &wasm_bytes,
-        MeteringConfig::new(opts.meter_limit, cost_function),
-    )?
-    .stdin(&json_data)
-    .env(&env_vars)
-    .args(&opts.args);
-    wasm.execute()?;
+    let mut wasm = wasm.stdin(&json_data).env(&env_vars).args(&opts.args);
+    if let Some(timeout) = opts.timeout {
+        wasm = wasm.with_timeout(Duration::from_secs(timeout));
+    }
+    let exec_result = wasm.execute();
 
     // Temporary output for user -- will eventually be more structured and both
     // human and machine readable.
@@ -83,5 +111,156 @@ pub fn run(opts: &ExecuteOpts) -> Result<()> {
         eprintln!("Contract errors: {}", &wasm.stderr());
     }
 
+    if let Some(report) = wasm.metering_report() {
+        eprintln!("Metering: {}", serde_json::to_string(&report)?);
+        if report.exhausted {
+            return Err(anyhow!(
+                "WASM module exhausted its meter limit of {} points.",
+                report.limit
+            ));
+        }
+    }
+    exec_result?;
+
     Ok(())
 }
+
+/// Parses `KEY=VALUE` entries into environment variables, failing with the
+/// list of entries that are not of that form.
+pub fn parse_env_vars(entries: &[String]) -> Result<HashMap<String, String>> {
+    let mut env_vars = HashMap::new();
+    let mut malformed = Vec::new();
+    for entry in entries.iter() {
+        match split_env_var(entry) {
+            Some((key, value)) => {
+                env_vars.insert(key, value);
+            }
+            None => malformed.push(format!("'{entry}'")),
+        }
+    }
+
+    if !malformed.is_empty() {
+        return Err(anyhow!(
+            "Malformed environment variables, expected KEY=VALUE: {}",
+            malformed.join(", ")
+        ));
+    }
+
+    Ok(env_vars)
+}
+
+/// Loads environment variables from a dotenv-style file. Blank lines and
+/// lines starting with '#' are skipped, an optional `export` prefix is
+/// allowed and values may be wrapped in single or double quotes.
+pub fn load_env_file(path: &Path) -> Result<HashMap<String, String>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| anyhow!("Failed to read env file {}: {err}", path.display()))?;
+
+    let mut env_vars = HashMap::new();
+    let mut malformed = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        match split_env_var(line) {
+            Some((key, value)) => {
+                env_vars.insert(key, unquote(&value).to_string());
+            }
+            None => malformed.push(format!("line {}: '{line}'", idx + 1)),
+        }
+    }
+
+    if !malformed.is_empty() {
+        return Err(anyhow!(
+            "Malformed entries in env file {}, expected KEY=VALUE: {}",
+            path.display(),
+            malformed.join(", ")
+        ));
+    }
+
+    Ok(env_vars)
+}
+
+fn split_env_var(entry: &str) -> Option<(String, String)> {
+    let (key, value) = entry.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_env_vars_are_reported() {
+        let entries = vec![
+            String::from("FOO=bar"),
+            String::from("FOOBAR"),
+            String::from("=baz"),
+        ];
+
+        let err = parse_env_vars(&entries).unwrap_err().to_string();
+
+        assert!(err.contains("'FOOBAR'"));
+        assert!(err.contains("'=baz'"));
+        assert!(!err.contains("FOO=bar"));
+    }
+
+    #[test]
+    fn env_vars_are_parsed() {
+        let entries = vec![String::from("FOO=bar"), String::from("EMPTY=")];
+
+        let env_vars = parse_env_vars(&entries).unwrap();
+
+        assert_eq!(env_vars.get("FOO").unwrap(), "bar");
+        assert_eq!(env_vars.get("EMPTY").unwrap(), "");
+    }
+
+    #[test]
+    fn env_file_is_loaded() {
+        let path = std::env::temp_dir().join(format!("wasm-cli-{}.env", std::process::id()));
+        std::fs::write(
+            &path,
+            "# contract settings\nFOO=bar\n\nexport GREETING=\"hello world\"\nURL=http://a/?b=c\n",
+        )
+        .unwrap();
+
+        let env_vars = load_env_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(env_vars.len(), 3);
+        assert_eq!(env_vars.get("FOO").unwrap(), "bar");
+        assert_eq!(env_vars.get("GREETING").unwrap(), "hello world");
+        assert_eq!(env_vars.get("URL").unwrap(), "http://a/?b=c");
+    }
+
+    #[test]
+    fn malformed_env_file_lines_are_reported() {
+        let path = std::env::temp_dir().join(format!("wasm-cli-bad-{}.env", std::process::id()));
+        std::fs::write(&path, "FOO=bar\nFOOBAR\n").unwrap();
+
+        let err = load_env_file(&path).unwrap_err().to_string();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(err.contains("line 2: 'FOOBAR'"));
+    }
+}
@@ -5,11 +5,29 @@ use clap::Parser;
 use wasm_loader::wasm_loader::WasmLoaderBuilder;
 use wasm_runtime::limiting_tunables::DEFAULT_PAGE_LIMIT;
 
+pub mod schema;
+
 #[derive(Parser, Debug)]
 pub struct ValidateOpts {
     /// The path to the WASM object file to load and validate
     #[clap(short, long, value_parser, value_name = "FILE")]
     wasm: PathBuf,
+    /// The path to a JSON file of inputs to check against the schema of the
+    /// contract function being called
+    #[clap(short, long, value_parser, value_name = "FILE", requires = "function")]
+    inputs: Option<PathBuf>,
+    /// The contract function the inputs are meant for, e.g. transfer
+    #[clap(short, long, value_parser, value_name = "NAME", requires = "inputs")]
+    function: Option<String>,
+    /// The kind of contract the function belongs to
+    #[clap(
+        short,
+        long,
+        value_parser,
+        value_name = "KIND",
+        default_value = "erc20"
+    )]
+    contract: String,
 }
 
 /// Constants for a currently support namespaces.
@@ -80,6 +98,32 @@ pub fn run(opts: &ValidateOpts) -> Result<()> {
         expected_to_run = false;
     }
 
+    if let (Some(inputs), Some(function)) = (&opts.inputs, &opts.function) {
+        let schema = schema::function_schema(&opts.contract, function)?;
+        let payload: serde_json::Value = serde_json::from_slice(
+            &std::fs::read(inputs)
+                .map_err(|e| anyhow::Error::msg(format!("Error reading inputs file: {}", e)))?,
+        )
+        .map_err(|e| anyhow::Error::msg(format!("Error parsing inputs file: {}", e)))?;
+
+        let errors = schema::validate(&schema, &payload);
+        if errors.is_empty() {
+            println!(
+                "Inputs conform to the schema of {} function {}",
+                opts.contract, function
+            );
+        } else {
+            println!(
+                "Inputs don't conform to the schema of {} function {}:",
+                opts.contract, function
+            );
+            for error in errors.iter() {
+                println!("  {}", error);
+            }
+            expected_to_run = false;
+        }
+    }
+
     if expected_to_run {
         println!("WASM module is expected to run under the Versatus runtime");
     } else {
@@ -0,0 +1,263 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+
+/// Schemas of the arguments taken by the functions of each supported
+/// contract kind, embedded so that validation needs no network access.
+const CONTRACT_SCHEMAS: &[(&str, &str)] = &[("erc20", include_str!("../../../schemas/erc20.json"))];
+
+/// A field of the payload that does not conform to the schema, along with
+/// the JSON pointer to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// Returns the schema of the inputs a contract expects when `function` of a
+/// `contract` kind contract is called, e.g. `transfer` of an `erc20`.
+///
+/// The arguments of the function are expected under
+/// `contractInput.functionInputs.<contract>.<function>`.
+pub fn function_schema(contract: &str, function: &str) -> Result<Value> {
+    let (_, schemas) = CONTRACT_SCHEMAS
+        .iter()
+        .find(|(kind, _)| *kind == contract)
+        .ok_or(anyhow!("No schemas are known for {contract} contracts"))?;
+
+    let mut schemas: Map<String, Value> = serde_json::from_str(schemas)?;
+    let arguments = schemas.remove(function).ok_or(anyhow!(
+        "{contract} contracts have no function named {function}"
+    ))?;
+
+    Ok(json!({
+        "type": "object",
+        "required": ["version", "accountAddr", "protocolInput", "contractInput"],
+        "properties": {
+            "version": { "type": "integer" },
+            "accountAddr": { "type": "string" },
+            "protocolInput": {
+                "type": "object",
+                "required": ["version", "blockHeight", "blockTime"],
+                "properties": {
+                    "version": { "type": "integer" },
+                    "blockHeight": { "type": "integer" },
+                    "blockTime": { "type": "integer" }
+                }
+            },
+            "contractInput": {
+                "type": "object",
+                "required": ["contractFn", "functionInputs"],
+                "properties": {
+                    "contractFn": { "type": "string", "enum": [function] },
+                    "functionInputs": {
+                        "type": "object",
+                        "required": [contract],
+                        "properties": {
+                            contract: {
+                                "type": "object",
+                                "required": [function],
+                                "properties": { function: arguments }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Checks `payload` against `schema`, returning every field that does not
+/// conform to it.
+///
+/// Only the subset of JSON schema used by the embedded schemas is supported:
+/// `type`, `enum`, `required`, `properties`, `additionalProperties`, `items`,
+/// `minLength` and `maxLength`.
+pub fn validate(schema: &Value, payload: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    validate_at(schema, payload, "", &mut errors);
+    errors
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<SchemaError>) {
+    let mut error = |message: String| {
+        errors.push(SchemaError {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        if !has_type(value, expected) {
+            error(format!("expected {expected}, found {}", type_name(value)));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            error(format!(
+                "expected one of {}, found {value}",
+                Value::from(allowed.clone())
+            ));
+        }
+    }
+
+    if let Some(string) = value.as_str() {
+        let len = string.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+            if len < min {
+                error(format!("expected at least {min} characters, found {len}"));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+            if len > max {
+                error(format!("expected at most {max} characters, found {len}"));
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    error(format!("missing required field '{field}'"));
+                }
+            }
+        }
+
+        for (field, field_value) in object.iter() {
+            let field_path = format!("{path}/{field}");
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(field_schema) => validate_at(field_schema, field_value, &field_path, errors),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    errors.push(SchemaError {
+                        path: field_path,
+                        message: String::from("unexpected field"),
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (idx, item) in array.iter().enumerate() {
+            validate_at(items, item, &format!("{path}/{idx}"), errors);
+        }
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        expected => type_name(value) == expected,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer_payload(arguments: Value) -> Value {
+        json!({
+            "version": 1,
+            "accountAddr": "0x0000000000000000000000000000000000000001",
+            "protocolInput": {
+                "version": 1,
+                "blockHeight": 42,
+                "blockTime": 1689897402
+            },
+            "contractInput": {
+                "contractFn": "transfer",
+                "functionInputs": {
+                    "erc20": {
+                        "transfer": arguments
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn valid_payload_conforms_to_schema() {
+        let schema = function_schema("erc20", "transfer").unwrap();
+        let payload = transfer_payload(json!({
+            "to": "0x0000000000000000000000000000000000000002",
+            "value": "0x64"
+        }));
+
+        assert!(validate(&schema, &payload).is_empty());
+    }
+
+    #[test]
+    fn missing_required_field_is_reported() {
+        let schema = function_schema("erc20", "transfer").unwrap();
+        let payload = transfer_payload(json!({
+            "to": "0x0000000000000000000000000000000000000002"
+        }));
+
+        assert_eq!(
+            validate(&schema, &payload),
+            vec![SchemaError {
+                path: String::from("/contractInput/functionInputs/erc20/transfer"),
+                message: String::from("missing required field 'value'"),
+            }]
+        );
+    }
+
+    #[test]
+    fn mistyped_and_unexpected_fields_are_reported() {
+        let schema = function_schema("erc20", "transfer").unwrap();
+        let payload = transfer_payload(json!({
+            "to": "0x0000000000000000000000000000000000000002",
+            "value": 100,
+            "memo": "rent"
+        }));
+
+        let errors: Vec<String> = validate(&schema, &payload)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains(&String::from(
+            "/contractInput/functionInputs/erc20/transfer/value: expected string, found number"
+        )));
+        assert!(errors.contains(&String::from(
+            "/contractInput/functionInputs/erc20/transfer/memo: unexpected field"
+        )));
+    }
+
+    #[test]
+    fn unknown_functions_have_no_schema() {
+        assert!(function_schema("erc20", "mint").is_err());
+        assert!(function_schema("erc721", "transfer").is_err());
+    }
+}
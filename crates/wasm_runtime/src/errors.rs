@@ -1,7 +1,6 @@
 use std::error::Error;
 use wasmer::RuntimeError;
-use wasmer::{CompileError, ExportError, FrameInfo, InstantiationError};
-use wasmer_vm::TrapCode;
+use wasmer::{CompileError, ExportError, FrameInfo, InstantiationError, TrapCode};
 use wasmer_wasix::{WasiError, WasiRuntimeError};
 
 #[derive(thiserror::Error, Debug)]
@@ -61,6 +60,15 @@ origin: {:?}",
 
     #[error("failed to build wasm runtime module: {0}")]
     ModuleBuildError(String),
+
+    #[error("failed to cache compiled wasm module: {0}")]
+    ModuleCacheError(String),
+
+    #[error("wasm module did not finish executing within {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("wasm module execution thread panicked")]
+    ExecutionPanicked,
 }
 
 impl WasmRuntimeError {
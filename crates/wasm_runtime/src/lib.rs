@@ -1,6 +1,7 @@
 pub mod errors;
 pub mod limiting_tunables;
 pub mod metering;
+pub mod module_cache;
 mod rust2wasm;
 pub mod wasm_runtime;
 
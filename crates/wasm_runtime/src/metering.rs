@@ -1,3 +1,4 @@
+use serde_derive::{Deserialize, Serialize};
 use wasmer::wasmparser::Operator;
 use wasmer_middlewares::Metering;
 
@@ -33,7 +34,24 @@ where
             cost_function,
         }
     }
+    /// Returns the number of points the meter starts out with.
+    pub fn initial_limit(&self) -> u64 {
+        self.initial_limit
+    }
     pub(crate) fn into_metering(self) -> Metering<F> {
         Metering::new(self.initial_limit, self.cost_function)
     }
 }
+
+/// Summary of the metering points spent by a WASM module execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeteringReport {
+    /// Points the meter started out with.
+    pub limit: u64,
+    /// Points spent by the module.
+    pub consumed: u64,
+    /// Points left on the meter after execution.
+    pub remaining: u64,
+    /// Whether the module ran out of points before finishing.
+    pub exhausted: bool,
+}
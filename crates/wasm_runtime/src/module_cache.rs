@@ -0,0 +1,151 @@
+//! On-disk cache of compiled WASM modules
+//!
+//! Compiling a WASM module with Cranelift dominates the time it takes to run
+//! small contracts. The cache keeps the serialized compiled artifact of every
+//! module it is handed, so that running the same bytes again only has to
+//! deserialize it.
+
+use std::{
+    fs::{DirBuilder, Permissions},
+    io::Write,
+    os::unix::fs::{DirBuilderExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use telemetry::debug;
+use tempfile::NamedTempFile;
+use wasmer::{wasmparser::Operator, Module, Store, Target};
+
+use crate::{errors::WasmRuntimeError, metering::MeteringConfig, wasm_runtime::RuntimeResult};
+
+/// Name of the directory, under the user's cache directory, used by
+/// `ModuleCache::default`.
+pub const DEFAULT_MODULE_CACHE_DIR: &str = "versatus-wasm-module-cache";
+
+/// Permissions of the cache directory. Loading a module trusts the artifact,
+/// so nobody but the current user may write to it.
+const MODULE_CACHE_DIR_MODE: u32 = 0o700;
+
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    dir: PathBuf,
+}
+
+impl Default for ModuleCache {
+    /// Uses `$HOME/.cache`, or a user specific directory under the system's
+    /// temporary directory when `HOME` is unset.
+    fn default() -> Self {
+        let dir = match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home)
+                .join(".cache")
+                .join(DEFAULT_MODULE_CACHE_DIR),
+            None => {
+                let user = std::env::var("USER").unwrap_or_default();
+                std::env::temp_dir().join(format!("{DEFAULT_MODULE_CACHE_DIR}-{user}"))
+            }
+        };
+
+        Self::new(dir)
+    }
+}
+
+impl ModuleCache {
+    /// Creates a cache storing compiled modules under `dir`.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Computes the key a compiled module is cached under.
+    ///
+    /// The key is the SHA-256 of the WASM bytes along with everything that
+    /// ends up in the compiled artifact: the target triple, the compiler
+    /// `C`, and the metering limit and cost function, since the metering
+    /// middleware compiles both into the module. Cost functions are told
+    /// apart by their type and the version of this crate.
+    pub fn key<C, F>(wasm_bytes: &[u8], target: &Target, metering: &MeteringConfig<F>) -> String
+    where
+        F: Fn(&Operator) -> u64 + Send + Sync,
+    {
+        let mut hasher = Sha256::new();
+        hasher.update(wasm_bytes);
+        hasher.update(target.triple().to_string());
+        hasher.update(std::any::type_name::<C>());
+        hasher.update(std::any::type_name::<F>());
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(metering.initial_limit().to_le_bytes());
+
+        hex::encode(hasher.finalize())
+    }
+
+    /// Loads the module cached under `key`, if any. Artifacts that fail to
+    /// deserialize, like ones written by a different wasmer version, are
+    /// treated as missing, and so is the whole cache when its directory is
+    /// not private to the current user.
+    pub fn load(&self, store: &Store, key: &str) -> Option<Module> {
+        let path = self.path_for(key);
+        if !path.exists() {
+            return None;
+        }
+
+        if let Err(err) = self.ensure_private_dir() {
+            debug!("Ignoring module cache {}: {err}", self.dir.display());
+            return None;
+        }
+
+        // SAFETY: deserializing executes whatever code the artifact holds,
+        // so it must come from a trusted source. The cache directory is only
+        // writable by the current user, see `ensure_private_dir`, hence the
+        // artifact was written either by `ModuleCache::save` or by that user.
+        match unsafe { Module::deserialize_from_file(store, &path) } {
+            Ok(module) => Some(module),
+            Err(err) => {
+                debug!("Ignoring unusable cached module {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Stores the compiled `module` under `key`.
+    pub fn save(&self, key: &str, module: &Module) -> RuntimeResult<()> {
+        self.ensure_private_dir()?;
+
+        let artifact = module
+            .serialize()
+            .map_err(|err| WasmRuntimeError::ModuleCacheError(err.to_string()))?;
+
+        // Write to a uniquely named temporary file first so that concurrent
+        // runs never load, or write to, a partially written artifact.
+        let mut tmp_file = NamedTempFile::new_in(&self.dir)?;
+        tmp_file.write_all(&artifact)?;
+        tmp_file
+            .persist(self.path_for(key))
+            .map_err(|err| err.error)?;
+
+        Ok(())
+    }
+
+    /// Creates the cache directory if needed and makes sure only the current
+    /// user can access it. Changing the permissions of a directory owned by
+    /// someone else fails, so a directory planted by another user is
+    /// rejected.
+    fn ensure_private_dir(&self) -> RuntimeResult<()> {
+        DirBuilder::new()
+            .recursive(true)
+            .mode(MODULE_CACHE_DIR_MODE)
+            .create(&self.dir)?;
+        std::fs::set_permissions(&self.dir, Permissions::from_mode(MODULE_CACHE_DIR_MODE))?;
+
+        Ok(())
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.wasmu"))
+    }
+}
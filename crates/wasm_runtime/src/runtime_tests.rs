@@ -1,11 +1,12 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 use serde_derive::{Deserialize, Serialize};
-use wasmer::{Cranelift, Target};
-use wasmer_vm::TrapCode;
+use wasmer::{wasmparser::Operator, CpuFeature, Cranelift, Target, TrapCode, Triple};
 
 use crate::{
+    errors::WasmRuntimeError,
     metering::{cost_function, MeteringConfig},
+    module_cache::ModuleCache,
     wasm_runtime::WasmRuntime,
 };
 
@@ -215,3 +216,227 @@ fn test_process_exit() {
         Some("Exit(ExitCode::2147483647)".to_string())
     );
 }
+
+// This test checks that a module running out of metering points is reported
+// as such.
+#[test]
+fn test_meter_exhaustion_is_reported() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let target = Target::default();
+    let metering_config = MeteringConfig::new(10, |_: &Operator| 1);
+    let mut runtime = WasmRuntime::new::<Cranelift>(&target, &wasm_bytes, metering_config)
+        .unwrap()
+        .stdin(&json_data);
+    assert!(runtime.metering_report().is_none());
+
+    assert!(runtime.execute().is_err());
+
+    let report = runtime.metering_report().unwrap();
+    assert!(report.exhausted);
+    assert_eq!(report.limit, 10);
+    assert_eq!(report.consumed, 10);
+    assert_eq!(report.remaining, 0);
+    assert!(runtime.meter_exhausted());
+}
+
+// This test checks that the points spent by a module that finished within its
+// meter limit are reported.
+#[test]
+fn test_metering_report_within_limit() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let target = Target::default();
+    let mut runtime = create_test_wasm_runtime(&target, &wasm_bytes)
+        .unwrap()
+        .stdin(&json_data);
+    runtime.execute().unwrap();
+
+    let report = runtime.metering_report().unwrap();
+    assert!(!report.exhausted);
+    assert_eq!(report.limit, TEST_SPENDING_LIMIT);
+    assert_eq!(report.consumed + report.remaining, TEST_SPENDING_LIMIT);
+    assert_eq!(runtime.consumed_points(), Some(report.consumed));
+}
+
+// This test checks that a module compiled once is loaded from the module
+// cache the next time the same bytes are run.
+#[test]
+fn test_module_cache_hit() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let cache_dir = std::env::temp_dir().join(format!(
+        "wasm-module-cache-test-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    let cache = ModuleCache::new(&cache_dir);
+    let target = Target::default();
+
+    let first = WasmRuntime::new_with_cache::<Cranelift>(
+        &target,
+        &wasm_bytes,
+        MeteringConfig::new(TEST_SPENDING_LIMIT, cost_function),
+        &cache,
+    )
+    .unwrap();
+    assert!(!first.from_cache());
+
+    let mut second = WasmRuntime::new_with_cache::<Cranelift>(
+        &target,
+        &wasm_bytes,
+        MeteringConfig::new(TEST_SPENDING_LIMIT, cost_function),
+        &cache,
+    )
+    .unwrap()
+    .stdin(&json_data);
+    assert!(second.from_cache());
+
+    second.execute().unwrap();
+    let out: TestOutput = serde_json::from_str(&second.stdout()).unwrap();
+    assert_eq!(out.stdin.version, TEST_VERSION);
+
+    std::fs::remove_dir_all(cache_dir).unwrap();
+}
+
+// This test checks that modules compiled for another target, or with other
+// metering, are cached under another key.
+#[test]
+fn test_module_cache_key_depends_on_target_and_metering() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let host = Target::default();
+    let other = Target::new(
+        Triple::from_str("aarch64-unknown-linux-gnu").unwrap(),
+        CpuFeature::set(),
+    );
+    let metering = MeteringConfig::new(TEST_SPENDING_LIMIT, cost_function);
+    let key = ModuleCache::key::<Cranelift, _>(&wasm_bytes, &host, &metering);
+
+    assert_eq!(
+        key,
+        ModuleCache::key::<Cranelift, _>(&wasm_bytes, &host, &metering)
+    );
+    assert_ne!(
+        key,
+        ModuleCache::key::<Cranelift, _>(&wasm_bytes, &other, &metering)
+    );
+    assert_ne!(
+        key,
+        ModuleCache::key::<Cranelift, _>(
+            &wasm_bytes,
+            &host,
+            &MeteringConfig::new(TEST_SPENDING_LIMIT + 1, cost_function)
+        )
+    );
+    assert_ne!(
+        key,
+        ModuleCache::key::<Cranelift, _>(
+            &wasm_bytes,
+            &host,
+            &MeteringConfig::new(TEST_SPENDING_LIMIT, |_: &Operator| 1)
+        )
+    );
+}
+
+// This test checks that the cache directory is only accessible by the
+// current user.
+#[test]
+fn test_module_cache_dir_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let cache_dir = std::env::temp_dir().join(format!(
+        "wasm-module-cache-test-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    std::fs::set_permissions(&cache_dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+    WasmRuntime::new_with_cache::<Cranelift>(
+        &Target::default(),
+        &wasm_bytes,
+        MeteringConfig::new(TEST_SPENDING_LIMIT, cost_function),
+        &ModuleCache::new(&cache_dir),
+    )
+    .unwrap();
+
+    let mode = std::fs::metadata(&cache_dir).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o700);
+
+    std::fs::remove_dir_all(cache_dir).unwrap();
+}
+
+// This test checks that a module that never finishes is abandoned once the
+// execution timeout elapses, well before its meter runs out, and that the
+// runtime can be executed again. The meter is finite so that the abandoned
+// executions eventually run out of points and their threads exit, rather than
+// spinning for the rest of the test run.
+#[test]
+fn test_execution_timeout() {
+    let wasm_bytes = br#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "_start")
+                (loop $spin (br $spin))))
+    "#;
+    let target = Target::default();
+    let timeout = std::time::Duration::from_millis(200);
+    let metering_config = MeteringConfig::new(2_000_000_000, |_: &Operator| 1);
+    let mut runtime = WasmRuntime::new::<Cranelift>(&target, wasm_bytes, metering_config)
+        .unwrap()
+        .with_timeout(timeout);
+
+    for _ in 0..2 {
+        let started = std::time::Instant::now();
+        let res = runtime.execute();
+
+        assert!(matches!(res, Err(WasmRuntimeError::Timeout(t)) if t == timeout));
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        assert!(runtime.remaining_points().is_none());
+    }
+}
+
+// This test checks that a module blocked reading stdin, which never reaches
+// EOF while the runtime holds it open, is released by the timeout.
+#[test]
+fn test_execution_timeout_releases_blocked_stdin() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let inputs = TestInput {
+        version: TEST_VERSION,
+        tx_id: TEST_TX_ID.to_string(),
+        last_block_time: TEST_LAST_BLOCK_TIME,
+    };
+    let target = Target::default();
+    let mut runtime = create_test_wasm_runtime(&target, &wasm_bytes)
+        .unwrap()
+        .stdin(&serde_json::to_vec_pretty(&inputs).unwrap())
+        .with_timeout(std::time::Duration::from_millis(200));
+
+    let started = std::time::Instant::now();
+    let _ = runtime.execute();
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+}
+
+// This test checks that modules finishing within the timeout behave as if no
+// timeout was set.
+#[test]
+fn test_execution_within_timeout() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let target = Target::default();
+    let mut runtime = create_test_wasm_runtime(&target, &wasm_bytes)
+        .unwrap()
+        .stdin(&json_data)
+        .with_timeout(std::time::Duration::from_secs(60));
+    runtime.execute().unwrap();
+
+    let out: TestOutput = serde_json::from_str(&runtime.stdout()).unwrap();
+    assert_eq!(out.stdin.tx_id, TEST_TX_ID);
+    assert!(runtime.metering_report().is_some());
+}
@@ -8,12 +8,17 @@
 use std::{
     collections::HashMap,
     io::{Read, Write},
-    sync::Arc,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    time::Duration,
 };
 
 use super::{
     limiting_tunables::{LimitingTunables, DEFAULT_PAGE_LIMIT},
-    metering::MeteringConfig,
+    metering::{MeteringConfig, MeteringReport},
+    module_cache::ModuleCache,
 };
 use telemetry::{debug, info, warn};
 use wasmer::{
@@ -38,6 +43,10 @@ pub struct WasmRuntime {
     stderr: String,
     args: Vec<String>,
     env: HashMap<String, String>,
+    meter_limit: u64,
+    remaining_points: Option<MeteringPoints>,
+    from_cache: bool,
+    timeout: Option<Duration>,
 }
 impl WasmRuntime {
     /// Creates a new WasmRuntime environment to execute the WASM binary passed
@@ -49,10 +58,39 @@ impl WasmRuntime {
         wasm_bytes: &[u8],
         metering_config: MeteringConfig<impl Fn(&Operator<'_>) -> u64 + Send + Sync + 'static>,
     ) -> RuntimeResult<Self>
+    where
+        C: Default + Into<Engine> + CompilerConfig,
+    {
+        Self::build::<C>(target, wasm_bytes, metering_config, None)
+    }
+
+    /// Like `new`, but loads the compiled module from `cache` when the same
+    /// bytes were already compiled for `target` with the same compiler and
+    /// metering, and stores it there otherwise. See `from_cache`.
+    pub fn new_with_cache<C>(
+        target: &Target,
+        wasm_bytes: &[u8],
+        metering_config: MeteringConfig<impl Fn(&Operator<'_>) -> u64 + Send + Sync + 'static>,
+        cache: &ModuleCache,
+    ) -> RuntimeResult<Self>
+    where
+        C: Default + Into<Engine> + CompilerConfig,
+    {
+        Self::build::<C>(target, wasm_bytes, metering_config, Some(cache))
+    }
+
+    fn build<C>(
+        target: &Target,
+        wasm_bytes: &[u8],
+        metering_config: MeteringConfig<impl Fn(&Operator<'_>) -> u64 + Send + Sync + 'static>,
+        cache: Option<&ModuleCache>,
+    ) -> RuntimeResult<Self>
     where
         C: Default + Into<Engine> + CompilerConfig,
     {
         // Setup Tunables
+        let meter_limit = metering_config.initial_limit();
+        let cache_key = ModuleCache::key::<C, _>(wasm_bytes, target, &metering_config);
         let mut compiler = C::default();
         compiler.push_middleware(Arc::new(metering_config.into_metering()));
         let base = BaseTunables::for_target(target);
@@ -63,20 +101,42 @@ impl WasmRuntime {
         // module
         let store = Store::new(engine);
 
-        debug!("Compiling {} bytes of WASM", wasm_bytes.len());
+        let cached_module = cache.and_then(|cache| cache.load(&store, &cache_key));
+        let from_cache = cached_module.is_some();
+
+        let module = match cached_module {
+            Some(module) => {
+                debug!("Loaded compiled WASM module {cache_key} from cache");
+                module
+            }
+            None => {
+                debug!("Compiling {} bytes of WASM", wasm_bytes.len());
+
+                // Compile module into in-memory store
+                let module = Module::new(&store, wasm_bytes)
+                    .map_err(|e| WasmRuntimeError::ModuleBuildError(format!("{e:?}")))?;
 
-        // Compile module into in-memory store
-        let module = Module::new(&store, wasm_bytes)
-            .map_err(|e| WasmRuntimeError::ModuleBuildError(format!("{e:?}")))?;
+                if let Some(cache) = cache {
+                    if let Err(err) = cache.save(&cache_key, &module) {
+                        warn!("Failed to cache compiled WASM module {cache_key}: {err}");
+                    }
+                }
+                module
+            }
+        };
         dbg!(&module);
         Ok(Self {
             store,
             module,
+            from_cache,
+            timeout: None,
             stdin: vec![],
             stdout: String::new(),
             stderr: String::new(),
             args: vec![],
             env: HashMap::new(),
+            meter_limit,
+            remaining_points: None,
         })
     }
 
@@ -92,6 +152,20 @@ impl WasmRuntime {
         self
     }
 
+    /// Bounds the wall-clock time the WASM module may run for, after which
+    /// `execute` returns `WasmRuntimeError::Timeout`.
+    ///
+    /// Metering bounds the number of instructions a module executes but not
+    /// the time it spends blocked in host functions, so both limits apply
+    /// and whichever is reached first ends the execution. A module with a
+    /// timeout runs on a thread of its own, which is abandoned once the
+    /// timeout elapses: its stdin is closed, but otherwise the thread keeps
+    /// running until the module runs out of metering points or returns.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Writes a vector of bytes to stdin for the WASM module on execution.
     pub fn stdin(mut self, input: &[u8]) -> Self {
         self.stdin = input.to_vec();
@@ -110,56 +184,164 @@ impl WasmRuntime {
         self.stderr.clone()
     }
 
+    /// Returns whether the compiled module was loaded from a `ModuleCache`
+    /// instead of being compiled.
+    pub fn from_cache(&self) -> bool {
+        self.from_cache
+    }
+
+    /// Returns the number of metering points the module started out with.
+    pub fn meter_limit(&self) -> u64 {
+        self.meter_limit
+    }
+
+    /// Returns the metering points left after the last execution, or `None`
+    /// if the module has not been executed yet.
+    pub fn remaining_points(&self) -> Option<u64> {
+        self.remaining_points.as_ref().map(|points| match points {
+            MeteringPoints::Remaining(points) => *points,
+            MeteringPoints::Exhausted => 0,
+        })
+    }
+
+    /// Returns the metering points spent by the last execution, or `None` if
+    /// the module has not been executed yet.
+    pub fn consumed_points(&self) -> Option<u64> {
+        self.remaining_points()
+            .map(|remaining| self.meter_limit.saturating_sub(remaining))
+    }
+
+    /// Returns whether the last execution ran out of metering points.
+    pub fn meter_exhausted(&self) -> bool {
+        matches!(self.remaining_points, Some(MeteringPoints::Exhausted))
+    }
+
+    /// Summarizes the metering points spent by the last execution, or `None`
+    /// if the module has not been executed yet.
+    pub fn metering_report(&self) -> Option<MeteringReport> {
+        let remaining = self.remaining_points()?;
+
+        Some(MeteringReport {
+            limit: self.meter_limit,
+            consumed: self.meter_limit.saturating_sub(remaining),
+            remaining,
+            exhausted: self.meter_exhausted(),
+        })
+    }
+
     /// Execute the compiled WASM module and retrieve the result.
     pub fn execute(&mut self) -> RuntimeResult<()> {
+        self.remaining_points = None;
         let (mut stdin, in_wasm) = Pipe::channel();
         let (out_wasm, mut stdout) = Pipe::channel();
         let (err_wasm, mut stderr) = Pipe::channel();
         stdin.write_all(&self.stdin)?;
         stdin.flush()?;
 
-        self.init_wasi_fn_env((in_wasm, out_wasm, err_wasm))?;
+        let pipes = (in_wasm, out_wasm, err_wasm);
+        match self.timeout {
+            Some(timeout) => self.execute_with_timeout(pipes, stdin, timeout)?,
+            None => init_wasi_fn_env(
+                &mut self.store,
+                &self.module,
+                &self.args,
+                &self.env,
+                pipes,
+                &mut self.remaining_points,
+            )?,
+        }
 
         stdout.read_to_string(&mut self.stdout)?;
         stderr.read_to_string(&mut self.stderr)?;
         Ok(())
     }
 
-    fn init_wasi_fn_env(
+    /// Runs the module on a new thread, in a store of its own created from
+    /// the runtime's engine, and waits at most `timeout` for it to finish.
+    /// The runtime's store is never handed to that thread, so the runtime
+    /// stays usable after abandoning a module that timed out.
+    fn execute_with_timeout(
         &mut self,
-        (in_wasm, out_wasm, err_wasm): (Pipe, Pipe, Pipe),
+        pipes: (Pipe, Pipe, Pipe),
+        stdin: Pipe,
+        timeout: Duration,
     ) -> RuntimeResult<()> {
-        let store = &mut self.store;
-        let module = &self.module;
-        let mut wasi_fn_env = WasiEnv::builder(MODULE_ARGV0)
-            .stdin(Box::new(in_wasm))
-            .stdout(Box::new(out_wasm))
-            .stderr(Box::new(err_wasm))
-            .args(Box::new(self.args.iter()))
-            .envs(Box::new(self.env.iter()))
-            .finalize(store)?;
-
-        let import_obj = wasi_fn_env.import_object(store, module)?;
-        let instance = Instance::new(store, module, &import_obj)?;
-
-        let mem_view = instance.exports.get_memory("memory")?.view(store);
-        telemetry::info!("Memory: {:?}", mem_view.size());
-
-        wasi_fn_env.initialize(store, instance.clone())?;
-        let start = instance.exports.get_function("_start")?;
-        let exec_result = start.call(store, &[]);
-
-        match get_remaining_points(store, &instance) {
-            MeteringPoints::Remaining(points) => {
-                info!("Remaining metering points: {points}");
+        let engine = self.store.engine().clone();
+        let module = self.module.clone();
+        let args = self.args.clone();
+        let env = self.env.clone();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        std::thread::Builder::new()
+            .name("wasm-module".to_string())
+            .spawn(move || {
+                let mut store = Store::new(engine);
+                let mut remaining_points = None;
+                let result = init_wasi_fn_env(
+                    &mut store,
+                    &module,
+                    &args,
+                    &env,
+                    pipes,
+                    &mut remaining_points,
+                );
+                // The receiver is gone if the execution timed out.
+                let _ = result_tx.send((result, remaining_points));
+            })?;
+
+        match result_rx.recv_timeout(timeout) {
+            Ok((result, remaining_points)) => {
+                self.remaining_points = remaining_points;
+                result
             }
-            MeteringPoints::Exhausted => {
-                warn!("Metering points were exhausted. If unreachable code was reached, try increasing the meter limit.");
+            Err(RecvTimeoutError::Timeout) => {
+                warn!("WASM module did not finish executing within {timeout:?}, abandoning it");
+                drop(stdin);
+                Err(WasmRuntimeError::Timeout(timeout))
             }
+            Err(RecvTimeoutError::Disconnected) => Err(WasmRuntimeError::ExecutionPanicked),
         }
+    }
+}
 
-        exec_result?;
-        wasi_fn_env.cleanup(store, None);
-        Ok(())
+fn init_wasi_fn_env(
+    store: &mut Store,
+    module: &Module,
+    args: &[String],
+    env: &HashMap<String, String>,
+    (in_wasm, out_wasm, err_wasm): (Pipe, Pipe, Pipe),
+    remaining_points: &mut Option<MeteringPoints>,
+) -> RuntimeResult<()> {
+    let mut wasi_fn_env = WasiEnv::builder(MODULE_ARGV0)
+        .stdin(Box::new(in_wasm))
+        .stdout(Box::new(out_wasm))
+        .stderr(Box::new(err_wasm))
+        .args(Box::new(args.iter()))
+        .envs(Box::new(env.iter()))
+        .finalize(store)?;
+
+    let import_obj = wasi_fn_env.import_object(store, module)?;
+    let instance = Instance::new(store, module, &import_obj)?;
+
+    let mem_view = instance.exports.get_memory("memory")?.view(store);
+    telemetry::info!("Memory: {:?}", mem_view.size());
+
+    wasi_fn_env.initialize(store, instance.clone())?;
+    let start = instance.exports.get_function("_start")?;
+    let exec_result = start.call(store, &[]);
+
+    let points = get_remaining_points(store, &instance);
+    match points {
+        MeteringPoints::Remaining(points) => {
+            info!("Remaining metering points: {points}");
+        }
+        MeteringPoints::Exhausted => {
+            warn!("Metering points were exhausted. If unreachable code was reached, try increasing the meter limit.");
+        }
     }
+    *remaining_points = Some(points);
+
+    exec_result?;
+    wasi_fn_env.cleanup(store, None);
+    Ok(())
 }